@@ -12,30 +12,131 @@
 // - MAJOR: Added parallel blockchain detection system with enhanced error reporting
 // - Added folder selection dialog for manual configuration discovery
 // - Added detection result structures for comprehensive status reporting
+// - Daemon connection test now uses the shared pooled HTTP client from rpc_client
+// - Added rpc_host field to Credentials (defaults to 127.0.0.1 for credentials saved before this field existed)
+// - Added opt-in rpc_use_tls / rpc_accept_invalid_certs flags for HTTPS RPC endpoints
+// - Added .cookie file authentication (CredentialsSource::Cookie) for daemons without static rpcuser/rpcpassword
+// - Credentials are registered with log_redaction when an endpoint is registered or tested
+// - Added test_connection command (getinfo with a 10s budget) reporting reachable/authenticated separately
+// - Added optional max_rpc_per_sec (client-side RPC rate limit for shared daemons, unset = unlimited)
+// - Credentials are stored as named daemon profiles (DaemonProfiles) with an active index; load_credentials /
+//   save_credentials use the active profile and a pre-profile credential is migrated into a "Default" profile.
+//   Added list_profiles / add_profile / activate_profile / delete_profile; clear_credentials removes every profile
+// - Commands return CommandError (CredentialError converts to CREDENTIALS / CREDENTIALS_NOT_FOUND)
 
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Runtime};
 use tauri_plugin_store::{StoreExt, Error as StoreError};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 use tokio::task::JoinSet;
 use std::time::Duration;
+use crate::CommandError;
 
 
 // Path for the store file relative to AppData directory
 const STORE_PATH: &str = "store.json";
 
-// Key used within the store file
+// Key used within the store file (single credential saved by older versions; migrated into a profile)
 const CREDENTIALS_KEY: &str = "verus_rpc_credentials";
 
+// Key holding the saved daemon profiles
+const PROFILES_KEY: &str = "verus_rpc_profiles";
+
+// Name of the profile created from a pre-profile credential or by save_credentials with no profiles yet
+const DEFAULT_PROFILE_NAME: &str = "Default";
+
 // Detection timeout in seconds
 const DETECTION_TIMEOUT_SECS: u64 = 8;
 
+// Cookie file written by the daemon when no static rpcuser/rpcpassword is configured
+const COOKIE_FILE_NAME: &str = ".cookie";
+
+// Overall time budget for test_connection
+const CONNECTION_TEST_TIMEOUT_SECS: u64 = 10;
+
+// Where the RPC user/password come from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CredentialsSource {
+    #[default]
+    Manual, // Static rpcuser/rpcpassword (config file or entered by hand)
+    Cookie, // <datadir>/.cookie, rotated by the daemon on every restart
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Credentials {
     pub rpc_user: String,
     pub rpc_pass: String,
     pub rpc_port: u16, // NEW: Port support for different blockchains
+    #[serde(default = "default_rpc_host")]
+    pub rpc_host: String, // NEW: Daemon host (LAN machine, container, ...)
+    #[serde(default)]
+    pub rpc_use_tls: bool, // NEW: Connect over https:// (e.g. TLS proxy in front of verusd)
+    #[serde(default)]
+    pub rpc_accept_invalid_certs: bool, // NEW: Accept self-signed certs (only honored with rpc_use_tls)
+    #[serde(default)]
+    pub credentials_source: CredentialsSource, // NEW: Manual vs .cookie authentication
+    #[serde(default)]
+    pub rpc_datadir: Option<String>, // NEW: Daemon data directory containing .cookie (cookie auth only)
+    #[serde(default)]
+    pub max_rpc_per_sec: Option<u32>, // NEW: Cap on outgoing RPC calls per second (None = unlimited)
+}
+
+impl Credentials {
+    // Transport options for this endpoint as understood by rpc_client
+    pub fn endpoint_options(&self) -> crate::rpc_client::EndpointOptions {
+        crate::rpc_client::EndpointOptions {
+            use_tls: self.rpc_use_tls,
+            accept_invalid_certs: self.rpc_use_tls && self.rpc_accept_invalid_certs,
+            cookie_path: self.cookie_path(),
+            max_rpc_per_sec: self.max_rpc_per_sec,
+        }
+    }
+
+    // Path of the .cookie file when using cookie authentication
+    pub fn cookie_path(&self) -> Option<PathBuf> {
+        match (self.credentials_source, &self.rpc_datadir) {
+            (CredentialsSource::Cookie, Some(datadir)) => Some(PathBuf::from(datadir).join(COOKIE_FILE_NAME)),
+            _ => None,
+        }
+    }
+
+    // Re-read rpc_user/rpc_pass from the .cookie file (no-op for manual credentials)
+    pub fn refresh_from_cookie(&mut self) -> Result<(), DiscoveryError> {
+        if let Some(cookie_path) = self.cookie_path() {
+            let (user, pass) = read_cookie_file(&cookie_path)?;
+            self.rpc_user = user;
+            self.rpc_pass = pass;
+        }
+        Ok(())
+    }
+
+    // Register this endpoint's transport options so make_rpc_call uses the right scheme/client,
+    // and its credentials so they are scrubbed from logs
+    pub fn register_endpoint(&self) {
+        crate::log_redaction::register_credentials(&self.rpc_user, &self.rpc_pass);
+        crate::rpc_client::set_endpoint_options(&self.rpc_host, self.rpc_port, self.endpoint_options());
+    }
+}
+
+// A named, saved daemon connection (e.g. "Mainnet", "Testnet")
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DaemonProfile {
+    pub name: String,
+    #[serde(flatten)]
+    pub credentials: Credentials,
+}
+
+// All saved profiles; load_credentials returns profiles[active_profile]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DaemonProfiles {
+    pub profiles: Vec<DaemonProfile>,
+    pub active_profile: usize,
+}
+
+// Default host for credentials that predate the rpc_host field
+fn default_rpc_host() -> String {
+    crate::rpc_client::DEFAULT_RPC_HOST.to_string()
 }
 
 // NEW: Blockchain configuration structure
@@ -202,6 +303,28 @@ pub fn get_standard_config_paths(blockchain_config: &BlockchainConfig) -> Vec<Pa
     paths
 }
 
+// NEW: Read and parse a daemon .cookie file ("user:pass" on a single line)
+pub fn read_cookie_file(cookie_path: &Path) -> Result<(String, String), DiscoveryError> {
+    let content = fs::read_to_string(cookie_path)
+        .map_err(|e| {
+            log::error!("Failed to read cookie file {:?}: {}", cookie_path, e);
+            match e.kind() {
+                std::io::ErrorKind::NotFound => DiscoveryError::NotFound,
+                std::io::ErrorKind::PermissionDenied => DiscoveryError::PermissionDenied,
+                _ => DiscoveryError::IoError(e.to_string()),
+            }
+        })?;
+
+    let line = content.lines().next().unwrap_or("").trim();
+    match line.split_once(':') {
+        Some((user, pass)) if !user.is_empty() && !pass.is_empty() => {
+            log::debug!("Read RPC cookie from {:?}", cookie_path);
+            Ok((user.to_string(), pass.to_string()))
+        }
+        _ => Err(DiscoveryError::ParseError("Cookie file is not in user:pass format".to_string())),
+    }
+}
+
 // NEW: Parse config file to extract credentials
 pub fn parse_config_file(file_path: &PathBuf) -> Result<Credentials, DiscoveryError> {
     log::info!("Attempting to parse config file: {:?}", file_path);
@@ -218,6 +341,7 @@ pub fn parse_config_file(file_path: &PathBuf) -> Result<Credentials, DiscoveryEr
     let mut rpc_user: Option<String> = None;
     let mut rpc_pass: Option<String> = None;
     let mut rpc_port: Option<u16> = None;
+    let mut rpc_host: Option<String> = None;
     
     for line in content.lines() {
         let line = line.trim();
@@ -242,6 +366,10 @@ pub fn parse_config_file(file_path: &PathBuf) -> Result<Credentials, DiscoveryEr
                     rpc_port = value.parse().ok();
                     log::debug!("Found rpcport in config: {:?}", rpc_port);
                 },
+                "rpchost" | "rpcconnect" => {
+                    rpc_host = Some(value.to_string()).filter(|h| !h.is_empty());
+                    log::debug!("Found {} in config: {:?}", key, rpc_host);
+                },
                 _ => {} // Ignore other config options
             }
         }
@@ -254,6 +382,32 @@ pub fn parse_config_file(file_path: &PathBuf) -> Result<Credentials, DiscoveryEr
                 rpc_user: user,
                 rpc_pass: pass,
                 rpc_port: port,
+                rpc_host: rpc_host.unwrap_or_else(default_rpc_host),
+                rpc_use_tls: false,
+                rpc_accept_invalid_certs: false,
+                credentials_source: CredentialsSource::Manual,
+                rpc_datadir: None,
+                max_rpc_per_sec: None,
+            })
+        },
+        (None, None, Some(port)) => {
+            // No static credentials - fall back to the daemon's .cookie file next to the config
+            let datadir = file_path.parent().map(Path::to_path_buf).unwrap_or_default();
+            let (user, pass) = read_cookie_file(&datadir.join(COOKIE_FILE_NAME)).map_err(|e| {
+                log::error!("Config file has no rpcuser/rpcpassword and no usable .cookie file: {}", e);
+                DiscoveryError::ParseError("Missing rpcuser/rpcpassword and no .cookie file found".to_string())
+            })?;
+            log::info!("Using .cookie authentication from {:?}. Port: {}", datadir, port);
+            Ok(Credentials {
+                rpc_user: user,
+                rpc_pass: pass,
+                rpc_port: port,
+                rpc_host: rpc_host.unwrap_or_else(default_rpc_host),
+                rpc_use_tls: false,
+                rpc_accept_invalid_certs: false,
+                credentials_source: CredentialsSource::Cookie,
+                rpc_datadir: Some(datadir.to_string_lossy().to_string()),
+                max_rpc_per_sec: None,
             })
         },
         (Some(_), Some(_), None) => {
@@ -269,7 +423,7 @@ pub fn parse_config_file(file_path: &PathBuf) -> Result<Credentials, DiscoveryEr
 
 // NEW: Parallel blockchain detection with timeout and error handling
 #[tauri::command]
-pub async fn detect_all_blockchains() -> Result<ParallelDetectionResult, DiscoveryError> {
+pub async fn detect_all_blockchains() -> Result<ParallelDetectionResult, CommandError> {
     let start_time = std::time::Instant::now();
     log::info!("Starting parallel blockchain detection for all supported chains");
     
@@ -438,14 +592,16 @@ async fn detect_single_blockchain(config: BlockchainConfig) -> BlockchainDetecti
 
 // NEW: Test daemon connection (simplified version for detection)
 async fn test_daemon_connection(credentials: &Credentials) -> Result<u64, String> {
-    use reqwest::{Client, StatusCode};
+    use reqwest::StatusCode;
     use serde_json::json;
     
-    let client = Client::new();
-    let url = format!("http://127.0.0.1:{}", credentials.rpc_port);
+    let options = credentials.endpoint_options();
+    let client = crate::rpc_client::http_client_for(&options);
+    let url = crate::rpc_client::build_rpc_url(&credentials.rpc_host, credentials.rpc_port, options.use_tls);
     
-    log::info!("Testing connection to {} with user: {} (pass length: {})", 
-               url, credentials.rpc_user, credentials.rpc_pass.len());
+    crate::log_redaction::register_credentials(&credentials.rpc_user, &credentials.rpc_pass);
+    log::info!("Testing connection to {} (user length: {}, pass length: {})",
+               url, credentials.rpc_user.len(), credentials.rpc_pass.len());
     
     let request_body = json!({
         "method": "getblockcount",
@@ -463,12 +619,21 @@ async fn test_daemon_connection(credentials: &Credentials) -> Result<u64, String
         .await
         .map_err(|e| {
             log::error!("HTTP request failed for {}: {}", url, e);
-            format!("HTTP request failed: {}", e)
+            if e.is_connect() {
+                format!("Connection refused - daemon may not be running: {}", e)
+            } else {
+                format!("HTTP request failed: {}", e)
+            }
         })?;
     
     let status = response.status();
     log::info!("HTTP response status: {}", status);
     
+    if status == StatusCode::UNAUTHORIZED {
+        log::warn!("Daemon rejected RPC credentials (401)");
+        return Err("Authentication failed - check rpcuser/rpcpassword".to_string());
+    }
+
     // Don't fail immediately on 500 errors - the daemon might be returning JSON error info
     if !status.is_success() && status != StatusCode::INTERNAL_SERVER_ERROR {
         log::warn!("Non-success status (non-500): {}", status);
@@ -523,7 +688,7 @@ async fn test_daemon_connection(credentials: &Credentials) -> Result<u64, String
 
 // NEW: Folder selection dialog command
 #[tauri::command]
-pub async fn select_folder_dialog<R: Runtime>(app: AppHandle<R>) -> Result<Option<String>, String> {
+pub async fn select_folder_dialog<R: Runtime>(app: AppHandle<R>) -> Result<Option<String>, CommandError> {
     log::info!("Opening folder selection dialog");
     
     use tauri_plugin_dialog::{DialogExt, FileDialogBuilder};
@@ -540,13 +705,13 @@ pub async fn select_folder_dialog<R: Runtime>(app: AppHandle<R>) -> Result<Optio
     
     match rx.await {
         Ok(result) => Ok(result),
-        Err(_) => Err("Dialog was cancelled or failed".to_string()),
+        Err(_) => Err(CommandError::new("DIALOG_FAILED", "Dialog was cancelled or failed")),
     }
 }
 
 // NEW: Detect blockchain from custom path
 #[tauri::command]
-pub async fn detect_blockchain_from_path(path: String) -> Result<ParallelDetectionResult, DiscoveryError> {
+pub async fn detect_blockchain_from_path(path: String) -> Result<ParallelDetectionResult, CommandError> {
     log::info!("Detecting blockchains from custom path: {}", path);
     
     let start_time = std::time::Instant::now();
@@ -637,6 +802,8 @@ pub enum CredentialError {
     Serialization(String),
     #[error("Deserialization error: {0}")]
     Deserialization(String),
+    #[error("Invalid credentials: {0}")]
+    Invalid(String),
 }
 
 // Convert StoreError to CredentialError
@@ -646,115 +813,361 @@ impl From<StoreError> for CredentialError {
     }
 }
 
-// Tauri command to save credentials
+// Outcome of test_connection; failures are reported in the fields rather than as an Err
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ConnectionTestResult {
+    pub reachable: bool,            // Something answered on host:port
+    pub authenticated: bool,        // The daemon accepted the credentials
+    pub block_height: Option<u64>,
+    pub chain_name: Option<String>, // getinfo "name" (e.g. VRSC, vrsctest)
+    pub error: Option<String>,
+}
+
+// Tauri command to check credentials work (the given, unsaved ones, or the stored ones if omitted)
+#[tauri::command]
+pub async fn test_connection<R: Runtime>(
+    app: AppHandle<R>,
+    credentials: Option<Credentials>,
+) -> Result<ConnectionTestResult, CommandError> {
+    let credentials = match credentials {
+        Some(mut credentials) => {
+            if let Err(e) = credentials.refresh_from_cookie() {
+                return Ok(ConnectionTestResult {
+                    error: Some(format!("Could not read RPC cookie: {}", e)),
+                    ..Default::default()
+                });
+            }
+            credentials.register_endpoint();
+            credentials
+        }
+        None => load_credentials(app).await?,
+    };
+    log::info!("Testing connection to {}:{}", credentials.rpc_host, credentials.rpc_port);
+
+    use crate::rpc_client::VerusRpcError;
+    let info = crate::rpc_client::make_rpc_call_with_timeout::<serde_json::Value>(
+        &credentials.rpc_user,
+        &credentials.rpc_pass,
+        &credentials.rpc_host,
+        credentials.rpc_port,
+        "getinfo",
+        vec![],
+        Duration::from_secs(CONNECTION_TEST_TIMEOUT_SECS),
+    )
+    .await;
+
+    let result = match info {
+        Ok(info) => ConnectionTestResult {
+            reachable: true,
+            authenticated: true,
+            block_height: info.get("blocks").and_then(|v| v.as_u64()),
+            chain_name: info.get("name").and_then(|v| v.as_str()).map(str::to_string),
+            error: None,
+        },
+        Err(e @ (VerusRpcError::ConnectionRefused | VerusRpcError::Timeout | VerusRpcError::Tls(_) | VerusRpcError::NetworkError(_))) => {
+            ConnectionTestResult { error: Some(e.to_string()), ..Default::default() }
+        }
+        Err(e @ VerusRpcError::Unauthorized) => ConnectionTestResult {
+            reachable: true,
+            error: Some(e.to_string()),
+            ..Default::default()
+        },
+        Err(VerusRpcError::Rpc { code: -28, message }) => ConnectionTestResult {
+            reachable: true,
+            authenticated: true,
+            error: Some(format!("Daemon is still loading: {}", message)),
+            ..Default::default()
+        },
+        // An RPC error can only come back once the credentials were accepted
+        Err(e @ VerusRpcError::Rpc { .. }) => ConnectionTestResult {
+            reachable: true,
+            authenticated: true,
+            error: Some(e.to_string()),
+            ..Default::default()
+        },
+        // Anything else means a server answered but not like a Verus daemon would
+        Err(e) => ConnectionTestResult {
+            reachable: true,
+            error: Some(e.to_string()),
+            ..Default::default()
+        },
+    };
+    log::info!("Connection test: reachable={}, authenticated={}", result.reachable, result.authenticated);
+    Ok(result)
+}
+
+// Host/datadir/rate-limit clean-up shared by save_credentials and add_profile
+fn sanitize_credentials(mut credentials: Credentials) -> Result<Credentials, CredentialError> {
+    if credentials.rpc_host.trim().is_empty() {
+        credentials.rpc_host = default_rpc_host();
+    }
+    credentials.rpc_datadir = credentials.rpc_datadir.filter(|d| !d.trim().is_empty());
+    credentials.max_rpc_per_sec = credentials.max_rpc_per_sec.filter(|&limit| limit > 0);
+    if credentials.credentials_source == CredentialsSource::Cookie && credentials.rpc_datadir.is_none() {
+        return Err(CredentialError::Invalid("Cookie authentication requires a data directory".to_string()));
+    }
+    Ok(credentials)
+}
+
+// Tauri command to save credentials (into the active profile, creating a default profile if there is none)
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn save_credentials<R: Runtime>(
     app: AppHandle<R>,
     rpc_user: String,
     rpc_pass: String,
     rpc_port: u16,
-) -> Result<(), CredentialError> {
+    rpc_host: Option<String>,
+    rpc_use_tls: Option<bool>,
+    rpc_accept_invalid_certs: Option<bool>,
+    credentials_source: Option<CredentialsSource>,
+    rpc_datadir: Option<String>,
+    max_rpc_per_sec: Option<u32>,
+) -> Result<(), CommandError> {
     log::info!("Attempting to save credentials to store...");
-    let credentials = Credentials { rpc_user, rpc_pass, rpc_port };
-    let credentials_json = serde_json::to_value(credentials)
-        .map_err(|e| CredentialError::Serialization(e.to_string()))?;
+    let credentials = sanitize_credentials(Credentials {
+        rpc_user,
+        rpc_pass,
+        rpc_port,
+        rpc_host: rpc_host.unwrap_or_default(),
+        rpc_use_tls: rpc_use_tls.unwrap_or(false),
+        rpc_accept_invalid_certs: rpc_accept_invalid_certs.unwrap_or(false),
+        credentials_source: credentials_source.unwrap_or_default(),
+        rpc_datadir,
+        max_rpc_per_sec,
+    })?;
+    credentials.register_endpoint();
 
-    // Get the store instance using the StoreExt trait
-    let store = app.store(STORE_PATH)?;
-
-    // set() returns () (unit type)
-    store.set(CREDENTIALS_KEY.to_string(), credentials_json);
-    
-    // save() returns Result so we keep the ?
-    store.save()?;
+    // Unreadable profiles are overwritten, as the single saved credential used to be
+    let mut profiles = load_profiles(&app).unwrap_or_else(|e| {
+        log::warn!("Replacing unreadable daemon profiles: {}", e);
+        DaemonProfiles::default()
+    });
+    match profiles.profiles.get_mut(profiles.active_profile) {
+        Some(active) => active.credentials = credentials,
+        None => {
+            profiles.profiles.push(DaemonProfile { name: DEFAULT_PROFILE_NAME.to_string(), credentials });
+            profiles.active_profile = profiles.profiles.len() - 1;
+        }
+    }
+    store_profiles(&app, &profiles)?;
 
     log::info!("Credentials saved successfully to store.");
     Ok(())
 }
 
-// Tauri command to load credentials
+// Tauri command to load credentials (those of the active profile)
 #[tauri::command]
 pub async fn load_credentials<R: Runtime>(
     app: AppHandle<R>,
-) -> Result<Credentials, CredentialError> {
+) -> Result<Credentials, CommandError> {
     log::info!("Attempting to load credentials from store...");
 
-    // Get the store instance
+    let profiles = load_profiles(&app)?;
+    let profile = profiles.profiles.get(profiles.active_profile).ok_or_else(|| {
+        log::info!("No active daemon profile in store.");
+        CredentialError::NotFound
+    })?;
+    let mut credentials = profile.credentials.clone();
+
+    // Cookie credentials rotate on every daemon restart - always use the current cookie
+    if let Err(e) = credentials.refresh_from_cookie() {
+        log::warn!("Failed to refresh RPC cookie, using stored values: {}", e);
+    }
+    log::info!(
+        "Successfully loaded credentials (profile '{}') with host: {}, port: {}, tls: {}",
+        profile.name, credentials.rpc_host, credentials.rpc_port, credentials.rpc_use_tls
+    );
+    credentials.register_endpoint();
+    Ok(credentials)
+}
+
+// Read the saved profiles. The first time, a single credential saved by older versions (CREDENTIALS_KEY)
+// is moved into a DEFAULT_PROFILE_NAME profile. No saved credentials at all is an empty list.
+fn load_profiles<R: Runtime>(app: &AppHandle<R>) -> Result<DaemonProfiles, CredentialError> {
     let store = app.store(STORE_PATH)?;
 
-    match store.get(CREDENTIALS_KEY) {
-        Some(value) => {
-            log::info!("Credentials JSON retrieved from store.");
-            
-            // Try to deserialize into the new format first
-            match serde_json::from_value::<Credentials>(value.clone()) {
-                Ok(credentials) => {
-                    log::info!("Successfully loaded credentials with port: {}", credentials.rpc_port);
-                    Ok(credentials)
-                }
-                Err(e) => {
-                    log::warn!("Failed to deserialize as new format: {}", e);
-                    
-                    // Try to migrate from old format (without rpc_port)
-                    #[derive(Deserialize)]
-                    struct OldCredentials {
-                        rpc_user: String,
-                        rpc_pass: String,
-                    }
-                    
-                    match serde_json::from_value::<OldCredentials>(value) {
-                        Ok(_old_creds) => {
-                            log::warn!("Found old credentials format without port information. Cannot migrate safely as port is blockchain-specific. Clearing old credentials to force fresh setup.");
-                            
-                            // Clear the old credentials instead of migrating with wrong port
-                            if store.delete(CREDENTIALS_KEY) {
-                                store.save()?;
-                                log::info!("Cleared old credentials. User will need to set up credentials again with proper port.");
-                            }
-                            
-                            Err(CredentialError::NotFound)
-                        }
-                        Err(migration_error) => {
-                            log::error!("Failed to parse credentials in any known format: {}", migration_error);
-                            Err(CredentialError::Deserialization(format!(
-                                "Could not deserialize credentials. New format error: {}. Old format error: {}",
-                                e, migration_error
-                            )))
-                        }
+    if let Some(value) = store.get(PROFILES_KEY) {
+        return serde_json::from_value::<DaemonProfiles>(value)
+            .map_err(|e| CredentialError::Deserialization(format!("Could not deserialize daemon profiles: {}", e)));
+    }
+
+    let Some(value) = store.get(CREDENTIALS_KEY) else {
+        log::info!("Keys '{}' / '{}' not found in store.", PROFILES_KEY, CREDENTIALS_KEY);
+        return Ok(DaemonProfiles::default());
+    };
+    log::info!("Credentials JSON retrieved from store.");
+
+    // Try to deserialize into the new format first
+    match serde_json::from_value::<Credentials>(value.clone()) {
+        Ok(credentials) => {
+            log::info!("Migrating saved credentials into daemon profile '{}'", DEFAULT_PROFILE_NAME);
+            let profiles = DaemonProfiles {
+                profiles: vec![DaemonProfile { name: DEFAULT_PROFILE_NAME.to_string(), credentials }],
+                active_profile: 0,
+            };
+            store_profiles(app, &profiles)?;
+            store.delete(CREDENTIALS_KEY);
+            store.save()?;
+            Ok(profiles)
+        }
+        Err(e) => {
+            log::warn!("Failed to deserialize as new format: {}", e);
+
+            // Try to migrate from old format (without rpc_port)
+            #[derive(Deserialize)]
+            struct OldCredentials {
+                rpc_user: String,
+                rpc_pass: String,
+            }
+
+            match serde_json::from_value::<OldCredentials>(value) {
+                Ok(_old_creds) => {
+                    log::warn!("Found old credentials format without port information. Cannot migrate safely as port is blockchain-specific. Clearing old credentials to force fresh setup.");
+
+                    // Clear the old credentials instead of migrating with wrong port
+                    if store.delete(CREDENTIALS_KEY) {
+                        store.save()?;
+                        log::info!("Cleared old credentials. User will need to set up credentials again with proper port.");
                     }
+
+                    Err(CredentialError::NotFound)
+                }
+                Err(migration_error) => {
+                    log::error!("Failed to parse credentials in any known format: {}", migration_error);
+                    Err(CredentialError::Deserialization(format!(
+                        "Could not deserialize credentials. New format error: {}. Old format error: {}",
+                        e, migration_error
+                    )))
                 }
             }
         }
-        None => {
-            log::info!("Key '{}' not found in store.", CREDENTIALS_KEY);
-            Err(CredentialError::NotFound)
-        }
     }
 }
 
-// Tauri command to clear credentials
+fn store_profiles<R: Runtime>(app: &AppHandle<R>, profiles: &DaemonProfiles) -> Result<(), CredentialError> {
+    let profiles_json = serde_json::to_value(profiles)
+        .map_err(|e| CredentialError::Serialization(e.to_string()))?;
+
+    // Get the store instance using the StoreExt trait
+    let store = app.store(STORE_PATH)?;
+
+    // set() returns () (unit type)
+    store.set(PROFILES_KEY.to_string(), profiles_json);
+
+    // save() returns Result so we keep the ?
+    store.save()?;
+    Ok(())
+}
+
+// A saved profile as listed to the UI (no password)
+#[derive(Serialize, Debug, Clone)]
+pub struct ProfileSummary {
+    pub index: usize,
+    pub name: String,
+    pub rpc_host: String,
+    pub rpc_port: u16,
+    pub rpc_user: String,
+    pub rpc_use_tls: bool,
+    pub active: bool,
+}
+
+// Tauri command to list saved daemon profiles
+#[tauri::command]
+pub async fn list_profiles<R: Runtime>(app: AppHandle<R>) -> Result<Vec<ProfileSummary>, CommandError> {
+    let profiles = load_profiles(&app)?;
+    Ok(profiles
+        .profiles
+        .iter()
+        .enumerate()
+        .map(|(index, profile)| ProfileSummary {
+            index,
+            name: profile.name.clone(),
+            rpc_host: profile.credentials.rpc_host.clone(),
+            rpc_port: profile.credentials.rpc_port,
+            rpc_user: profile.credentials.rpc_user.clone(),
+            rpc_use_tls: profile.credentials.rpc_use_tls,
+            active: index == profiles.active_profile,
+        })
+        .collect())
+}
+
+// Tauri command to save a new daemon profile; returns its index. activate makes it the active profile.
+#[tauri::command]
+pub async fn add_profile<R: Runtime>(
+    app: AppHandle<R>,
+    name: String,
+    credentials: Credentials,
+    activate: Option<bool>,
+) -> Result<usize, CommandError> {
+    let name = name.trim().to_string();
+    log::info!("Adding daemon profile '{}'", name);
+    if name.is_empty() {
+        return Err(CredentialError::Invalid("Profile name cannot be empty".to_string()).into());
+    }
+    let credentials = sanitize_credentials(credentials)?;
+
+    let mut profiles = load_profiles(&app)?;
+    if profiles.profiles.iter().any(|profile| profile.name.eq_ignore_ascii_case(&name)) {
+        return Err(CredentialError::Invalid(format!("A profile named '{}' already exists", name)).into());
+    }
+    profiles.profiles.push(DaemonProfile { name, credentials });
+    let index = profiles.profiles.len() - 1;
+    if activate.unwrap_or(false) || profiles.profiles.len() == 1 {
+        profiles.active_profile = index;
+    }
+    store_profiles(&app, &profiles)?;
+    Ok(index)
+}
+
+// Tauri command to switch the active daemon profile; returns its credentials (as load_credentials would)
+#[tauri::command]
+pub async fn activate_profile<R: Runtime>(app: AppHandle<R>, index: usize) -> Result<Credentials, CommandError> {
+    let mut profiles = load_profiles(&app)?;
+    let Some(profile) = profiles.profiles.get(index) else {
+        return Err(CredentialError::Invalid(format!("No profile with index {}", index)).into());
+    };
+    log::info!("Activating daemon profile '{}'", profile.name);
+    profiles.active_profile = index;
+    store_profiles(&app, &profiles)?;
+    load_credentials(app).await
+}
+
+// Tauri command to delete a daemon profile. Deleting the active profile activates the first remaining one.
 #[tauri::command]
-pub async fn clear_credentials<R: Runtime>(app: AppHandle<R>) -> Result<(), CredentialError> {
+pub async fn delete_profile<R: Runtime>(app: AppHandle<R>, index: usize) -> Result<(), CommandError> {
+    let mut profiles = load_profiles(&app)?;
+    if index >= profiles.profiles.len() {
+        return Err(CredentialError::Invalid(format!("No profile with index {}", index)).into());
+    }
+    let removed = profiles.profiles.remove(index);
+    log::info!("Deleted daemon profile '{}'", removed.name);
+    if index < profiles.active_profile {
+        profiles.active_profile -= 1;
+    } else if index == profiles.active_profile {
+        profiles.active_profile = 0;
+    }
+    Ok(store_profiles(&app, &profiles)?)
+}
+
+// Tauri command to clear credentials (every saved profile, plus any pre-profile credential)
+#[tauri::command]
+pub async fn clear_credentials<R: Runtime>(app: AppHandle<R>) -> Result<(), CommandError> {
     log::info!("Attempting to clear credentials from store...");
 
     // Get the store instance
-    let store = app.store(STORE_PATH)?;
+    let store = app.store(STORE_PATH).map_err(CredentialError::from)?;
 
-    // has() returns bool
-    if store.has(CREDENTIALS_KEY) {
-        // delete() returns bool indicating whether the key was found and deleted
-        let deleted = store.delete(CREDENTIALS_KEY);
-        
-        if deleted {
-            // Only need to save if we actually deleted something
-            store.save()?;
-            log::info!("Credentials cleared successfully from store.");
-        } else {
-            log::info!("Key '{}' not found during delete attempt.", CREDENTIALS_KEY);
-        }
+    // delete() returns bool indicating whether the key was found and deleted
+    let deleted_profiles = store.delete(PROFILES_KEY);
+    let deleted_legacy = store.delete(CREDENTIALS_KEY);
+    if deleted_profiles || deleted_legacy {
+        // Only need to save if we actually deleted something
+        store.save().map_err(CredentialError::from)?;
+        log::info!("Credentials cleared successfully from store.");
     } else {
-        log::info!("Key '{}' not found, nothing to clear.", CREDENTIALS_KEY);
+        log::info!("Keys '{}' / '{}' not found, nothing to clear.", PROFILES_KEY, CREDENTIALS_KEY);
     }
-    
+
     Ok(())
-} 
\ No newline at end of file
+}
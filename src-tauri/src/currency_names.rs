@@ -0,0 +1,115 @@
+// File: src-tauri/src/currency_names.rs
+// Description: Process-wide currency i-address -> name cache shared by the balance and namespace code.
+// Changes:
+// - Created file: moved the getcurrency name cache out of wallet_rpc. Every getcurrency response that
+//   carries a currencynames map is fed in via remember_currency_names, so most lookups never hit the daemon.
+//   Added the resolve_currency_name (single id) and resolve_currency_names (batch) commands.
+
+use crate::rpc_client::make_rpc_batch;
+use crate::CommandError;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+// Currency i-address -> name per endpoint ("host:port/i-address"); currency names never change
+static CURRENCY_NAME_CACHE: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<HashMap<String, String>> {
+    CURRENCY_NAME_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn cache_key(rpc_host: &str, rpc_port: u16, currency_id: &str) -> String {
+    format!("{}:{}/{}", rpc_host, rpc_port, currency_id)
+}
+
+// Add id -> name pairs (e.g. a getcurrency currencynames map) to the cache
+pub fn remember_currency_names(rpc_host: &str, rpc_port: u16, names: &HashMap<String, String>) {
+    if names.is_empty() {
+        return;
+    }
+    let mut cached = cache().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    for (id, name) in names {
+        cached.insert(cache_key(rpc_host, rpc_port, id), name.clone());
+    }
+}
+
+// Map currency i-addresses to names, batching getcurrency for the ones not cached yet.
+// An id that can't be resolved maps to itself so callers never drop an entry.
+pub async fn fetch_currency_names(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    ids: &[String],
+) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    let mut missing = Vec::new();
+    {
+        let cached = cache().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for id in ids {
+            match cached.get(&cache_key(rpc_host, rpc_port, id)) {
+                Some(name) => { names.insert(id.clone(), name.clone()); }
+                None => missing.push(id.clone()),
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        log::debug!("Resolving {} currency name(s) via getcurrency", missing.len());
+        let calls = missing.iter().map(|id| ("getcurrency".to_string(), vec![json!(id)])).collect();
+        let results = make_rpc_batch(rpc_user, rpc_pass, rpc_host, rpc_port, calls).await;
+        for (id, result) in missing.into_iter().zip(results) {
+            let Ok(currency) = result else {
+                // Not cached, so a transient failure is retried on the next lookup
+                log::warn!("Could not resolve currency name for {}", id);
+                names.insert(id.clone(), id);
+                continue;
+            };
+            // The response also names its reserves/related currencies - keep those too
+            if let Some(related) = currency.get("currencynames").cloned() {
+                if let Ok(related) = serde_json::from_value::<HashMap<String, String>>(related) {
+                    remember_currency_names(rpc_host, rpc_port, &related);
+                }
+            }
+            let name = currency.get("fullyqualifiedname")
+                .or_else(|| currency.get("name"))
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            match name {
+                Some(name) => {
+                    remember_currency_names(rpc_host, rpc_port, &HashMap::from([(id.clone(), name.clone())]));
+                    names.insert(id, name);
+                }
+                None => {
+                    log::warn!("getcurrency for {} returned no name", id);
+                    names.insert(id.clone(), id);
+                }
+            }
+        }
+    }
+    names
+}
+
+// Name of one currency, falling back to the id itself when it can't be resolved
+#[tauri::command]
+pub async fn resolve_currency_name(app: tauri::AppHandle, currency_id: String) -> String {
+    let creds = match crate::credentials::load_credentials(app).await {
+        Ok(creds) => creds,
+        Err(e) => {
+            log::warn!("resolve_currency_name: failed to load credentials: {}", e);
+            return currency_id;
+        }
+    };
+    fetch_currency_names(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, std::slice::from_ref(&currency_id))
+        .await
+        .remove(&currency_id)
+        .unwrap_or(currency_id)
+}
+
+// Batch lookup for balance views: every requested id is present in the result (unresolved ids map to themselves)
+#[tauri::command]
+pub async fn resolve_currency_names(app: tauri::AppHandle, ids: Vec<String>) -> Result<HashMap<String, String>, CommandError> {
+    log::debug!("resolve_currency_names command received ({} ids)", ids.len());
+    let creds = crate::credentials::load_credentials(app).await?;
+    Ok(fetch_currency_names(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &ids).await)
+}
@@ -16,12 +16,134 @@
 //   - get_identity (getidentity raw)
 //   - dump_privkey (dumpprivkey)
 //   - export_z_key (z_exportkey)
+// - RPC calls now take an rpc_host argument (configurable daemon host)
+// - wait_for_confirmations / wait_for_identity_ready use a short per-attempt RPC timeout and keep polling on timeouts
+// - get_login_identities_fast resolves names with batched getidentity calls instead of one request per identity
+// - register_identity takes a typed IdentityRegistration and validates minimumsignatures client-side
+// - register_name_commitment checks that a referral exists and lives in the target namespace before the RPC
+// - check_identity_eligibility formats names like the login list (fullyqualifiedname, or a full parent walk) for any nesting depth
+// - display_name_from_parents joins segments via join_display_name; added tests that it matches
+//   transform_fully_qualified_name at 0-3 nesting levels
+// - Added a session getidentity cache (5 min TTL) used by get_identity, check_identity_eligibility and the
+//   login name resolution; clear_identity_cache drops it after registering/updating an ID
+// - get_login_identities fetches balances concurrently (at most 8 in flight via a semaphore)
+// - Added IdentityRevision and fetch_identity_history (getidentityhistory), with a friendly error on daemons without it
+// - Added get_identity_content / set_identity_content (contentmultimap read + merge via updateidentity)
+// - Added update_identity_private_address (validates a wallet-owned z-address, then updateidentity)
+// - dump_privkey / export_z_key report a locked wallet with the bare WalletLocked message
+// - Exported keys are registered with log_redaction and only logged as a truncated fingerprint
+// - wait_for_confirmations / wait_for_identity_ready accept an optional cancel_key (see wait_cancel)
+// - wait_for_confirmations / wait_for_identity_ready run on the shared wait_cancel::poll_until loop
+// - Added TxStatus and get_transaction_status (mempool vs unknown); get_transaction_confirmations wraps it.
+//   An unknown txid is now reported as 0 confirmations instead of an error, so waits keep polling.
+// - Added get_total_private_balance (sum over login identities, per-identity breakdown, failed_addresses);
+//   the bounded concurrent balance fetch is shared with get_login_identities via fetch_private_balances
+// - FormattedIdentity gained pending_balance (0-conf), fetched alongside the confirmed balance in
+//   get_login_identities; sorting still uses the confirmed balance
+// - get_new_address / get_new_private_address return NewAddress {address, kind, in_wallet} (checked via
+//   validateaddress / z_validateaddress)
+// - Added export_identity_backup (definition + primary WIFs + z-key, warnings for keys that can't be exported);
+//   dump_privkey / export_z_key share export_private_key
+// - Added quick_messageable_check (single getidentity, privateaddress only)
+// - Commands return CommandError (code from VerusRpcError::code) instead of a formatted String
+// - Non-finite balances are treated as failed fetches; the login list sorts with total_cmp
+// - Balance sanitizing and sorting live in finite_balance / sort_by_balance, with tests
+// - get_login_identities_fast retries failed getidentity calls once (after NAME_RETRY_DELAY) before dropping an identity
+// - Added get_login_identities_page ({items, total}; names and optional balances resolved for the window only)
+// - Added recommended_confirmations (registration wait target from get_chain_info: 1 on testnet, 2 on mainnet)
+// - namereservation is now a typed NameReservation (name, salt, referral, parent, nameid; unknown fields kept);
+//   register_name_commitment rejects a reservation whose name doesn't echo the requested one
+// - register_name_commitment dedupes retries: a commitment for the same (name, control address, parent) within
+//   COMMITMENT_DEDUPE_WINDOW is returned again, and an attempt whose reply was lost blocks a new one; force_new overrides
+// - Added prepare_subid_registration (parent checked via get_namespace, name.parent@ availability, referral,
+//   and the fee in the parent's fee currency) for the sub-ID registration flow
+// - fetch_qualifying_identities checks z_listaddresses when nothing qualifies, so a wallet without imported
+//   shielded keys gets its own message instead of "no VerusIDs"
+// - getidentityhistory on an old daemon now surfaces VerusRpcError::MethodUnsupported (mapped in rpc_client);
+//   updateidentity failures go through rpc_failure_message so that message reaches the UI unprefixed
+// - get_login_identities takes an optional min_conf for the confirmed balances
+// - Added normalize_identity_name; identity lookups/commands normalize their name input (case, missing '@', whitespace)
+// - Added normalize_identity_name tests (case, missing '@', sub-IDs, i-addresses, whitespace)
+// - check_identity_eligibility returns NotFound / IneligibleNoPrivateAddress instead of NotFoundOrIneligible
+//   (still used for a malformed getidentity result)
+// - attach_balances shares one semaphore between the confirmed and pending fetches (8 in flight in total)
+// - export_identity_backup reports an unknown identity (getidentity -5 or no definition) as IDENTITY_NOT_FOUND
+// - TxStatus.conflicted flags negative confirmations (in_mempool only for exactly 0); wait_for_confirmations
+//   fails with TX_CONFLICTED instead of waiting for a conflicted tx
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use super::rpc_client::{make_rpc_call, VerusRpcError};
-use super::wallet_rpc::get_private_balance;
-use tokio::time::{sleep, Duration};
+use super::rpc_client::{make_rpc_batch, make_rpc_call, make_rpc_call_with_timeout, VerusRpcError, DEFAULT_RPC_TIMEOUT};
+use super::namespace_rpc::{get_namespace, referral_discount, NamespaceOption};
+use super::wallet_rpc::{fetch_address_validation, fetch_chain_info, get_pending_balance, get_private_balance, AddressKind};
+use crate::wait_cancel::{poll_until, WaitCancel};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use tokio::time::Duration;
+use crate::CommandError;
+
+// Max getidentity calls per JSON-RPC batch when resolving login identity names
+const IDENTITY_BATCH_SIZE: usize = 50;
+
+// Pause before retrying identities whose getidentity failed during the fast load
+const NAME_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+// Max concurrent z_getbalance calls when loading login identities
+const BALANCE_FETCH_CONCURRENCY: usize = 8;
+
+// How long a cached getidentity result is reused
+const IDENTITY_CACHE_TTL: Duration = Duration::from_secs(300);
+
+// Session cache of successful getidentity results, keyed by endpoint + lowercased name or i-address
+type IdentityCache = HashMap<String, (Instant, Value)>;
+static IDENTITY_CACHE: OnceLock<Mutex<IdentityCache>> = OnceLock::new();
+
+fn identity_cache() -> std::sync::MutexGuard<'static, IdentityCache> {
+    IDENTITY_CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn identity_cache_key(rpc_host: &str, rpc_port: u16, identity: &str) -> String {
+    format!("{}:{}|{}", rpc_host, rpc_port, identity.to_lowercase())
+}
+
+fn cached_identity(rpc_host: &str, rpc_port: u16, identity: &str) -> Option<Value> {
+    let cache = identity_cache();
+    cache
+        .get(&identity_cache_key(rpc_host, rpc_port, identity))
+        .filter(|(fetched_at, _)| fetched_at.elapsed() < IDENTITY_CACHE_TTL)
+        .map(|(_, value)| value.clone())
+}
+
+// Store a result under the requested key and its i-address, so name and ID lookups share an entry
+fn cache_identity(rpc_host: &str, rpc_port: u16, identity: &str, result: &Value) {
+    let now = Instant::now();
+    let mut cache = identity_cache();
+    cache.insert(identity_cache_key(rpc_host, rpc_port, identity), (now, result.clone()));
+    if let Some(i_address) = result.get("identity").and_then(|d| d.get("identityaddress")).and_then(|v| v.as_str()) {
+        cache.insert(identity_cache_key(rpc_host, rpc_port, i_address), (now, result.clone()));
+    }
+}
+
+// getidentity through the session cache (only successful lookups are cached)
+async fn get_identity_cached(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    identity: &str,
+) -> Result<Value, VerusRpcError> {
+    if let Some(result) = cached_identity(rpc_host, rpc_port, identity) {
+        log::debug!("getidentity cache hit for {}", identity);
+        return Ok(result);
+    }
+    let result: Value = make_rpc_call(rpc_user, rpc_pass, rpc_host, rpc_port, "getidentity", vec![json!(identity)]).await?;
+    cache_identity(rpc_host, rpc_port, identity, &result);
+    Ok(result)
+}
 
 // Updated struct to include balance for dropdown display
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -30,19 +152,22 @@ pub struct FormattedIdentity {
     pub i_address: String,            // identityaddress
     pub private_address: String,      // privateaddress (required, not optional)
     pub balance: Option<f64>,         // Private balance (None while loading)
+    #[serde(default)]
+    pub pending_balance: Option<f64>, // 0-conf balance incl. confirmed (None while loading)
 }
 
-// NEW: Fast function to get identities without balances for progressive loading
-pub async fn get_login_identities_fast(
-    rpc_user: String,
-    rpc_pass: String,
+// listidentities filtered to login-capable identities: (i-address, private address) in listing order.
+// Errors with a user-facing message when the wallet has no identities or none qualify.
+async fn fetch_qualifying_identities(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
     rpc_port: u16,
-) -> Result<Vec<FormattedIdentity>, VerusRpcError> {
-    log::info!("Fetching identities (fast mode - no balances)...");
-
+) -> Result<Vec<(String, String)>, VerusRpcError> {
     let identities_raw: Vec<Value> = match make_rpc_call(
-        &rpc_user,
-        &rpc_pass,
+        rpc_user,
+        rpc_pass,
+        rpc_host,
         rpc_port,
         "listidentities",
         vec![json!(true), json!(true), json!(true)],
@@ -53,18 +178,18 @@ pub async fn get_login_identities_fast(
             // Handle the case where listidentities returns nothing (empty response)
             // This happens when there are no identities in the wallet - RPC returns no result/error fields
             log::info!("listidentities returned empty response (Format error) - no identities in wallet");
-            return Err(VerusRpcError::Rpc {
-                code: -1,
-                message: "No VerusIDs found in your wallet. You'll need to create a new VerusID to continue.".to_string(),
-            });
+            return Err(no_identities_error(
+                rpc_user, rpc_pass, rpc_host, rpc_port,
+                "No VerusIDs found in your wallet. You'll need to create a new VerusID to continue.",
+            ).await);
         }
         Err(VerusRpcError::ParseError(ref msg)) if msg.contains("EOF while parsing") || msg.contains("expected value") => {
             // Handle alternative parse error cases for empty responses
             log::info!("listidentities returned empty response (Parse error) - no identities in wallet");
-            return Err(VerusRpcError::Rpc {
-                code: -1,
-                message: "No VerusIDs found in your wallet. You'll need to create a new VerusID to continue.".to_string(),
-            });
+            return Err(no_identities_error(
+                rpc_user, rpc_pass, rpc_host, rpc_port,
+                "No VerusIDs found in your wallet. You'll need to create a new VerusID to continue.",
+            ).await);
         }
         Err(e) => return Err(e),
     };
@@ -110,43 +235,89 @@ pub async fn get_login_identities_fast(
 
     if qualifying_identities.is_empty() {
         log::error!("No qualifying VerusIDs found (must have private address, canspendfor=true, cansignfor=true).");
-        return Err(VerusRpcError::Rpc {
-            code: -1,
-            message: "No eligible VerusIDs found. Identities must have private addresses and spending/signing permissions.".to_string(),
-        });
+        return Err(no_identities_error(
+            rpc_user, rpc_pass, rpc_host, rpc_port,
+            "No eligible VerusIDs found. Identities must have private addresses and spending/signing permissions.",
+        ).await);
     }
 
-    log::info!("Found {} qualifying identities, fetching names...", qualifying_identities.len());
+    Ok(qualifying_identities)
+}
 
-    // Step 2: Get formatted names using getidentity + fullyqualifiedname (NO BALANCE FETCHING)
-    let mut formatted_identities = Vec::new();
+// Error for an empty login list. A wallet with no z-addresses at all (e.g. restored without its shielded
+// keys) gets an import hint instead of the default message; if z_listaddresses fails, the default is used.
+async fn no_identities_error(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    default_message: &str,
+) -> VerusRpcError {
+    let message = match make_rpc_call::<Vec<String>>(rpc_user, rpc_pass, rpc_host, rpc_port, "z_listaddresses", vec![json!(true)]).await {
+        Ok(addresses) if addresses.is_empty() => {
+            log::info!("No login identities: wallet has no private addresses imported");
+            "No eligible VerusIDs found, and this wallet has no private (z) addresses. If you restored this wallet, \
+             import your shielded keys (z_importkey or z_importviewingkey) and let it rescan."
+                .to_string()
+        }
+        Ok(addresses) => {
+            log::info!("No login identities: wallet has {} private addresses but no qualifying identities", addresses.len());
+            default_message.to_string()
+        }
+        Err(e) => {
+            log::warn!("No login identities: z_listaddresses check failed ({:?}), using the generic message", e);
+            default_message.to_string()
+        }
+    };
+    VerusRpcError::Rpc { code: -1, message }
+}
 
-    for (identity_address, private_address) in qualifying_identities {
-        log::debug!("Fetching name for identity: {}", identity_address);
-        
-        match make_rpc_call::<Value>(&rpc_user, &rpc_pass, rpc_port, "getidentity", vec![json!(identity_address)]).await {
-            Ok(identity_result) => {
-                if let Some(fully_qualified_name) = identity_result.get("fullyqualifiedname").and_then(|v| v.as_str()) {
-                    // Transform fullyqualifiedname by removing everything after the last dot before @
-                    let formatted_name = transform_fully_qualified_name(fully_qualified_name);
-                    
-                    log::debug!("Transformed '{}' -> '{}'", fully_qualified_name, formatted_name);
-                    
-                    formatted_identities.push(FormattedIdentity {
-                        formatted_name,
-                        i_address: identity_address.clone(),
-                        private_address: private_address.clone(),
-                        balance: None, // No balance fetching in fast mode
-                    });
-                } else {
-                    log::warn!("No fullyqualifiedname found for identity {}, skipping", identity_address);
-                }
-            }
-            Err(e) => {
-                log::error!("Failed to get identity details for {}: {:?}, skipping", identity_address, e);
-            }
+// resolve_identity_names, retrying failed getidentity calls once after NAME_RETRY_DELAY so a transient
+// blip doesn't hide an identity. Results come back in the order of indices.
+async fn resolve_identity_names_with_retry(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    qualifying: &[(String, String)],
+    indices: &[usize],
+) -> Vec<FormattedIdentity> {
+    let (mut resolved, failed) = resolve_identity_names(rpc_user, rpc_pass, rpc_host, rpc_port, qualifying, indices).await;
+    if !failed.is_empty() {
+        log::warn!("Retrying name resolution for {} identities in {:?}", failed.len(), NAME_RETRY_DELAY);
+        tokio::time::sleep(NAME_RETRY_DELAY).await;
+        let (recovered, still_failed) = resolve_identity_names(rpc_user, rpc_pass, rpc_host, rpc_port, qualifying, &failed).await;
+        for (_, identity) in &recovered {
+            log::info!("Recovered {} on retry", identity.i_address);
         }
+        for &i in &still_failed {
+            log::error!("Dropping identity {}: getidentity failed twice", qualifying[i].0);
+        }
+        resolved.extend(recovered);
+        // Back to the requested order
+        let position: HashMap<usize, usize> = indices.iter().enumerate().map(|(pos, &i)| (i, pos)).collect();
+        resolved.sort_by_key(|(i, _)| position.get(i).copied().unwrap_or(usize::MAX));
     }
+    resolved.into_iter().map(|(_, identity)| identity).collect()
+}
+
+// NEW: Fast function to get identities without balances for progressive loading
+pub async fn get_login_identities_fast(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+) -> Result<Vec<FormattedIdentity>, VerusRpcError> {
+    log::info!("Fetching identities (fast mode - no balances)...");
+
+    let qualifying_identities = fetch_qualifying_identities(&rpc_user, &rpc_pass, &rpc_host, rpc_port).await?;
+
+    log::info!("Found {} qualifying identities, fetching names...", qualifying_identities.len());
+
+    // Step 2: Get formatted names using getidentity + fullyqualifiedname (NO BALANCE FETCHING)
+    let all: Vec<usize> = (0..qualifying_identities.len()).collect();
+    let formatted_identities =
+        resolve_identity_names_with_retry(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &qualifying_identities, &all).await;
 
     if formatted_identities.is_empty() {
         log::error!("No identities could be processed for name formatting.");
@@ -161,33 +332,229 @@ pub async fn get_login_identities_fast(
     Ok(formatted_identities)
 }
 
+// Resolve display names for qualifying[indices] (i-address, private address) via batched, cached getidentity.
+// Returns the formatted identities with their index, and the indices whose getidentity call failed.
+// Identities without a fullyqualifiedname are skipped and not reported as failed.
+async fn resolve_identity_names(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    qualifying: &[(String, String)],
+    indices: &[usize],
+) -> (Vec<(usize, FormattedIdentity)>, Vec<usize>) {
+    let mut formatted_identities = Vec::new();
+    let mut failed = Vec::new();
+
+    for chunk in indices.chunks(IDENTITY_BATCH_SIZE) {
+        // Serve what we can from the cache and batch only the misses
+        let mut results: Vec<Option<Result<Value, VerusRpcError>>> = chunk
+            .iter()
+            .map(|&i| cached_identity(rpc_host, rpc_port, &qualifying[i].0).map(Ok))
+            .collect();
+        let misses: Vec<usize> = (0..chunk.len()).filter(|&k| results[k].is_none()).collect();
+        log::debug!("Fetching names for {} identities in one batch ({} cached)", misses.len(), chunk.len() - misses.len());
+        if !misses.is_empty() {
+            let calls = misses
+                .iter()
+                .map(|&k| ("getidentity".to_string(), vec![json!(qualifying[chunk[k]].0)]))
+                .collect();
+            let fetched = make_rpc_batch(rpc_user, rpc_pass, rpc_host, rpc_port, calls).await;
+            for (k, result) in misses.into_iter().zip(fetched) {
+                if let Ok(value) = &result {
+                    cache_identity(rpc_host, rpc_port, &qualifying[chunk[k]].0, value);
+                }
+                results[k] = Some(result);
+            }
+        }
+        let results = results.into_iter().flatten();
+
+        for (&i, result) in chunk.iter().zip(results) {
+            let (identity_address, private_address) = &qualifying[i];
+            match result {
+                Ok(identity_result) => {
+                    if let Some(fully_qualified_name) = identity_result.get("fullyqualifiedname").and_then(|v| v.as_str()) {
+                        // Transform fullyqualifiedname by removing everything after the last dot before @
+                        let formatted_name = transform_fully_qualified_name(fully_qualified_name);
+
+                        log::debug!("Transformed '{}' -> '{}'", fully_qualified_name, formatted_name);
+
+                        formatted_identities.push((i, FormattedIdentity {
+                            formatted_name,
+                            i_address: identity_address.clone(),
+                            private_address: private_address.clone(),
+                            balance: None, // No balance fetching in fast mode
+                            pending_balance: None,
+                        }));
+                    } else {
+                        log::warn!("No fullyqualifiedname found for identity {}, skipping", identity_address);
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Failed to get identity details for {}: {:?}", identity_address, e);
+                    failed.push(i);
+                }
+            }
+        }
+    }
+
+    (formatted_identities, failed)
+}
+
 // NEW: Function to get balance for a specific identity
 pub async fn get_identity_balance(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
     private_address: String,
 ) -> Result<f64, VerusRpcError> {
     log::debug!("Fetching balance for private address: {}", private_address);
-    get_private_balance(rpc_user, rpc_pass, rpc_port, private_address).await
+    get_private_balance(rpc_user, rpc_pass, rpc_host, rpc_port, private_address, None).await
+}
+
+// z_getbalance for several addresses concurrently, in input order. Each call holds a semaphore permit;
+// callers running several fetches at once share one semaphore so the total stays within its limit.
+// pending = true uses the 0-conf balance (get_pending_balance) instead of the confirmed one;
+// otherwise min_conf is passed to get_private_balance (None = its default).
+#[allow(clippy::too_many_arguments)]
+async fn fetch_private_balances(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    addresses: &[String],
+    pending: bool,
+    min_conf: Option<u32>,
+    semaphore: &tokio::sync::Semaphore,
+) -> Vec<Result<f64, VerusRpcError>> {
+    let balance_futures = addresses.iter().map(|private_address| {
+        async move {
+            // The semaphore is never closed, so acquire can't fail
+            let _permit = semaphore.acquire().await.ok();
+            log::debug!("Fetching {} balance for {}", if pending { "pending" } else { "confirmed" }, private_address);
+            let (user, pass, host, address) = (rpc_user.to_string(), rpc_pass.to_string(), rpc_host.to_string(), private_address.clone());
+            let balance = if pending {
+                get_pending_balance(user, pass, host, rpc_port, address).await
+            } else {
+                get_private_balance(user, pass, host, rpc_port, address, min_conf).await
+            };
+            finite_balance(balance, private_address)
+        }
+    });
+    futures::future::join_all(balance_futures).await
+}
+
+// Balance of one login identity within a TotalPrivateBalance
+#[derive(Serialize, Debug, Clone)]
+pub struct IdentityBalance {
+    pub formatted_name: String,
+    pub private_address: String,
+    pub balance: Option<f64>, // None when the address couldn't be queried (counted as 0)
+}
+
+// Shielded balance summed over all login identities
+#[derive(Serialize, Debug, Clone)]
+pub struct TotalPrivateBalance {
+    pub total: f64,
+    pub per_identity: Vec<IdentityBalance>,
+    pub failed_addresses: Vec<String>, // Non-empty means total is partial
+}
+
+// NEW: Sum z_getbalance over the private addresses of all login identities
+pub async fn get_total_private_balance(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+) -> Result<TotalPrivateBalance, VerusRpcError> {
+    let identities = get_login_identities_fast(rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port).await?;
+
+    // Identities can share a z-address - query (and count) each address once
+    let mut addresses: Vec<String> = identities.iter().map(|identity| identity.private_address.clone()).collect();
+    addresses.sort();
+    addresses.dedup();
+    let semaphore = tokio::sync::Semaphore::new(BALANCE_FETCH_CONCURRENCY);
+    let results = fetch_private_balances(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &addresses, false, None, &semaphore).await;
+
+    let mut balances: HashMap<String, f64> = HashMap::new();
+    let mut failed_addresses = Vec::new();
+    for (address, result) in addresses.into_iter().zip(results) {
+        match result {
+            Ok(balance) => {
+                balances.insert(address, balance);
+            }
+            Err(e) => {
+                log::warn!("Failed to fetch balance for {}: {:?}, counting it as 0", address, e);
+                failed_addresses.push(address);
+            }
+        }
+    }
+
+    let total = balances.values().sum();
+    let per_identity = identities
+        .into_iter()
+        .map(|identity| IdentityBalance {
+            balance: balances.get(&identity.private_address).copied(),
+            formatted_name: identity.formatted_name,
+            private_address: identity.private_address,
+        })
+        .collect();
+
+    log::info!("Total private balance: {:.8} ({} address(es) failed)", total, failed_addresses.len());
+    Ok(TotalPrivateBalance { total, per_identity, failed_addresses })
 }
 
 // Updated function with new filtering logic and balance integration (MAINTAINED FOR COMPATIBILITY)
+// min_conf overrides the confirmations a balance needs to count (see get_private_balance)
 pub async fn get_login_identities(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
+    min_conf: Option<u32>,
 ) -> Result<Vec<FormattedIdentity>, VerusRpcError> {
     log::info!("Fetching identities for login selection with enhanced filtering...");
 
     // First get identities without balances
-    let mut identities = get_login_identities_fast(rpc_user.clone(), rpc_pass.clone(), rpc_port).await?;
+    let mut identities = get_login_identities_fast(rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port).await?;
+
+    // Then fetch balances for all identities concurrently, bounded by a semaphore
+    attach_balances(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &mut identities, min_conf).await;
+
+    sort_by_balance(&mut identities);
 
-    // Then fetch balances for all identities
-    for identity in &mut identities {
-        log::debug!("Fetching balance for {}", identity.private_address);
-        
-        match get_private_balance(rpc_user.clone(), rpc_pass.clone(), rpc_port, identity.private_address.clone()).await {
+    log::info!("Successfully processed {} identities with balances", identities.len());
+
+    Ok(identities)
+}
+
+// Fill in confirmed (at least min_conf confirmations) and pending balances (None where the fetch failed)
+async fn attach_balances(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    identities: &mut [FormattedIdentity],
+    min_conf: Option<u32>,
+) {
+    let addresses: Vec<String> = identities.iter().map(|identity| identity.private_address.clone()).collect();
+    // One semaphore for both passes: BALANCE_FETCH_CONCURRENCY bounds confirmed + pending calls together
+    let semaphore = tokio::sync::Semaphore::new(BALANCE_FETCH_CONCURRENCY);
+    let (balances, pending_balances) = futures::future::join(
+        fetch_private_balances(rpc_user, rpc_pass, rpc_host, rpc_port, &addresses, false, min_conf, &semaphore),
+        fetch_private_balances(rpc_user, rpc_pass, rpc_host, rpc_port, &addresses, true, None, &semaphore),
+    )
+    .await;
+
+    for (identity, pending) in identities.iter_mut().zip(pending_balances) {
+        identity.pending_balance = pending
+            .map_err(|e| log::warn!("Failed to fetch pending balance for {}: {:?}", identity.formatted_name, e))
+            .ok();
+    }
+
+    for (identity, result) in identities.iter_mut().zip(balances) {
+        match result {
             Ok(balance) => {
                 identity.balance = Some(balance);
                 log::debug!("Balance for {}: {:.5}", identity.formatted_name, balance);
@@ -198,25 +565,87 @@ pub async fn get_login_identities(
             }
         }
     }
+}
+
+// One window of the login identity list
+#[derive(Serialize, Debug, Clone)]
+pub struct LoginIdentityPage {
+    pub items: Vec<FormattedIdentity>,
+    pub total: usize, // Qualifying identities in the wallet (items may be fewer if names failed to resolve)
+}
 
-    // Sort by balance (highest first), treating None as 0
+// NEW: Paged login identities. listidentities is filtered in full (to know the total), but names -
+// and balances when include_balances is set - are only resolved for qualifying[offset..offset + limit].
+// Pages follow listidentities order, which is stable between calls.
+pub async fn get_login_identities_page(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    offset: usize,
+    limit: usize,
+    include_balances: bool,
+) -> Result<LoginIdentityPage, VerusRpcError> {
+    log::info!("Fetching login identities page: offset={}, limit={}, balances={}", offset, limit, include_balances);
+
+    let qualifying_identities = fetch_qualifying_identities(&rpc_user, &rpc_pass, &rpc_host, rpc_port).await?;
+    let total = qualifying_identities.len();
+    let window: Vec<usize> = (offset.min(total)..offset.saturating_add(limit).min(total)).collect();
+
+    let mut items =
+        resolve_identity_names_with_retry(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &qualifying_identities, &window).await;
+    if include_balances {
+        attach_balances(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &mut items, None).await;
+    }
+
+    log::info!("Login identities page: {} of {} identities", items.len(), total);
+    Ok(LoginIdentityPage { items, total })
+}
+
+// A malformed response could decode to NaN/inf; treat it like a failed fetch (balance None)
+fn finite_balance(balance: Result<f64, VerusRpcError>, private_address: &str) -> Result<f64, VerusRpcError> {
+    match balance {
+        Ok(value) if !value.is_finite() => Err(VerusRpcError::ParseError(format!(
+            "Non-finite balance {} for {}",
+            value, private_address
+        ))),
+        other => other,
+    }
+}
+
+// Sort by balance (highest first), treating None as 0. Balances are finite (see finite_balance);
+// total_cmp keeps the order total regardless, and the stable sort keeps ties in listing order.
+fn sort_by_balance(identities: &mut [FormattedIdentity]) {
     identities.sort_by(|a, b| {
         let balance_a = a.balance.unwrap_or(0.0);
         let balance_b = b.balance.unwrap_or(0.0);
-        balance_b.partial_cmp(&balance_a).unwrap_or(std::cmp::Ordering::Equal)
+        balance_b.total_cmp(&balance_a)
     });
+}
 
-    log::info!("Successfully processed {} identities with balances", identities.len());
+// Max parent levels walked when building a display name (guards against cycles/bad data)
+const MAX_IDENTITY_PARENT_DEPTH: usize = 16;
 
-    Ok(identities)
+// Canonical form of a typed identity name: trimmed, lowercased, exactly one trailing '@'.
+// "Alice" / "alice@" / " ALICE@ " -> "alice@", "Bob.Parent" -> "bob.parent@". i-addresses are
+// base58 (case-sensitive) and pass through unchanged apart from trimming; so does empty input.
+pub fn normalize_identity_name(input: &str) -> String {
+    let trimmed = input.trim();
+    let is_i_address = trimmed.len() == 34
+        && trimmed.starts_with('i')
+        && trimmed.chars().all(|c| c.is_ascii_alphanumeric());
+    if trimmed.is_empty() || is_i_address {
+        return trimmed.to_string();
+    }
+    format!("{}@", trimmed.trim_end_matches('@').trim_end().to_lowercase())
 }
 
-// Helper function to transform fullyqualifiedname
+// Helper function to transform fullyqualifiedname into a display name by dropping the
+// system (chain) segment, whatever the nesting depth:
+// Example: "JohnGomez.VRSCTEST@" -> "JohnGomez@"
+// Example: "JohnGomez.parent.VRSCTEST@" -> "JohnGomez.parent@"
+// Example: "a.b.c.VRSCTEST@" -> "a.b.c@"
 fn transform_fully_qualified_name(fully_qualified_name: &str) -> String {
-    // Remove everything after the last dot before @
-    // Example: "JohnGomez.parent.VRSCTEST@" -> "JohnGomez.parent@"
-    // Example: "JohnGomez.VRSCTEST@" -> "JohnGomez@"
-    
     if let Some(at_pos) = fully_qualified_name.rfind('@') {
         let before_at = &fully_qualified_name[..at_pos];
         if let Some(last_dot_pos) = before_at.rfind('.') {
@@ -231,13 +660,97 @@ fn transform_fully_qualified_name(fully_qualified_name: &str) -> String {
     }
 }
 
+// Build the same display name as transform_fully_qualified_name by walking parent identities up to
+// the system ID (for daemons that don't return fullyqualifiedname). Falls back to the names found so
+// far if a parent lookup fails.
+async fn display_name_from_parents(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    name: &str,
+    parent_id: &str,
+    system_id: &str,
+) -> String {
+    let mut segments = vec![name.to_string()];
+    let mut current_parent = parent_id.to_string();
+
+    while current_parent != system_id && segments.len() <= MAX_IDENTITY_PARENT_DEPTH {
+        log::debug!("Identity '{}' is a sub-ID. Fetching parent '{}'...", name, current_parent);
+        let parent_result = match get_identity_cached(rpc_user, rpc_pass, rpc_host, rpc_port, &current_parent).await {
+            Ok(result) => result,
+            Err(e) => {
+                log::error!("Error fetching parent identity {}: {:?}. Using partial name.", current_parent, e);
+                break;
+            }
+        };
+        let parent_details = parent_result.get("identity");
+        let parent_name = parent_details.and_then(|d| d.get("name")).and_then(|n| n.as_str());
+        let next_parent = parent_details.and_then(|d| d.get("parent")).and_then(|p| p.as_str());
+        match (parent_name, next_parent) {
+            (Some(parent_name), Some(next_parent)) => {
+                segments.push(parent_name.to_string());
+                current_parent = next_parent.to_string();
+            }
+            (Some(parent_name), None) => {
+                segments.push(parent_name.to_string());
+                break;
+            }
+            _ => {
+                log::error!("Failed to extract parent name for {}. Using partial name.", current_parent);
+                break;
+            }
+        }
+    }
+
+    join_display_name(&segments)
+}
+
+// Display name from name segments ordered child first: ["a", "b", "c"] -> "a.b.c@"
+fn join_display_name(segments: &[String]) -> String {
+    format!("{}@", segments.join("."))
+}
+
+// Fast "can I message this identity" check for the new-chat input: one (cached) getidentity, no
+// name formatting or parent lookups. check_identity_eligibility gives the display details afterwards.
+pub async fn quick_messageable_check(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    identity_name: &str,
+) -> Result<bool, VerusRpcError> {
+    let identity_name = &normalize_identity_name(identity_name);
+    if !identity_name.ends_with('@') || identity_name.len() <= 1 {
+        return Err(VerusRpcError::InvalidFormat);
+    }
+
+    match get_identity_cached(rpc_user, rpc_pass, rpc_host, rpc_port, identity_name).await {
+        Ok(identity_result) => {
+            let messageable = identity_result
+                .get("identity")
+                .and_then(|identity| identity.get("privateaddress"))
+                .and_then(|v| v.as_str())
+                .is_some_and(|address| !address.is_empty());
+            log::debug!("quick_messageable_check({}): {}", identity_name, messageable);
+            Ok(messageable)
+        }
+        // Same "not found" codes as check_identity_eligibility
+        Err(VerusRpcError::Rpc { code: -5 | -8, .. }) => Ok(false),
+        Err(VerusRpcError::ParseError(ref msg)) if msg.contains("500 Internal Server Error") => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
 // NEW function for New Chat: Check identity eligibility
 pub async fn check_identity_eligibility(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
     target_identity_name: String,
 ) -> Result<FormattedIdentity, VerusRpcError> {
+    let target_identity_name = normalize_identity_name(&target_identity_name);
     log::info!("Checking eligibility for identity: {}", target_identity_name);
 
     // Basic format check
@@ -246,7 +759,7 @@ pub async fn check_identity_eligibility(
         return Err(VerusRpcError::InvalidFormat);
     }
 
-    match make_rpc_call::<Value>(&rpc_user, &rpc_pass, rpc_port, "getidentity", vec![json!(target_identity_name)]).await {
+    match get_identity_cached(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &target_identity_name).await {
         Ok(identity_result) => {
             log::debug!("getidentity result for {}: {:?}", target_identity_name, identity_result);
             if let Some(identity_details) = identity_result.get("identity") {
@@ -262,34 +775,11 @@ pub async fn check_identity_eligibility(
                         identity_details.get("parent").and_then(|v| v.as_str()),
                         identity_details.get("systemid").and_then(|v| v.as_str()),
                     ) {
-                        // Start with default format
-                        let mut formatted_name = format!("{}@", name);
-                        
-                        // Check if it's a sub-ID (parent is not the system ID)
-                        if parent_id != system_id {
-                            log::debug!("Identity '{}' is a sub-ID. Fetching parent '{}'...", name, parent_id);
-                            // Get parent identity to format the name properly (name.parentname@)
-                            match make_rpc_call::<Value>(&rpc_user, &rpc_pass, rpc_port, "getidentity", vec![json!(parent_id)]).await {
-                                Ok(parent_identity_result) => {
-                                    // Extract parent name from the parent identity details
-                                    if let Some(parent_name) = parent_identity_result
-                                        .get("identity")
-                                        .and_then(|id_details| id_details.get("name"))
-                                        .and_then(|n| n.as_str()) 
-                                    {
-                                        log::debug!("Parent name found: {}", parent_name);
-                                        formatted_name = format!("{}.{}@", name, parent_name);
-                                    } else {
-                                        log::error!("Failed to extract parent name for sub-ID. Using default format.");
-                                        // Keep default format as fallback
-                                    }
-                                },
-                                Err(e) => {
-                                    log::error!("Error fetching parent identity: {:?}. Using default format.", e);
-                                    // Keep default format as fallback
-                                }
-                            }
-                        }
+                        // Same formatting as the login list: prefer fullyqualifiedname, otherwise walk the parents
+                        let formatted_name = match identity_result.get("fullyqualifiedname").and_then(|v| v.as_str()) {
+                            Some(fully_qualified_name) => transform_fully_qualified_name(fully_qualified_name),
+                            None => display_name_from_parents(&rpc_user, &rpc_pass, &rpc_host, rpc_port, name, parent_id, system_id).await,
+                        };
                         
                         log::info!("Identity {} is eligible. Formatted as: {}", target_identity_name, formatted_name);
                         Ok(FormattedIdentity {
@@ -297,6 +787,7 @@ pub async fn check_identity_eligibility(
                             i_address: i_address.to_string(),
                             private_address: private_address_opt.unwrap(),
                             balance: None,
+                            pending_balance: None,
                         })
                     } else {
                         log::warn!("Identity {} found but missing required fields.", target_identity_name);
@@ -304,7 +795,7 @@ pub async fn check_identity_eligibility(
                     }
                 } else {
                     log::warn!("Identity {} found but has no private address.", target_identity_name);
-                    Err(VerusRpcError::NotFoundOrIneligible)
+                    Err(VerusRpcError::IneligibleNoPrivateAddress)
                 }
             } else {
                  log::warn!("'identity' object not found in getidentity result for {}.", target_identity_name);
@@ -318,12 +809,12 @@ pub async fn check_identity_eligibility(
                     // Code -5: Invalid address or key (Identity not found)
                     // Code -8: Invalid parameter (Could also indicate identity not found)
                     log::warn!("getidentity RPC error indicates not found for {}: code={}, message={}", target_identity_name, code, message);
-                    Err(VerusRpcError::NotFoundOrIneligible)
+                    Err(VerusRpcError::NotFound)
                 },
                 VerusRpcError::ParseError(ref msg) if msg.contains("500 Internal Server Error") => {
                      // Treat 500 error specifically for getidentity as likely not found
                     log::warn!("getidentity received 500 error, treating as not found for {}: {}", target_identity_name, msg);
-                    Err(VerusRpcError::NotFoundOrIneligible)
+                    Err(VerusRpcError::NotFound)
                 }
                 _ => {
                     // Propagate other errors (network, timeout, different RPC errors, etc.)
@@ -339,9 +830,11 @@ pub async fn check_identity_eligibility(
 pub async fn check_identity_exists(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
     identity_name: String,
 ) -> Result<bool, VerusRpcError> {
+    let identity_name = normalize_identity_name(&identity_name);
     log::info!("Checking existence of identity: {}", identity_name);
 
     // Basic format check to avoid unnecessary RPC calls
@@ -351,7 +844,7 @@ pub async fn check_identity_exists(
         return Ok(false);
     }
 
-    match make_rpc_call::<Value>(&rpc_user, &rpc_pass, rpc_port, "getidentity", vec![json!(identity_name)]).await {
+    match make_rpc_call::<Value>(&rpc_user, &rpc_pass, &rpc_host, rpc_port, "getidentity", vec![json!(identity_name)]).await {
         Ok(_) => {
             // If we get any successful result, the identity exists.
             log::info!("Identity '{}' exists.", identity_name);
@@ -381,40 +874,472 @@ pub async fn check_identity_exists(
     }
 } 
 
+// --- Identity history ---
+
+// Raw getidentityhistory entry
+#[derive(Deserialize, Debug)]
+struct IdentityHistoryEntry {
+    identity: Value,
+    #[serde(default)]
+    blockhash: Option<String>,
+    height: u64,
+    output: IdentityHistoryOutput,
+}
+
+#[derive(Deserialize, Debug)]
+struct IdentityHistoryOutput {
+    txid: String,
+    #[serde(default)]
+    voutnum: Option<u32>,
+}
+
+// One revision of a VerusID, oldest first
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IdentityRevision {
+    pub blockheight: u64,
+    pub blockhash: Option<String>,
+    pub txid: String, // Transaction that created this revision
+    pub vout: Option<u32>,
+    pub identity: Value, // Identity snapshot at this revision
+}
+
+// Fetch the revision timeline of an identity via getidentityhistory
+pub async fn fetch_identity_history(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    identity_name: &str,
+) -> Result<Vec<IdentityRevision>, VerusRpcError> {
+    let identity_name = &normalize_identity_name(identity_name);
+    log::info!("Fetching identity history for {}", identity_name);
+    // An old daemon without getidentityhistory fails with MethodUnsupported
+    let result: Value = make_rpc_call(rpc_user, rpc_pass, rpc_host, rpc_port, "getidentityhistory", vec![json!(identity_name)]).await?;
+
+    let entries: Vec<IdentityHistoryEntry> = serde_json::from_value(result.get("history").cloned().unwrap_or_else(|| json!([])))
+        .map_err(|e| VerusRpcError::ParseError(format!("Invalid getidentityhistory response: {}", e)))?;
+
+    let revisions: Vec<IdentityRevision> = entries
+        .into_iter()
+        .map(|entry| IdentityRevision {
+            blockheight: entry.height,
+            blockhash: entry.blockhash,
+            txid: entry.output.txid,
+            vout: entry.output.voutnum,
+            identity: entry.identity,
+        })
+        .collect();
+
+    log::info!("Identity {} has {} revisions", identity_name, revisions.len());
+    Ok(revisions)
+}
+
+// --- Identity updates ---
+
+// Fields of an identity definition that updateidentity accepts; everything else in getidentity is derived state
+const UPDATABLE_IDENTITY_FIELDS: &[&str] = &[
+    "name",
+    "parent",
+    "primaryaddresses",
+    "minimumsignatures",
+    "revocationauthority",
+    "recoveryauthority",
+    "privateaddress",
+    "contentmultimap",
+    "timelock",
+    "flags",
+];
+
+// Fetch the current identity definition (uncached - updates must start from the latest revision)
+async fn fetch_current_identity(
+    creds: &crate::credentials::Credentials,
+    identity_name: &str,
+) -> Result<Value, CommandError> {
+    let result: Value = make_rpc_call(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "getidentity", vec![json!(identity_name)])
+        .await
+        .map_err(CommandError::from)?;
+    result
+        .get("identity")
+        .cloned()
+        .ok_or_else(|| CommandError::new(VerusRpcError::NotFound.code(), format!("No identity definition returned for {}", identity_name)))
+}
+
+// Copy the updatable fields of a current identity, so an update keeps everything it doesn't change
+fn identity_update_from(current: &Value) -> Value {
+    let mut update = serde_json::Map::new();
+    for field in UPDATABLE_IDENTITY_FIELDS {
+        if let Some(value) = current.get(*field) {
+            update.insert(field.to_string(), value.clone());
+        }
+    }
+    Value::Object(update)
+}
+
+// Submit updateidentity, drop stale cache entries, return txid
+async fn submit_identity_update(
+    creds: &crate::credentials::Credentials,
+    update: Value,
+) -> Result<String, CommandError> {
+    log::debug!("updateidentity payload: {}", update);
+    let txid: String = make_rpc_call(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "updateidentity", vec![update])
+        .await
+        .map_err(|e| CommandError::rpc_failure("updateidentity", e))?;
+    identity_cache().clear();
+    log::info!("updateidentity txid: {}", txid);
+    Ok(txid)
+}
+
+/// Read an identity's contentmultimap (key -> list of values)
+#[tauri::command]
+pub async fn get_identity_content(
+    app: tauri::AppHandle,
+    identity_name: String,
+) -> Result<HashMap<String, Vec<Value>>, CommandError> {
+    let identity_name = normalize_identity_name(&identity_name);
+    let creds = crate::credentials::load_credentials(app).await?;
+    let result = get_identity_cached(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &identity_name)
+        .await
+        .map_err(CommandError::from)?;
+
+    let content = match result.get("identity").and_then(|d| d.get("contentmultimap")) {
+        Some(map) => serde_json::from_value(map.clone())
+            .map_err(|e| CommandError::from(VerusRpcError::ParseError(format!("Invalid contentmultimap for {}: {}", identity_name, e))))?,
+        None => HashMap::new(),
+    };
+    Ok(content)
+}
+
+/// Set one contentmultimap key (replacing its values), keeping all other keys; returns the updateidentity txid
+#[tauri::command]
+pub async fn set_identity_content(
+    app: tauri::AppHandle,
+    identity_name: String,
+    key: String,
+    value: Value,
+) -> Result<String, CommandError> {
+    let identity_name = normalize_identity_name(&identity_name);
+    log::info!("set_identity_content: {} key={}", identity_name, key);
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    let current = fetch_current_identity(&creds, &identity_name).await?;
+    let mut update = identity_update_from(&current);
+
+    let mut content = match update.get("contentmultimap") {
+        Some(Value::Object(map)) => map.clone(),
+        _ => serde_json::Map::new(),
+    };
+    content.insert(key, json!([value]));
+    update["contentmultimap"] = Value::Object(content);
+
+    submit_identity_update(&creds, update).await
+}
+
+// Subset of z_validateaddress output needed to vet a new private address
+#[derive(Deserialize, Debug)]
+struct ZValidateAddressResult {
+    isvalid: bool,
+    #[serde(default)]
+    ismine: bool,
+}
+
+/// Rotate an identity's private (shielded) address; returns the updateidentity txid.
+/// Follow up with wait_for_confirmations on the txid before relying on the new address.
+#[tauri::command]
+pub async fn update_identity_private_address(
+    app: tauri::AppHandle,
+    identity_name: String,
+    new_private_address: String,
+) -> Result<String, CommandError> {
+    let identity_name = normalize_identity_name(&identity_name);
+    log::info!("update_identity_private_address: {}", identity_name);
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    let validation: ZValidateAddressResult = make_rpc_call(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "z_validateaddress", vec![json!(new_private_address)])
+        .await
+        .map_err(CommandError::from)?;
+    if !validation.isvalid {
+        return Err(CommandError::invalid_argument(format!("{} is not a valid z-address", new_private_address)));
+    }
+    if !validation.ismine {
+        return Err(CommandError::from(VerusRpcError::SignerNotInWallet(new_private_address)));
+    }
+
+    let current = fetch_current_identity(&creds, &identity_name).await?;
+    let mut update = identity_update_from(&current);
+    update["privateaddress"] = json!(new_private_address);
+
+    submit_identity_update(&creds, update).await
+}
+
 // --- Registration helpers & commands ---
 
+// Confirmations to wait for between registration phases (commitment -> registeridentity -> identity ready).
+// One is all the daemon requires; mainnet waits for a second as a guard against a single-block reorg.
+const MAINNET_REGISTRATION_CONFIRMATIONS: u64 = 2;
+const TESTNET_REGISTRATION_CONFIRMATIONS: u64 = 1;
+
+/// Chain-appropriate min_confirmations for wait_for_confirmations during registration
+#[tauri::command]
+pub async fn recommended_confirmations(app: tauri::AppHandle) -> Result<u64, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+    let chain_info = fetch_chain_info(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port)
+        .await
+        .map_err(CommandError::from)?;
+    let confirmations = if chain_info.is_testnet() {
+        TESTNET_REGISTRATION_CONFIRMATIONS
+    } else {
+        MAINNET_REGISTRATION_CONFIRMATIONS
+    };
+    log::info!("recommended_confirmations: {} on {} ({})", confirmations, chain_info.name, chain_info.chain);
+    Ok(confirmations)
+}
+
+// namereservation object from registernamecommitment. Unknown fields (e.g. version) are kept in
+// `extra` so the reservation round-trips into registeridentity unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NameReservation {
+    pub name: String,
+    pub salt: String,
+    #[serde(default)]
+    pub referral: String, // Empty when no referral was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    pub nameid: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct NameCommitmentResponse {
     pub txid: String,
-    pub namereservation: Value,
+    pub namereservation: NameReservation,
+}
+
+// Identity definition inside a registeridentity bundle
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IdentityDefinition {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    pub primaryaddresses: Vec<String>,
+    pub minimumsignatures: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revocationauthority: Option<Value>, // ID name/i-address (string or [string]); empty = self
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recoveryauthority: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privateaddress: Option<String>,
+}
+
+// registeridentity bundle: the name commitment plus the identity to create
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IdentityRegistration {
+    pub txid: String, // registernamecommitment txid
+    pub namereservation: NameReservation, // Passed back exactly as registernamecommitment returned it
+    pub identity: IdentityDefinition,
+}
+
+impl IdentityRegistration {
+    // Catch common mistakes before the daemon does (its errors are cryptic)
+    pub fn validate(&self) -> Result<(), VerusRpcError> {
+        let identity = &self.identity;
+        if identity.name.is_empty() || identity.primaryaddresses.is_empty() {
+            log::warn!("Identity registration missing name or primary addresses");
+            return Err(VerusRpcError::InvalidFormat);
+        }
+        if identity.minimumsignatures == 0 || identity.minimumsignatures as usize > identity.primaryaddresses.len() {
+            log::warn!(
+                "Identity registration minimumsignatures {} invalid for {} primary addresses",
+                identity.minimumsignatures,
+                identity.primaryaddresses.len()
+            );
+            return Err(VerusRpcError::InvalidFormat);
+        }
+        Ok(())
+    }
+}
+
+// Freshly generated address, confirmed against the wallet
+#[derive(Debug, Serialize, Clone)]
+pub struct NewAddress {
+    pub address: String,
+    pub kind: AddressKind,
+    pub in_wallet: bool, // false means generation silently failed - don't fund this address
+}
+
+// Confirm a generated address is valid and owned by the wallet
+async fn describe_new_address(creds: &crate::credentials::Credentials, address: String) -> Result<NewAddress, CommandError> {
+    let validation = fetch_address_validation(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &address)
+        .await
+        .map_err(|e| CommandError::with_context(format!("Failed to validate new address {}", address), e))?;
+    let in_wallet = validation.is_valid && validation.is_mine;
+    if !in_wallet {
+        log::error!("Generated address {} is not in the wallet (valid: {})", address, validation.is_valid);
+    }
+    Ok(NewAddress { address, kind: validation.kind, in_wallet })
 }
 
 /// Get a new transparent control address (R-addr)
 #[tauri::command]
-pub async fn get_new_address(app: tauri::AppHandle) -> Result<String, String> {
+pub async fn get_new_address(app: tauri::AppHandle) -> Result<NewAddress, CommandError> {
     log::info!("get_new_address called");
-    let creds = crate::credentials::load_credentials(app)
+    let creds = crate::credentials::load_credentials(app).await?;
+    let addr = make_rpc_call::<String>(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "getnewaddress", vec![])
         .await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
-    let addr = make_rpc_call::<String>(&creds.rpc_user, &creds.rpc_pass, creds.rpc_port, "getnewaddress", vec![])
-        .await
-        .map_err(|e| format!("getnewaddress failed: {}", e))?;
+        .map_err(CommandError::from)?;
     log::info!("get_new_address result: {}", addr);
-    Ok(addr)
+    describe_new_address(&creds, addr).await
 }
 
 /// Get a new shielded private address (zs-addr)
 #[tauri::command]
-pub async fn get_new_private_address(app: tauri::AppHandle) -> Result<String, String> {
+pub async fn get_new_private_address(app: tauri::AppHandle) -> Result<NewAddress, CommandError> {
     log::info!("get_new_private_address called");
-    let creds = crate::credentials::load_credentials(app)
-        .await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
-    let zaddr = make_rpc_call::<String>(&creds.rpc_user, &creds.rpc_pass, creds.rpc_port, "z_getnewaddress", vec![])
+    let creds = crate::credentials::load_credentials(app).await?;
+    let zaddr = make_rpc_call::<String>(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "z_getnewaddress", vec![])
         .await
-        .map_err(|e| format!("z_getnewaddress failed: {}", e))?;
+        .map_err(CommandError::from)?;
     log::info!("get_new_private_address result: {}", zaddr);
-    Ok(zaddr)
+    describe_new_address(&creds, zaddr).await
+}
+
+// Check a referral before registernamecommitment: it must exist and belong to the parent namespace
+// being registered under (an empty parent means a top-level ID on the current chain).
+async fn validate_referral(
+    creds: &crate::credentials::Credentials,
+    referral: &str,
+    parent_namespace: &str,
+) -> Result<(), VerusRpcError> {
+    let exists = check_identity_exists(
+        creds.rpc_user.clone(),
+        creds.rpc_pass.clone(),
+        creds.rpc_host.clone(),
+        creds.rpc_port,
+        referral.to_string(),
+    )
+    .await?;
+    if !exists {
+        return Err(VerusRpcError::ReferralNotFound(referral.to_string()));
+    }
+
+    let referral_identity: Value = make_rpc_call(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "getidentity", vec![json!(referral)]).await?;
+    let details = referral_identity.get("identity").ok_or(VerusRpcError::Format)?;
+    let referral_parent = details.get("parent").and_then(|v| v.as_str()).unwrap_or_default();
+
+    let expected_parent = if parent_namespace.is_empty() {
+        details.get("systemid").and_then(|v| v.as_str()).unwrap_or_default().to_string()
+    } else {
+        let currency: Value = make_rpc_call(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "getcurrency", vec![json!(parent_namespace)]).await?;
+        currency.get("currencyid").and_then(|v| v.as_str()).ok_or(VerusRpcError::Format)?.to_string()
+    };
+
+    if referral_parent != expected_parent {
+        log::warn!(
+            "Referral {} has parent {} but registration is under {} ({})",
+            referral, referral_parent, parent_namespace, expected_parent
+        );
+        return Err(VerusRpcError::ReferralNamespaceMismatch {
+            referral: referral.to_string(),
+            namespace: if parent_namespace.is_empty() { "root".to_string() } else { parent_namespace.to_string() },
+        });
+    }
+    Ok(())
+}
+
+// Checked inputs for registering name.parent@, returned by prepare_subid_registration
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SubIdRegistrationPlan {
+    pub identity_name: String, // "name.parent@"
+    pub parent: NamespaceOption,
+    pub fee_currency: String,
+    pub base_fee: f64,
+    pub referral_discount: f64,
+    pub net_fee: f64,
+}
+
+/// Check a sub-ID registration before anything is paid: the parent must be a registerable namespace,
+/// name.parent@ must be free, and a referral must exist under that parent. Returns the fee in the
+/// parent's fee currency.
+#[tauri::command]
+pub async fn prepare_subid_registration(
+    app: tauri::AppHandle,
+    name: String,
+    parent_namespace: String,
+    referral: Option<String>,
+) -> Result<SubIdRegistrationPlan, CommandError> {
+    let name = name.trim().to_string();
+    let parent_namespace = parent_namespace.trim().trim_end_matches('@').to_string();
+    log::info!("prepare_subid_registration: name={}, parent={}, referral={:?}", name, parent_namespace, referral);
+    if name.is_empty() || name.contains(['.', '@']) {
+        return Err(CommandError::invalid_argument(format!("Invalid name '{}': it must be non-empty and contain no '.' or '@'", name)));
+    }
+
+    let parent = get_namespace(app.clone(), parent_namespace.clone())
+        .await?
+        .ok_or_else(|| CommandError::invalid_argument(format!("{} is not a namespace IDs can be registered under", parent_namespace)))?;
+    if !parent.fee_currency_resolved {
+        log::warn!("prepare_subid_registration: fee currency of {} is unverified ({})", parent.name, parent.fee_currency_name);
+    }
+
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    let identity_name = format!("{}.{}@", name, parent.name);
+    let taken = check_identity_exists(creds.rpc_user.clone(), creds.rpc_pass.clone(), creds.rpc_host.clone(), creds.rpc_port, identity_name.clone())
+        .await
+        .map_err(|e| CommandError::with_context(format!("Failed to check {}", identity_name), e))?;
+    if taken {
+        return Err(CommandError::new("NAME_TAKEN", format!("{} is already registered", identity_name)));
+    }
+
+    let referral = referral.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    if let Some(referral) = &referral {
+        let referral_id = if referral.ends_with('@') { referral.clone() } else { format!("{}@", referral) };
+        validate_referral(&creds, &referral_id, &parent.name)
+            .await
+            .map_err(CommandError::from)?;
+    }
+
+    let base_fee = parent.registration_fee;
+    let discount = if referral.is_some() { referral_discount(base_fee, parent.id_referral_levels) } else { 0.0 };
+    log::info!(
+        "prepare_subid_registration: {} available, fee {} - {} {}",
+        identity_name, base_fee, discount, parent.fee_currency_name
+    );
+    Ok(SubIdRegistrationPlan {
+        identity_name,
+        fee_currency: parent.fee_currency_name.clone(),
+        base_fee,
+        referral_discount: discount,
+        net_fee: base_fee - discount,
+        parent,
+    })
+}
+
+// Retrying a commitment within this window reuses the earlier one instead of paying again
+// (it only has to confirm before registeridentity, which the registration flow does right away)
+const COMMITMENT_DEDUPE_WINDOW: Duration = Duration::from_secs(30 * 60);
+
+enum CommitmentAttempt {
+    Done(NameCommitmentResponse),
+    // Network error or timeout: the daemon may or may not have created (and paid for) it
+    Unknown,
+}
+
+struct CommitmentRecord {
+    at: Instant,
+    attempt: CommitmentAttempt,
+}
+
+// Recent commitments per "host:port|name|control address|parent" (in memory, this session only)
+static RECENT_COMMITMENTS: OnceLock<Mutex<HashMap<String, CommitmentRecord>>> = OnceLock::new();
+
+fn record_commitment(key: String, attempt: CommitmentAttempt) {
+    let mut recent = RECENT_COMMITMENTS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    recent.retain(|_, record| record.at.elapsed() < COMMITMENT_DEDUPE_WINDOW);
+    recent.insert(key, CommitmentRecord { at: Instant::now(), attempt });
 }
 
 /// Call registernamecommitment
@@ -425,7 +1350,8 @@ pub async fn register_name_commitment(
     control_address: String,
     referral_identity: Option<String>,
     parent_namespace: Option<String>,
-) -> Result<NameCommitmentResponse, String> {
+    force_new: Option<bool>,
+) -> Result<NameCommitmentResponse, CommandError> {
     log::info!(
         "register_name_commitment: name={}, control={}, referral='{}', parent='{}'",
         name,
@@ -433,58 +1359,134 @@ pub async fn register_name_commitment(
         referral_identity.clone().unwrap_or_else(|| "".into()),
         parent_namespace.clone().unwrap_or_else(|| "".into())
     );
-    let creds = crate::credentials::load_credentials(app)
-        .await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+    let creds = crate::credentials::load_credentials(app).await?;
 
     // Per spec, explicitly pass empty string when not provided
     let referral = referral_identity.unwrap_or_else(|| "".to_string());
     let parent = parent_namespace.unwrap_or_else(|| "".to_string());
 
-    let result: Value = make_rpc_call(
+    let dedupe_key = format!(
+        "{}:{}|{}|{}|{}",
+        creds.rpc_host,
+        creds.rpc_port,
+        name.to_lowercase(),
+        control_address,
+        parent.to_lowercase()
+    );
+    if !force_new.unwrap_or(false) {
+        let recent = RECENT_COMMITMENTS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(record) = recent.get(&dedupe_key).filter(|r| r.at.elapsed() < COMMITMENT_DEDUPE_WINDOW) {
+            match &record.attempt {
+                CommitmentAttempt::Done(commitment) => {
+                    log::info!("register_name_commitment: reusing commitment {} for {}", commitment.txid, name);
+                    return Ok(commitment.clone());
+                }
+                CommitmentAttempt::Unknown => {
+                    log::warn!("register_name_commitment: previous attempt for {} has an unknown outcome", name);
+                    return Err(CommandError::new("COMMITMENT_OUTCOME_UNKNOWN", format!(
+                        "A commitment for '{}' may already have been submitted {}s ago, but no reply came back. \
+                         Check the wallet for it, or retry with force_new to pay for a new one.",
+                        name,
+                        record.at.elapsed().as_secs()
+                    )));
+                }
+            }
+        }
+    }
+
+    // Fail fast on a typo'd or foreign referral instead of wasting a commitment fee
+    if !referral.is_empty() {
+        // getidentity needs the trailing '@'; the commitment itself still gets the referral as entered
+        let referral_id = if referral.ends_with('@') { referral.clone() } else { format!("{}@", referral) };
+        validate_referral(&creds, &referral_id, &parent)
+            .await
+            .map_err(CommandError::from)?;
+    }
+
+    let result: Value = match make_rpc_call(
         &creds.rpc_user,
         &creds.rpc_pass,
+        &creds.rpc_host,
         creds.rpc_port,
         "registernamecommitment",
         vec![json!(name), json!(control_address), json!(referral), json!(parent)],
     )
     .await
-    .map_err(|e| format!("registernamecommitment failed: {}", e))?;
+    {
+        Ok(result) => result,
+        Err(e) => {
+            // The request may have reached the daemon; don't let a blind retry pay twice
+            if matches!(e, VerusRpcError::NetworkError(_) | VerusRpcError::Timeout) {
+                record_commitment(dedupe_key, CommitmentAttempt::Unknown);
+            }
+            return Err(CommandError::from(e));
+        }
+    };
 
     // Expect { txid, namereservation: {...} }
     let txid = result
         .get("txid")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing txid in registernamecommitment response".to_string())?
+        .ok_or_else(|| CommandError::from(VerusRpcError::ParseError("Missing txid in registernamecommitment response".to_string())))?
         .to_string();
 
-    let namereservation = result
+    let namereservation: NameReservation = result
         .get("namereservation")
         .cloned()
-        .ok_or_else(|| "Missing namereservation in response".to_string())?;
+        .ok_or_else(|| VerusRpcError::ParseError("Missing namereservation in response".to_string()))
+        .and_then(|v| serde_json::from_value(v).map_err(|e| VerusRpcError::ParseError(format!("Malformed namereservation in response: {}", e))))?;
+
+    // The daemon may normalize the name; anything beyond case means the reservation isn't for what was asked
+    if !namereservation.name.eq_ignore_ascii_case(&name) {
+        log::error!(
+            "register_name_commitment: reservation name '{}' does not match requested '{}' (txid {})",
+            namereservation.name,
+            name,
+            txid
+        );
+        return Err(CommandError::new("COMMITMENT_NAME_MISMATCH", format!(
+            "Name commitment reserved '{}' instead of the requested '{}'",
+            namereservation.name, name
+        )));
+    }
 
-    log::info!("register_name_commitment txid: {}", txid);
-    Ok(NameCommitmentResponse { txid, namereservation })
+    log::info!("register_name_commitment txid: {}, nameid: {}", txid, namereservation.nameid);
+    let commitment = NameCommitmentResponse { txid, namereservation };
+    record_commitment(dedupe_key, CommitmentAttempt::Done(commitment.clone()));
+    Ok(commitment)
 }
 
-/// Call registeridentity with pass-through bundle, return txid
+/// Validate and submit a registeridentity bundle, return txid
 #[tauri::command]
-pub async fn register_identity(app: tauri::AppHandle, identity_bundle: Value) -> Result<String, String> {
-    log::info!("register_identity called");
-    log::debug!("register_identity payload: {}", identity_bundle);
-    let creds = crate::credentials::load_credentials(app)
-        .await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+pub async fn register_identity(app: tauri::AppHandle, identity_bundle: IdentityRegistration) -> Result<String, CommandError> {
+    log::info!("register_identity called for {}", identity_bundle.identity.name);
+    log::debug!("register_identity payload: {:?}", identity_bundle);
+    identity_bundle.validate().map_err(|e| {
+        CommandError::new(e.code(), format!(
+            "{}: name and primary addresses are required, and minimumsignatures ({}) must be between 1 and the number of primary addresses ({})",
+            e,
+            identity_bundle.identity.minimumsignatures,
+            identity_bundle.identity.primaryaddresses.len()
+        ))
+    })?;
+    let bundle = serde_json::to_value(&identity_bundle)
+        .map_err(|e| CommandError::new("INTERNAL", format!("Failed to serialize identity bundle: {}", e)))?;
+
+    let creds = crate::credentials::load_credentials(app).await?;
 
     let result: Value = make_rpc_call(
         &creds.rpc_user,
         &creds.rpc_pass,
+        &creds.rpc_host,
         creds.rpc_port,
         "registeridentity",
-        vec![identity_bundle],
+        vec![bundle],
     )
     .await
-    .map_err(|e| format!("registeridentity failed: {}", e))?;
+    .map_err(CommandError::from)?;
 
     // Try common shapes: string txid or object with txid
     if let Some(txid) = result.as_str() {
@@ -500,45 +1502,102 @@ pub async fn register_identity(app: tauri::AppHandle, identity_bundle: Value) ->
     Ok(result.to_string())
 }
 
-/// Get confirmations for a txid using gettransaction
-#[tauri::command]
-pub async fn get_transaction_confirmations(app: tauri::AppHandle, txid: String) -> Result<u64, String> {
-    log::info!("get_transaction_confirmations({}, ..)", txid);
-    let creds = crate::credentials::load_credentials(app)
-        .await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+// RPC_INVALID_ADDRESS_OR_KEY: getrawtransaction's "No information available about transaction"
+const RPC_TX_NOT_FOUND: i32 = -5;
 
+// Status of a transaction as seen by the wallet/daemon.
+// in_mempool = known but unconfirmed; confirmations 0 with neither in_mempool nor conflicted = unknown txid.
+// conflicted = the wallet reports negative confirmations (a conflicting tx was mined; this one never will be).
+#[derive(Debug, Serialize, Clone)]
+pub struct TxStatus {
+    pub confirmations: u64,
+    pub in_mempool: bool,
+    pub conflicted: bool,
+    pub height: Option<u64>, // Block height (only when the daemon reports it)
+    pub time: Option<u64>,   // Block time, or time first seen for mempool txs
+}
+
+impl TxStatus {
+    fn unknown() -> Self {
+        TxStatus { confirmations: 0, in_mempool: false, conflicted: false, height: None, time: None }
+    }
+
+    fn from_tx(tx: &Value) -> Self {
+        // Conflicted wallet txs report negative confirmations: neither confirmed nor waiting in the mempool
+        let raw_confirmations = tx.get("confirmations").and_then(|v| v.as_i64()).unwrap_or(0);
+        TxStatus {
+            confirmations: raw_confirmations.max(0) as u64,
+            in_mempool: raw_confirmations == 0,
+            conflicted: raw_confirmations < 0,
+            height: tx.get("height").or_else(|| tx.get("blockheight")).and_then(|v| v.as_u64()),
+            time: tx.get("blocktime").or_else(|| tx.get("time")).and_then(|v| v.as_u64()),
+        }
+    }
+}
+
+// Fetch a tx's status (gettransaction, falling back to getrawtransaction verbose)
+async fn fetch_transaction_status(
+    creds: &crate::credentials::Credentials,
+    txid: &str,
+    timeout: Duration,
+) -> Result<TxStatus, VerusRpcError> {
     // Try gettransaction first
-    let primary = make_rpc_call::<Value>(
+    let primary = make_rpc_call_with_timeout::<Value>(
         &creds.rpc_user,
         &creds.rpc_pass,
+        &creds.rpc_host,
         creds.rpc_port,
         "gettransaction",
-        vec![json!(txid.clone())],
+        vec![json!(txid)],
+        timeout,
     )
     .await;
     let result: Value = match primary {
         Ok(val) => val,
         Err(e) => {
             log::warn!("gettransaction failed for {}: {:?}. Falling back to getrawtransaction(verbose)", txid, e);
-            make_rpc_call::<Value>(
+            match make_rpc_call_with_timeout::<Value>(
                 &creds.rpc_user,
                 &creds.rpc_pass,
+                &creds.rpc_host,
                 creds.rpc_port,
                 "getrawtransaction",
-                vec![json!(txid.clone()), json!(true)],
+                vec![json!(txid), json!(true)],
+                timeout,
             )
             .await
-            .map_err(|e2| format!("gettransaction failed and getrawtransaction fallback also failed: {}", e2))?
+            {
+                Ok(val) => val,
+                Err(VerusRpcError::Rpc { code: RPC_TX_NOT_FOUND, .. }) => {
+                    log::info!("tx {} is unknown to the daemon", txid);
+                    return Ok(TxStatus::unknown());
+                }
+                Err(e) => return Err(e),
+            }
         }
     };
 
-    let confs = result
-        .get("confirmations")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(0);
-    log::info!("tx {} confirmations: {}", txid, confs);
-    Ok(confs)
+    let status = TxStatus::from_tx(&result);
+    log::info!("tx {} confirmations: {} (in mempool: {}, conflicted: {})", txid, status.confirmations, status.in_mempool, status.conflicted);
+    Ok(status)
+}
+
+/// Get confirmations, mempool state, height and time for a txid
+#[tauri::command]
+pub async fn get_transaction_status(app: tauri::AppHandle, txid: String) -> Result<TxStatus, CommandError> {
+    log::info!("get_transaction_status({}, ..)", txid);
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    fetch_transaction_status(&creds, &txid, DEFAULT_RPC_TIMEOUT)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Get confirmations for a txid using gettransaction (count only; see get_transaction_status)
+#[tauri::command]
+pub async fn get_transaction_confirmations(app: tauri::AppHandle, txid: String) -> Result<u64, CommandError> {
+    log::info!("get_transaction_confirmations({}, ..)", txid);
+    get_transaction_status(app, txid).await.map(|status| status.confirmations)
 }
 
 /// Wait until a tx reaches min confirmations, or timeout
@@ -549,52 +1608,77 @@ pub async fn wait_for_confirmations(
     min_confirmations: u64,
     interval_secs: u64,
     timeout_secs: u64,
-) -> Result<bool, String> {
-    let start = std::time::Instant::now();
-    loop {
-        let confs = match get_transaction_confirmations(app.clone(), txid.clone()).await {
-            Ok(c) => c,
+    cancel_key: Option<String>,
+) -> Result<bool, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+    let cancel = WaitCancel::register(cancel_key);
+    let (creds, txid) = (&creds, &txid);
+
+    let confirmed = poll_until("wait_for_confirmations", interval_secs, timeout_secs, &cancel, |attempt_timeout| async move {
+        match fetch_transaction_status(creds, txid, attempt_timeout).await {
+            // A conflicted tx will never confirm, so waiting out the timeout would only hide that
+            Ok(status) if status.conflicted => Err(CommandError::new(
+                "TX_CONFLICTED",
+                format!("Transaction {} conflicts with a mined transaction and will not confirm", txid),
+            )),
+            Ok(status) => Ok(status.confirmations >= min_confirmations),
+            Err(VerusRpcError::Timeout) => {
+                // A single slow poll shouldn't abort the wait - try again next interval
+                log::warn!("wait_for_confirmations: poll timed out for tx {}, retrying", txid);
+                Ok(false)
+            }
             Err(e) => {
                 log::error!("wait_for_confirmations get tx error: {}", e);
-                return Err(e);
+                Err(CommandError::from(e))
             }
-        };
-        if confs >= min_confirmations {
-            return Ok(true);
-        }
-        if start.elapsed() >= Duration::from_secs(timeout_secs) {
-            log::warn!(
-                "wait_for_confirmations timeout: tx={}, waited_secs={}, required_confs={}",
-                txid,
-                timeout_secs,
-                min_confirmations
-            );
-            return Ok(false);
         }
-        sleep(Duration::from_secs(interval_secs)).await;
+    })
+    .await?;
+
+    if !confirmed {
+        log::warn!("wait_for_confirmations: tx={} did not reach {} confirmations", txid, min_confirmations);
     }
+    Ok(confirmed)
 }
 
 /// Raw getidentity call to retrieve identity object
 #[tauri::command]
-pub async fn get_identity(app: tauri::AppHandle, identity_name: String) -> Result<Value, String> {
-    let creds = crate::credentials::load_credentials(app)
-        .await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
-    make_rpc_call::<Value>(&creds.rpc_user, &creds.rpc_pass, creds.rpc_port, "getidentity", vec![json!(identity_name)])
+pub async fn get_identity(app: tauri::AppHandle, identity_name: String) -> Result<Value, CommandError> {
+    let identity_name = normalize_identity_name(&identity_name);
+    let creds = crate::credentials::load_credentials(app).await?;
+    get_identity_cached(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &identity_name)
         .await
-        .map_err(|e| format!("getidentity failed: {}", e))
+        .map_err(CommandError::from)
+}
+
+/// Drop all cached getidentity results (call after registering or updating an ID)
+#[tauri::command]
+pub async fn clear_identity_cache() -> Result<(), CommandError> {
+    let mut cache = identity_cache();
+    log::info!("Clearing identity cache ({} entries)", cache.len());
+    cache.clear();
+    Ok(())
 }
 
 /// Check if identity exists (returns true/false instead of erroring on not found)
 #[tauri::command]
-pub async fn check_identity_ready(app: tauri::AppHandle, identity_name: String) -> Result<bool, String> {
+pub async fn check_identity_ready(app: tauri::AppHandle, identity_name: String) -> Result<bool, CommandError> {
+    let identity_name = normalize_identity_name(&identity_name);
     log::info!("check_identity_ready: checking {}", identity_name);
-    let creds = crate::credentials::load_credentials(app)
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    fetch_identity_ready(&creds, &identity_name, DEFAULT_RPC_TIMEOUT)
         .await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+        .map_err(CommandError::from)
+}
 
-    match make_rpc_call::<Value>(&creds.rpc_user, &creds.rpc_pass, creds.rpc_port, "getidentity", vec![json!(identity_name)]).await {
+// getidentity-based readiness check; "not found" errors map to Ok(false)
+async fn fetch_identity_ready(
+    creds: &crate::credentials::Credentials,
+    identity_name: &str,
+    timeout: Duration,
+) -> Result<bool, VerusRpcError> {
+    match make_rpc_call_with_timeout::<Value>(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "getidentity", vec![json!(identity_name)], timeout).await {
         Ok(_) => {
             log::info!("check_identity_ready: {} exists", identity_name);
             Ok(true)
@@ -615,7 +1699,7 @@ pub async fn check_identity_ready(app: tauri::AppHandle, identity_name: String)
                 _ => {
                     // Propagate other errors (network, auth, etc.)
                     log::error!("check_identity_ready: unexpected error for {}: {:?}", identity_name, e);
-                    Err(format!("Error checking identity: {}", e))
+                    Err(e)
                 }
             }
         }
@@ -629,53 +1713,290 @@ pub async fn wait_for_identity_ready(
     identity_name: String,
     interval_secs: u64,
     timeout_secs: u64,
-) -> Result<bool, String> {
+    cancel_key: Option<String>,
+) -> Result<bool, CommandError> {
+    let identity_name = normalize_identity_name(&identity_name);
     log::info!("wait_for_identity_ready: waiting for {} (timeout: {}s)", identity_name, timeout_secs);
-    let start = std::time::Instant::now();
-    
-    loop {
-        match check_identity_ready(app.clone(), identity_name.clone()).await {
+    let creds = crate::credentials::load_credentials(app).await?;
+    let cancel = WaitCancel::register(cancel_key);
+    let (creds, identity_name) = (&creds, &identity_name);
+
+    poll_until("wait_for_identity_ready", interval_secs, timeout_secs, &cancel, |attempt_timeout| async move {
+        match fetch_identity_ready(creds, identity_name, attempt_timeout).await {
             Ok(true) => {
                 log::info!("wait_for_identity_ready: {} is ready", identity_name);
-                return Ok(true);
+                Ok(true)
             }
             Ok(false) => {
                 log::debug!("wait_for_identity_ready: {} not ready yet, continuing to poll", identity_name);
-                // Continue polling
+                Ok(false)
+            }
+            Err(VerusRpcError::Timeout) => {
+                log::warn!("wait_for_identity_ready: poll timed out for {}, retrying", identity_name);
+                Ok(false)
             }
             Err(e) => {
                 log::error!("wait_for_identity_ready: error checking {}: {}", identity_name, e);
-                return Err(e);
+                Err(CommandError::from(e))
             }
         }
+    })
+    .await
+}
 
-        if start.elapsed() >= Duration::from_secs(timeout_secs) {
-            log::warn!("wait_for_identity_ready: timeout waiting for {}", identity_name);
-            return Ok(false);
-        }
-
-        sleep(Duration::from_secs(interval_secs)).await;
-    }
+// dumpprivkey / z_exportkey; the exported key is registered for log redaction before anything is logged
+async fn export_private_key(
+    creds: &crate::credentials::Credentials,
+    method: &str,
+    address: &str,
+) -> Result<String, CommandError> {
+    let key = make_rpc_call::<String>(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, method, vec![json!(address)])
+        .await
+        .map_err(|e| CommandError::rpc_failure(method, e))?;
+    crate::log_redaction::register_secret(&key);
+    log::info!("{}: exported key {} for {}", method, crate::log_redaction::key_fingerprint(&key), address);
+    Ok(key)
 }
 
 /// Export transparent private key (WIF) for control R-addr
 #[tauri::command]
-pub async fn dump_privkey(app: tauri::AppHandle, address: String) -> Result<String, String> {
-    let creds = crate::credentials::load_credentials(app)
-        .await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
-    make_rpc_call::<String>(&creds.rpc_user, &creds.rpc_pass, creds.rpc_port, "dumpprivkey", vec![json!(address)])
-        .await
-        .map_err(|e| format!("dumpprivkey failed: {}", e))
+pub async fn dump_privkey(app: tauri::AppHandle, address: String) -> Result<String, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+    export_private_key(&creds, "dumpprivkey", &address).await
 }
 
 /// Export shielded private key for zs-addr
 #[tauri::command]
-pub async fn export_z_key(app: tauri::AppHandle, z_address: String) -> Result<String, String> {
-    let creds = crate::credentials::load_credentials(app)
-        .await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
-    make_rpc_call::<String>(&creds.rpc_user, &creds.rpc_pass, creds.rpc_port, "z_exportkey", vec![json!(z_address)])
+pub async fn export_z_key(app: tauri::AppHandle, z_address: String) -> Result<String, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+    export_private_key(&creds, "z_exportkey", &z_address).await
+}
+
+// One exported key; private_key is None when the export failed (see IdentityBackup.warnings)
+#[derive(Debug, Serialize, Clone)]
+pub struct ExportedKey {
+    pub address: String,
+    pub private_key: Option<String>,
+}
+
+// Everything needed to restore an identity on another wallet
+#[derive(Debug, Serialize, Clone)]
+pub struct IdentityBackup {
+    pub identity_name: String,            // Fully qualified name
+    pub identity: Value,                  // getidentity definition at export time
+    pub primary_keys: Vec<ExportedKey>,   // WIF per primary R-address
+    pub private_key: Option<ExportedKey>, // z-key for the identity's private address, if it has one
+    pub exported_at: u64,                 // Unix seconds
+    pub warnings: Vec<String>,            // Keys that couldn't be exported (e.g. not in this wallet)
+}
+
+async fn export_backup_key(
+    creds: &crate::credentials::Credentials,
+    method: &str,
+    address: &str,
+    warnings: &mut Vec<String>,
+) -> ExportedKey {
+    let private_key = match export_private_key(creds, method, address).await {
+        Ok(key) => Some(key),
+        Err(e) => {
+            log::warn!("Backup: could not export key for {}: {}", address, e);
+            warnings.push(format!("Could not export key for {}: {}", address, e));
+            None
+        }
+    };
+    ExportedKey { address: address.to_string(), private_key }
+}
+
+/// Bundle an identity's definition with its primary WIFs and private z-key for backup.
+/// Fails only if the identity can't be read; keys that can't be exported are listed in warnings.
+#[tauri::command]
+pub async fn export_identity_backup(app: tauri::AppHandle, identity_name: String) -> Result<IdentityBackup, CommandError> {
+    let identity_name = normalize_identity_name(&identity_name);
+    log::info!("export_identity_backup: {}", identity_name);
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    // getidentity reports an unknown name as RPC_INVALID_ADDRESS_OR_KEY (-5)
+    let result: Value = make_rpc_call(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "getidentity", vec![json!(identity_name)])
         .await
-        .map_err(|e| format!("z_exportkey failed: {}", e))
-}
\ No newline at end of file
+        .map_err(|e| match e {
+            VerusRpcError::Rpc { code: -5, .. } => VerusRpcError::NotFound,
+            e => e,
+        })?;
+    let identity = result
+        .get("identity")
+        .cloned()
+        .ok_or_else(|| CommandError::new(VerusRpcError::NotFound.code(), format!("No identity definition returned for {}", identity_name)))?;
+    let fully_qualified = result
+        .get("fullyqualifiedname")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| identity_name.clone());
+
+    let mut warnings = Vec::new();
+
+    let primary_addresses: Vec<String> = identity
+        .get("primaryaddresses")
+        .and_then(|v| v.as_array())
+        .map(|addrs| addrs.iter().filter_map(|a| a.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+    if primary_addresses.is_empty() {
+        warnings.push(format!("{} has no primary addresses", fully_qualified));
+    }
+    let mut primary_keys = Vec::with_capacity(primary_addresses.len());
+    for address in &primary_addresses {
+        primary_keys.push(export_backup_key(&creds, "dumpprivkey", address, &mut warnings).await);
+    }
+
+    let private_key = match identity.get("privateaddress").and_then(|v| v.as_str()).filter(|a| !a.is_empty()) {
+        Some(z_address) => Some(export_backup_key(&creds, "z_exportkey", z_address, &mut warnings).await),
+        None => None,
+    };
+
+    let exported_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    log::info!(
+        "export_identity_backup: {} primary key(s) exported, private key exported: {}, {} warning(s)",
+        primary_keys.iter().filter(|k| k.private_key.is_some()).count(),
+        private_key.as_ref().is_some_and(|k| k.private_key.is_some()),
+        warnings.len()
+    );
+
+    Ok(IdentityBackup {
+        identity_name: fully_qualified,
+        identity,
+        primary_keys,
+        private_key,
+        exported_at,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn fully_qualified_name_drops_system_segment_at_each_depth() {
+        let cases = [
+            ("alice.VRSCTEST@", "alice@", vec!["alice"]),
+            ("alice.parent.VRSCTEST@", "alice.parent@", vec!["alice", "parent"]),
+            ("alice.b.c.VRSCTEST@", "alice.b.c@", vec!["alice", "b", "c"]),
+            ("alice.b.c.d.VRSC@", "alice.b.c.d@", vec!["alice", "b", "c", "d"]),
+        ];
+        for (fully_qualified_name, expected, parent_walk) in cases {
+            let transformed = transform_fully_qualified_name(fully_qualified_name);
+            assert_eq!(transformed, expected);
+            // The parent walk for daemons without fullyqualifiedname must produce the same string
+            assert_eq!(join_display_name(&segments(&parent_walk)), transformed);
+        }
+    }
+
+    #[test]
+    fn fully_qualified_name_without_dot_or_at_is_unchanged() {
+        assert_eq!(transform_fully_qualified_name("alice@"), "alice@");
+        assert_eq!(transform_fully_qualified_name("alice.VRSCTEST"), "alice.VRSCTEST");
+    }
+
+    fn identity(name: &str, balance: Option<f64>) -> FormattedIdentity {
+        FormattedIdentity {
+            formatted_name: name.to_string(),
+            i_address: String::new(),
+            private_address: String::new(),
+            balance,
+            pending_balance: None,
+        }
+    }
+
+    fn names(identities: &[FormattedIdentity]) -> Vec<&str> {
+        identities.iter().map(|identity| identity.formatted_name.as_str()).collect()
+    }
+
+    #[test]
+    fn finite_balance_rejects_nan_and_infinity() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(finite_balance(Ok(value), "zs1test"), Err(VerusRpcError::ParseError(_))));
+        }
+        assert!(matches!(finite_balance(Ok(1.5), "zs1test"), Ok(value) if value == 1.5));
+        assert!(matches!(finite_balance(Err(VerusRpcError::Timeout), "zs1test"), Err(VerusRpcError::Timeout)));
+    }
+
+    #[test]
+    fn sort_by_balance_is_descending_and_stable_for_ties() {
+        let mut identities = vec![
+            identity("a@", Some(1.0)),
+            identity("b@", None),
+            identity("c@", Some(5.0)),
+            identity("d@", Some(1.0)),
+            identity("e@", Some(0.0)),
+            identity("f@", Some(1.0)),
+        ];
+        sort_by_balance(&mut identities);
+        // None sorts as 0 and keeps its listing position among the zero balances
+        assert_eq!(names(&identities), ["c@", "a@", "d@", "f@", "b@", "e@"]);
+    }
+
+    #[test]
+    fn non_finite_balances_sort_like_failed_fetches() {
+        let fetched = [("a@", Ok(f64::NAN)), ("b@", Ok(2.0)), ("c@", Ok(f64::INFINITY)), ("d@", Ok(2.0))];
+        let mut identities: Vec<FormattedIdentity> = fetched
+            .into_iter()
+            .map(|(name, balance)| identity(name, finite_balance(balance, name).ok()))
+            .collect();
+        assert!(identities[0].balance.is_none() && identities[2].balance.is_none());
+        sort_by_balance(&mut identities);
+        assert_eq!(names(&identities), ["b@", "d@", "a@", "c@"]);
+    }
+
+    #[test]
+    fn normalize_lowercases_and_adds_one_trailing_at() {
+        assert_eq!(normalize_identity_name("Alice"), "alice@");
+        assert_eq!(normalize_identity_name("alice@"), "alice@");
+        assert_eq!(normalize_identity_name("ALICE@"), "alice@");
+        assert_eq!(normalize_identity_name("AlIcE@@"), "alice@");
+        assert_eq!(normalize_identity_name(" ALICE@ "), "alice@");
+        assert_eq!(normalize_identity_name("alice @"), "alice@");
+    }
+
+    #[test]
+    fn normalize_keeps_sub_id_segments() {
+        assert_eq!(normalize_identity_name("Bob.Parent"), "bob.parent@");
+        assert_eq!(normalize_identity_name("bob.parent@"), "bob.parent@");
+        assert_eq!(normalize_identity_name("\tBob.Mid.Parent@\n"), "bob.mid.parent@");
+    }
+
+    #[test]
+    fn normalize_passes_i_addresses_through() {
+        let i_address = "iJhCezBExJHvtyH3fGhNnt2NhU4Ztkf2yq";
+        assert_eq!(normalize_identity_name(i_address), i_address);
+        assert_eq!(normalize_identity_name(&format!("  {} ", i_address)), i_address);
+        // Not 34 alphanumeric characters, so treated as a name
+        assert_eq!(normalize_identity_name("iAlice"), "ialice@");
+    }
+
+    #[test]
+    fn normalize_leaves_empty_and_whitespace_input_empty() {
+        assert_eq!(normalize_identity_name(""), "");
+        assert_eq!(normalize_identity_name("   "), "");
+        assert_eq!(normalize_identity_name("\t\n"), "");
+    }
+
+    #[test]
+    fn tx_status_splits_mempool_and_conflicted() {
+        let confirmed = TxStatus::from_tx(&json!({ "confirmations": 3, "height": 100 }));
+        assert_eq!((confirmed.confirmations, confirmed.in_mempool, confirmed.conflicted), (3, false, false));
+        assert_eq!(confirmed.height, Some(100));
+
+        let pending = TxStatus::from_tx(&json!({ "confirmations": 0 }));
+        assert_eq!((pending.confirmations, pending.in_mempool, pending.conflicted), (0, true, false));
+
+        let conflicted = TxStatus::from_tx(&json!({ "confirmations": -1 }));
+        assert_eq!((conflicted.confirmations, conflicted.in_mempool, conflicted.conflicted), (0, false, true));
+    }
+}
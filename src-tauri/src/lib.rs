@@ -22,41 +22,148 @@
 // - Added get_utxo_info command for Fast Messages feature
 // - Added progressive loading commands: get_login_identities_fast, get_identity_balance
 // - Removed fund_private_address_for_messages_cmd command as automatic funding was removed
+// - Threaded configurable rpc_host through all RPC commands (connect_verus_daemon defaults to 127.0.0.1)
+// - connect_verus_daemon accepts optional TLS settings for HTTPS RPC endpoints
+// - connect_verus_daemon distinguishes refused connections from rejected credentials
+// - Added `pub mod messaging_rpc;` with async z_sendmany sending and operation tracking commands
+// - Added get_operation_result command (z_getoperationresult) returning the final txid of a send
+// - Registered messaging_rpc::get_received_messages (z_listreceivedbyaddress inbox)
+// - Registered messaging_rpc::get_conversations (threads grouped by counterparty)
+// - Added message_state module (local read/unread store) and its commands
+// - Added consolidate_utxos command (dust merge, tracked via the operation-status commands)
+// - get_utxo_info accepts an optional min_usable threshold
+// - Added get_wallet_utxo_summary command (UTXO info across all identity addresses)
+// - Registered wallet_rpc::estimate_conversion_input (reverse conversion estimate)
+// - Registered wallet_rpc::get_transaction_history (listtransactions)
+// - Registered wallet_rpc::export_transactions_csv
+// - Registered namespace_rpc::get_currency_reserves
+// - Registered namespace_rpc::estimate_registration_cost
+// - Registered identity_rpc::clear_identity_cache
+// - Added get_identity_history command (getidentityhistory revision timeline)
+// - Registered identity_rpc::get_identity_content / set_identity_content
+// - Registered identity_rpc::update_identity_private_address
+// - Added sign_message / verify_message commands (R-address or name@ signer)
+// - Registered wallet_rpc::unlock_wallet / lock_wallet (encrypted wallet support)
+// - Registered wallet_rpc::get_sync_status / wait_for_sync
+// - Added log_redaction module: logging goes through a wrapper that scrubs RPC credentials and keys
+// - Added wait_cancel module and cancel_wait command (aborts wait_for_* polling loops)
+// - Registered identity_rpc::get_transaction_status
+// - wait_for_operation is now a lib.rs command so OperationFailed reaches the frontend as a typed error
+// - Added get_total_private_balance command (summary across login identities)
+// - Registered credentials::test_connection
+// - Added sweep_to_address command (gather funds before VerusID registration)
+// - Registered namespace_rpc::find_conversion_path
+// - Added price_history module (local estimateconversion samples) and its commands
+// - Registered wallet_rpc::validate_address
+// - Registered identity_rpc::export_identity_backup
+// - Added import_z_key command (z_importkey with a rescan mode)
+// - Added quick_messageable_check command
+// - Registered messaging_rpc::send_long_message_async (chunked memos)
+// - Registered messaging_rpc::start_message_watcher / stop_message_watcher (new-message events)
+// - CommandError is now a { code, message } struct (codes from VerusRpcError::code) instead of an enum
+// - CommandError is pub (module commands return it) with invalid_argument / with_context / rpc_failure helpers
+// - Added get_login_identities_page command (paged login list)
+// - Registered wallet_rpc::get_chain_info
+// - Added view_shielded_transaction command (z_viewtransaction)
+// - Registered identity_rpc::recommended_confirmations
+// - Registered namespace_rpc::get_namespace
+// - Added split_utxos command (create many spendable outputs for messaging bursts)
+// - Registered messaging_rpc::list_operations (pending z_sendmany operations)
+// - Registered message_state::unlock_message_store / lock_message_store / get_message_store_status
+// - Registered wallet_rpc::get_network_health
+// - Registered wallet_rpc::get_transaction_fee
+// - Registered log_redaction::set_log_level / get_recent_logs (runtime verbosity, support log tail)
+// - Registered messaging_rpc::check_memo (memo byte count / limit check)
+// - MemoError converts to CommandError as MEMO_TOO_LONG / MEMO_CONTAINS_NUL
+// - Added get_transparent_balance command (R-address balance, e.g. a registration control address)
+// - Registered namespace_rpc::get_registration_options
+// - Registered identity_rpc::prepare_subid_registration
+// - Added currency_names module; registered resolve_currency_name / resolve_currency_names
+// - get_private_balance / get_login_identities accept an optional min_conf
+// - Added estimate_message_capacity command
+// - Added get_address_deltas command (getaddressdeltas-based receive detection)
+// - Registered credentials::list_profiles / add_profile / activate_profile / delete_profile (saved daemon profiles)
+// - Registered wallet_rpc::get_transaction_reserve_transfers
+// - Added shutdown command; closing the main window also cancels all waits and the message watcher
+// - Registered wallet_rpc::estimate_currency_conversion_quote
+// - Registered messaging_rpc::send_private_message_multi
+// - PriceHistoryError converts to CommandError as PRICE_HISTORY (price_history commands return CommandError)
+// - MessageStateError converts to CommandError (MESSAGE_STORE_LOCKED / WRONG_PASSPHRASE / MESSAGE_STORE)
 
 mod credentials; // Added credentials module
 mod settings; // Added settings module
+mod message_state; // Local read/unread state
+mod log_redaction; // Scrubs credentials/keys from log output
+mod wait_cancel; // Cancellation for wait_for_* commands
+mod price_history; // Local price samples for currency pairs
+mod currency_names; // Shared currency i-address -> name cache
 pub mod rpc_client;
 pub mod identity_rpc;
 pub mod message_rpc;
 pub mod wallet_rpc;
 pub mod namespace_rpc;
+pub mod messaging_rpc;
 
 // use crate::rpc_client::VerusRpcError; // Corrected (unused)
 use crate::credentials::CredentialError; // Import credential error
 use crate::settings::SettingsError; // Import settings error
 use crate::identity_rpc::FormattedIdentity; // Corrected
+use crate::identity_rpc::IdentityRevision;
+use crate::identity_rpc::TotalPrivateBalance;
+use crate::identity_rpc::LoginIdentityPage;
 use crate::message_rpc::ChatMessage; // Corrected
 use crate::wallet_rpc::UtxoInfo; // Import UtxoInfo
+use crate::wallet_rpc::WalletUtxoSummary;
+use crate::wallet_rpc::MessageCapacity;
+use crate::wallet_rpc::AddressDelta;
+use crate::wallet_rpc::{RescanMode, ZKeyImport};
+use crate::messaging_rpc::OperationResult;
+use crate::messaging_rpc::ShieldedTx;
 
-// Custom error type serializable for Tauri
+// Error returned to the frontend as { code, message }: branch on code (NOT_FOUND, UNAUTHORIZED, TIMEOUT,
+// WALLET_LOCKED, ... - see VerusRpcError::code), show message
 #[derive(Debug, serde::Serialize, thiserror::Error)]
-enum CommandError {
-    #[error("Verus RPC Error: {0}")]
-    Rpc(String),
-    #[error("Credential Error: {0}")] // Added Credential error variant
-    Credentials(String),
-    #[error("Settings Error: {0}")] // Added Settings error variant
-    Settings(String),
-    #[error("Verus RPC Error: {0}")] // Use the same variant, but handle specific RPC errors
-    RpcSpecific(crate::rpc_client::VerusRpcError), // Corrected
+#[error("{message}")]
+pub struct CommandError {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl CommandError {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        CommandError { code, message: message.into() }
+    }
+
+    // Input rejected before anything reached the daemon
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        CommandError::new("INVALID_ARGUMENT", message)
+    }
+
+    // An RPC failure with what was being done prefixed to the message; the code stays the error's own
+    pub fn with_context(context: impl std::fmt::Display, error: crate::rpc_client::VerusRpcError) -> Self {
+        log::error!("{}: {:?}", context, error);
+        CommandError::new(error.code(), format!("{}: {}", context, error))
+    }
+
+    // rpc_failure_message wording (WalletLocked / MethodUnsupported stay bare) with the error's code
+    pub fn rpc_failure(method: &str, error: crate::rpc_client::VerusRpcError) -> Self {
+        log::error!("{} failed: {:?}", method, error);
+        CommandError::new(error.code(), crate::rpc_client::rpc_failure_message(method, &error))
+    }
 }
 
 // Convert VerusRpcError to CommandError
 impl From<crate::rpc_client::VerusRpcError> for CommandError { // Corrected
     fn from(error: crate::rpc_client::VerusRpcError) -> Self { // Corrected
         log::error!("RPC call failed: {:?}", error);
-        // Return the specific error type for frontend handling
-        CommandError::RpcSpecific(error)
+        CommandError::new(error.code(), error.to_string())
+    }
+}
+
+// Plain string errors from module helpers carry no code of their own
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::new("INTERNAL", message)
     }
 }
 
@@ -66,8 +173,9 @@ impl From<CredentialError> for CommandError {
         log::error!("Credential operation failed: {:?}", error);
         // Avoid leaking potentially sensitive details from StoreError
         match error {
-            CredentialError::Store(_) => CommandError::Credentials("Failed to access store.".to_string()),
-            _ => CommandError::Credentials(error.to_string()),
+            CredentialError::Store(_) => CommandError::new("CREDENTIALS", "Failed to access store."),
+            CredentialError::NotFound => CommandError::new("CREDENTIALS_NOT_FOUND", error.to_string()),
+            _ => CommandError::new("CREDENTIALS", error.to_string()),
         }
     }
 }
@@ -78,12 +186,46 @@ impl From<SettingsError> for CommandError {
         log::error!("Settings operation failed: {:?}", error);
         // Avoid leaking potentially sensitive details from StoreError
         match error {
-            SettingsError::Store(_) => CommandError::Settings("Failed to access settings store.".to_string()),
-            _ => CommandError::Settings(error.to_string()),
+            SettingsError::Store(_) => CommandError::new("SETTINGS", "Failed to access settings store."),
+            _ => CommandError::new("SETTINGS", error.to_string()),
         }
     }
 }
 
+// Memo validation failures keep their own codes so the composer can point at the problem
+impl From<crate::messaging_rpc::MemoError> for CommandError {
+    fn from(error: crate::messaging_rpc::MemoError) -> Self {
+        let code = match error {
+            crate::messaging_rpc::MemoError::TooLong { .. } => "MEMO_TOO_LONG",
+            crate::messaging_rpc::MemoError::ContainsNul { .. } => "MEMO_CONTAINS_NUL",
+        };
+        CommandError::new(code, error.to_string())
+    }
+}
+
+// Message store failures; a locked store and a wrong passphrase get their own codes so the UI can prompt
+impl From<crate::message_state::MessageStateError> for CommandError {
+    fn from(error: crate::message_state::MessageStateError) -> Self {
+        use crate::message_state::MessageStateError;
+        log::error!("Message store operation failed: {:?}", error);
+        let code = match error {
+            MessageStateError::Locked => "MESSAGE_STORE_LOCKED",
+            MessageStateError::WrongPassphrase => "WRONG_PASSPHRASE",
+            MessageStateError::EmptyPassphrase => "INVALID_ARGUMENT",
+            _ => "MESSAGE_STORE",
+        };
+        CommandError::new(code, error.to_string())
+    }
+}
+
+// Price history file failures (the RPC side of sample_price converts from VerusRpcError directly)
+impl From<crate::price_history::PriceHistoryError> for CommandError {
+    fn from(error: crate::price_history::PriceHistoryError) -> Self {
+        log::error!("Price history operation failed: {:?}", error);
+        CommandError::new("PRICE_HISTORY", error.to_string())
+    }
+}
+
 // macOS window customization function
 #[cfg(target_os = "macos")]
 fn set_macos_window_background(window: &tauri::WebviewWindow) {
@@ -108,15 +250,43 @@ fn set_macos_window_background(window: &tauri::WebviewWindow) {
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
-async fn connect_verus_daemon(rpc_user: String, rpc_pass: String, rpc_port: u16) -> Result<u64, CommandError> {
+async fn connect_verus_daemon(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: Option<String>,
+    rpc_port: u16,
+    rpc_use_tls: Option<bool>,
+    rpc_accept_invalid_certs: Option<bool>,
+) -> Result<u64, CommandError> {
     // Ensure logging is initialized (can be done once at startup too)
     // TODO: Initialize logger properly in main/run function
-    let _ = env_logger::try_init();
+    crate::log_redaction::init();
 
-    log::info!("connect_verus_daemon command received");
-    crate::wallet_rpc::connect_and_get_block_height(rpc_user, rpc_pass, rpc_port) // Corrected path
-        .await
-        .map_err(CommandError::from)
+    let rpc_host = rpc_host
+        .filter(|h| !h.trim().is_empty())
+        .unwrap_or_else(|| crate::rpc_client::DEFAULT_RPC_HOST.to_string());
+    crate::log_redaction::register_credentials(&rpc_user, &rpc_pass);
+    log::info!("connect_verus_daemon command received (host: {})", rpc_host);
+
+    // Register transport options before testing so unsaved TLS settings are honored
+    let use_tls = rpc_use_tls.unwrap_or(false);
+    crate::rpc_client::set_endpoint_options(&rpc_host, rpc_port, crate::rpc_client::EndpointOptions {
+        use_tls,
+        accept_invalid_certs: use_tls && rpc_accept_invalid_certs.unwrap_or(false),
+        ..Default::default()
+    });
+    match crate::wallet_rpc::connect_and_get_block_height(rpc_user, rpc_pass, rpc_host.clone(), rpc_port).await {
+        Ok(height) => Ok(height),
+        Err(crate::rpc_client::VerusRpcError::ConnectionRefused) => {
+            log::warn!("Daemon at {}:{} refused the connection - not running or wrong host/port", rpc_host, rpc_port);
+            Err(CommandError::from(crate::rpc_client::VerusRpcError::ConnectionRefused))
+        }
+        Err(crate::rpc_client::VerusRpcError::Unauthorized) => {
+            log::warn!("Daemon at {}:{} rejected the RPC credentials", rpc_host, rpc_port);
+            Err(CommandError::from(crate::rpc_client::VerusRpcError::Unauthorized))
+        }
+        Err(e) => Err(CommandError::from(e)),
+    }
 }
 
 // New command to get formatted identities (fast mode - no balances)
@@ -128,7 +298,42 @@ async fn get_login_identities_fast(
     // Load credentials first
     let creds = crate::credentials::load_credentials(app).await?;
     // Then call the RPC function
-    crate::identity_rpc::get_login_identities_fast(creds.rpc_user, creds.rpc_pass, creds.rpc_port)
+    crate::identity_rpc::get_login_identities_fast(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port)
+        .await
+        .map_err(CommandError::from)
+}
+
+// NEW command to get one page of login identities (balances only when include_balances is set)
+#[tauri::command]
+async fn get_login_identities_page(
+    app: tauri::AppHandle,
+    offset: usize,
+    limit: usize,
+    include_balances: Option<bool>,
+) -> Result<LoginIdentityPage, CommandError> {
+    log::info!("get_login_identities_page command received: offset={}, limit={}", offset, limit);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::identity_rpc::get_login_identities_page(
+        creds.rpc_user,
+        creds.rpc_pass,
+        creds.rpc_host,
+        creds.rpc_port,
+        offset,
+        limit,
+        include_balances.unwrap_or(false),
+    )
+    .await
+    .map_err(CommandError::from)
+}
+
+// NEW command: total shielded balance across all login identities
+#[tauri::command]
+async fn get_total_private_balance(
+    app: tauri::AppHandle,
+) -> Result<TotalPrivateBalance, CommandError> {
+    log::info!("get_total_private_balance command received");
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::identity_rpc::get_total_private_balance(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port)
         .await
         .map_err(CommandError::from)
 }
@@ -137,12 +342,13 @@ async fn get_login_identities_fast(
 #[tauri::command]
 async fn get_login_identities(
     app: tauri::AppHandle, // Need AppHandle to get stored credentials
+    min_conf: Option<u32>,
 ) -> Result<Vec<FormattedIdentity>, CommandError> {
-    log::info!("get_login_identities command received");
+    log::info!("get_login_identities command received (min_conf {:?})", min_conf);
     // Load credentials first
     let creds = crate::credentials::load_credentials(app).await?;
     // Then call the RPC function
-    crate::identity_rpc::get_login_identities(creds.rpc_user, creds.rpc_pass, creds.rpc_port) // Corrected path
+    crate::identity_rpc::get_login_identities(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, min_conf) // Corrected path
         .await
         .map_err(CommandError::from)
 }
@@ -155,7 +361,7 @@ async fn get_identity_balance(
 ) -> Result<f64, CommandError> {
     log::info!("get_identity_balance command received for address: {}", private_address);
     let creds = crate::credentials::load_credentials(app).await?;
-    crate::identity_rpc::get_identity_balance(creds.rpc_user, creds.rpc_pass, creds.rpc_port, private_address)
+    crate::identity_rpc::get_identity_balance(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, private_address)
         .await
         .map_err(CommandError::from)
 }
@@ -165,10 +371,11 @@ async fn get_identity_balance(
 async fn get_private_balance(
     app: tauri::AppHandle, // Need AppHandle for credentials
     address: String,
+    min_conf: Option<u32>, // Defaults to 1
 ) -> Result<f64, CommandError> {
     log::info!("get_private_balance command received for address: {}", address);
     let creds = crate::credentials::load_credentials(app).await?;
-    crate::wallet_rpc::get_private_balance(creds.rpc_user, creds.rpc_pass, creds.rpc_port, address) // Correct path
+    crate::wallet_rpc::get_private_balance(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, address, min_conf) // Correct path
         .await
         .map_err(CommandError::from)
 }
@@ -181,7 +388,35 @@ async fn get_pending_balance(
 ) -> Result<f64, CommandError> {
     log::info!("get_pending_balance command received for address: {}", address);
     let creds = crate::credentials::load_credentials(app).await?;
-    crate::wallet_rpc::get_pending_balance(creds.rpc_user, creds.rpc_pass, creds.rpc_port, address)
+    crate::wallet_rpc::get_pending_balance(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, address)
+        .await
+        .map_err(CommandError::from)
+}
+
+// Balance of a transparent address; minconf defaults to 1
+#[tauri::command]
+async fn get_transparent_balance(
+    app: tauri::AppHandle,
+    address: String,
+    minconf: Option<u32>,
+) -> Result<f64, CommandError> {
+    log::info!("get_transparent_balance command received for address: {}", address);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::wallet_rpc::get_transparent_balance(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, address, minconf.unwrap_or(1))
+        .await
+        .map_err(CommandError::from)
+}
+
+// Balance changes of transparent addresses since start_height (needs -addressindex on the daemon)
+#[tauri::command]
+async fn get_address_deltas(
+    app: tauri::AppHandle,
+    addresses: Vec<String>,
+    start_height: u64,
+) -> Result<Vec<AddressDelta>, CommandError> {
+    log::info!("get_address_deltas command received for {} address(es) from height {}", addresses.len(), start_height);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::wallet_rpc::fetch_address_deltas(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &addresses, start_height)
         .await
         .map_err(CommandError::from)
 }
@@ -194,11 +429,23 @@ async fn check_identity_eligibility(
 ) -> Result<FormattedIdentity, CommandError> {
     log::info!("check_identity_eligibility command received for: {}", target_identity_name);
     let creds = crate::credentials::load_credentials(app).await?;
-    crate::identity_rpc::check_identity_eligibility(creds.rpc_user, creds.rpc_pass, creds.rpc_port, target_identity_name) // Corrected path
+    crate::identity_rpc::check_identity_eligibility(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, target_identity_name) // Corrected path
         .await
         .map_err(CommandError::from) // Uses the updated From implementation
 }
 
+// NEW Command: Fast check that an identity can receive messages (run before check_identity_eligibility)
+#[tauri::command]
+async fn quick_messageable_check(
+    app: tauri::AppHandle,
+    identity_name: String,
+) -> Result<bool, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::identity_rpc::quick_messageable_check(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &identity_name)
+        .await
+        .map_err(CommandError::from)
+}
+
 // NEW Command: Check if an identity exists
 #[tauri::command]
 async fn check_identity_exists(
@@ -207,7 +454,7 @@ async fn check_identity_exists(
 ) -> Result<bool, CommandError> {
     log::info!("check_identity_exists command received for: {}", identity_name);
     let creds = crate::credentials::load_credentials(app).await?;
-    crate::identity_rpc::check_identity_exists(creds.rpc_user, creds.rpc_pass, creds.rpc_port, identity_name)
+    crate::identity_rpc::check_identity_exists(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, identity_name)
         .await
         .map_err(CommandError::from)
 }
@@ -221,7 +468,7 @@ async fn get_chat_history(
 ) -> Result<Vec<ChatMessage>, CommandError> {
     log::info!("get_chat_history command received from: {} for owner: {}", target_identity_name, own_private_address);
     let creds = crate::credentials::load_credentials(app).await?;
-    crate::message_rpc::get_chat_history(creds.rpc_user, creds.rpc_pass, creds.rpc_port, target_identity_name, own_private_address) // Corrected path
+    crate::message_rpc::get_chat_history(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, target_identity_name, own_private_address) // Corrected path
         .await
         .map_err(CommandError::from)
 }
@@ -234,7 +481,7 @@ async fn get_new_received_messages(
 ) -> Result<Vec<ChatMessage>, CommandError> {
     log::info!("get_new_received_messages command received for owner: {}", own_private_address);
     let creds = crate::credentials::load_credentials(app).await?;
-    crate::message_rpc::get_new_received_messages(creds.rpc_user, creds.rpc_pass, creds.rpc_port, own_private_address) // Corrected path
+    crate::message_rpc::get_new_received_messages(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, own_private_address) // Corrected path
         .await
         .map_err(CommandError::from)
 }
@@ -259,6 +506,7 @@ async fn send_private_message(
     crate::message_rpc::send_private_message( // Corrected path
         creds.rpc_user,
         creds.rpc_pass,
+        creds.rpc_host,
         creds.rpc_port,
         sender_z_address,
         recipient_z_address,
@@ -275,18 +523,206 @@ async fn send_private_message(
 async fn get_utxo_info(
     app: tauri::AppHandle,
     address: String,
+    min_usable: Option<f64>,
 ) -> Result<UtxoInfo, CommandError> {
     log::info!("get_utxo_info command received for address: {}", address);
     let creds = crate::credentials::load_credentials(app).await?;
-    crate::wallet_rpc::get_utxo_info(creds.rpc_user, creds.rpc_pass, creds.rpc_port, address)
+    let min_usable = min_usable.unwrap_or(crate::wallet_rpc::DEFAULT_MIN_USABLE_UTXO);
+    crate::wallet_rpc::get_utxo_info(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, address, min_usable)
         .await
         .map_err(CommandError::from)
 }
 
+// NEW command to get UTXO info for several addresses at once
+#[tauri::command]
+async fn get_wallet_utxo_summary(
+    app: tauri::AppHandle,
+    addresses: Vec<String>,
+    min_usable: Option<f64>,
+) -> Result<WalletUtxoSummary, CommandError> {
+    log::info!("get_wallet_utxo_summary command received for {} addresses", addresses.len());
+    let creds = crate::credentials::load_credentials(app).await?;
+    let min_usable = min_usable.unwrap_or(crate::wallet_rpc::DEFAULT_MIN_USABLE_UTXO);
+    crate::wallet_rpc::get_wallet_utxo_summary(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, addresses, min_usable)
+        .await
+        .map_err(CommandError::from)
+}
+
+// How many messages an address can fund (UTXO- vs balance-limited)
+#[tauri::command]
+async fn estimate_message_capacity(
+    app: tauri::AppHandle,
+    address: String,
+) -> Result<MessageCapacity, CommandError> {
+    log::info!("estimate_message_capacity command received for address: {}", address);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::wallet_rpc::estimate_message_capacity(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, address)
+        .await
+        .map_err(CommandError::from)
+}
+
+// NEW command to merge dust UTXOs; returns the opid (None if no merge was needed)
+#[tauri::command]
+async fn consolidate_utxos(
+    app: tauri::AppHandle,
+    address: String,
+    target_count: u32,
+) -> Result<Option<String>, CommandError> {
+    log::info!("consolidate_utxos command received for address: {}, target: {}", address, target_count);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::wallet_rpc::consolidate_utxos(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, address, target_count)
+        .await
+        .map_err(CommandError::from)
+}
+
+// NEW command to sweep several addresses into one (returns the z_mergetoaddress opid)
+#[tauri::command]
+async fn sweep_to_address(
+    app: tauri::AppHandle,
+    from_addresses: Vec<String>,
+    to_address: String,
+    fee: Option<f64>,
+) -> Result<String, CommandError> {
+    log::info!("sweep_to_address command received: {} address(es) -> {}", from_addresses.len(), to_address);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::wallet_rpc::sweep_to_address(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, from_addresses, to_address, fee)
+        .await
+        .map_err(CommandError::from)
+}
+
+// Split one address's balance into `count` outputs of `per_amount`; returns the opid
+#[tauri::command]
+async fn split_utxos(
+    app: tauri::AppHandle,
+    address: String,
+    count: u32,
+    per_amount: f64,
+) -> Result<String, CommandError> {
+    log::info!("split_utxos command received for address: {}, {} x {}", address, count, per_amount);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::wallet_rpc::split_utxos(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, address, count, per_amount)
+        .await
+        .map_err(CommandError::from)
+}
+
+// NEW command to import a shielded key (e.g. restoring a VerusID's messaging address)
+#[tauri::command]
+async fn import_z_key(
+    app: tauri::AppHandle,
+    z_private_key: String,
+    rescan: RescanMode,
+) -> Result<ZKeyImport, CommandError> {
+    log::info!("import_z_key command received (rescan: {:?})", rescan);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::wallet_rpc::import_z_key(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, z_private_key, rescan)
+        .await
+        .map_err(CommandError::from)
+}
+
+// NEW command to get the revision history of a VerusID
+#[tauri::command]
+async fn get_identity_history(
+    app: tauri::AppHandle,
+    identity_name: String,
+) -> Result<Vec<IdentityRevision>, CommandError> {
+    log::info!("get_identity_history command received for: {}", identity_name);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::identity_rpc::fetch_identity_history(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &identity_name)
+        .await
+        .map_err(CommandError::from)
+}
+
+// Sign an arbitrary message with an R-address or VerusID (name@) held by the wallet
+#[tauri::command]
+async fn sign_message(
+    app: tauri::AppHandle,
+    identity_or_address: String,
+    message: String,
+) -> Result<String, CommandError> {
+    log::info!("sign_message command received for: {}", identity_or_address);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::rpc_client::sign_message(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &identity_or_address, &message)
+        .await
+        .map(|response| response.signature)
+        .map_err(CommandError::from)
+}
+
+// Verify a signature made by an R-address or VerusID (invalid signatures return false)
+#[tauri::command]
+async fn verify_message(
+    app: tauri::AppHandle,
+    identity_or_address: String,
+    signature: String,
+    message: String,
+) -> Result<bool, CommandError> {
+    log::info!("verify_message command received for: {}", identity_or_address);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::rpc_client::verify_message(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &identity_or_address, &signature, &message)
+        .await
+        .map_err(CommandError::from)
+}
+
+// NEW command to drain a finished z_sendmany operation and get its txid
+#[tauri::command]
+async fn get_operation_result(
+    app: tauri::AppHandle,
+    opid: String,
+) -> Result<OperationResult, CommandError> {
+    log::info!("get_operation_result command received for opid: {}", opid);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::messaging_rpc::fetch_operation_result(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &opid)
+        .await
+        .map_err(CommandError::from)
+}
+
+// Wait for a z_sendmany operation: true = success, false = timeout/cancelled, OperationFailed = failed
+#[tauri::command]
+async fn wait_for_operation(
+    app: tauri::AppHandle,
+    opid: String,
+    interval_secs: u64,
+    timeout_secs: u64,
+    cancel_key: Option<String>,
+) -> Result<bool, CommandError> {
+    log::info!("wait_for_operation command received for opid: {}", opid);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::messaging_rpc::wait_for_operation_completion(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &opid, interval_secs, timeout_secs, cancel_key)
+        .await
+        .map_err(CommandError::from)
+}
+
+// Inspect a wallet shielded transaction's outputs and memos (NotFoundOrIneligible for unknown txids)
+#[tauri::command]
+async fn view_shielded_transaction(
+    app: tauri::AppHandle,
+    txid: String,
+) -> Result<ShieldedTx, CommandError> {
+    log::info!("view_shielded_transaction command received for txid: {}", txid);
+    let creds = crate::credentials::load_credentials(app).await?;
+    crate::messaging_rpc::fetch_shielded_transaction(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &txid)
+        .await
+        .map_err(CommandError::from)
+}
+
+// Stop everything that polls the daemon: wait_for_* loops and the message watcher
+fn stop_background_polling() {
+    let waits = crate::wait_cancel::cancel_all_waits();
+    let watcher = crate::messaging_rpc::abort_message_watcher();
+    log::info!("Background polling stopped ({} keyed waits, watcher running: {})", waits, watcher);
+}
+
+// Cancel all background pollers before the app exits (also run when the main window closes)
+#[tauri::command]
+async fn shutdown() -> Result<(), CommandError> {
+    log::info!("shutdown command received");
+    stop_background_polling();
+    Ok(())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // TODO: Initialize logger here instead of in command
-    env_logger::init(); // Basic logger initialization
+    // Redacting wrapper around env_logger (connect_verus_daemon also calls this; it's idempotent)
+    crate::log_redaction::init();
 
     let store_plugin = tauri_plugin_store::Builder::default().build(); // Build the store plugin instance
 
@@ -337,24 +773,43 @@ pub fn run() {
             
             Ok(())
         })
+        .on_window_event(|window, event| {
+            // Closing the main window ends the app - stop polling a daemon that may be going away too
+            if window.label() == "main" && matches!(event, tauri::WindowEvent::CloseRequested { .. }) {
+                stop_background_polling();
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             connect_verus_daemon,
             crate::credentials::save_credentials, // Add credential commands
             crate::credentials::load_credentials,
             crate::credentials::clear_credentials,
+            crate::credentials::list_profiles,
+            crate::credentials::add_profile,
+            crate::credentials::activate_profile,
+            crate::credentials::delete_profile,
+            crate::credentials::test_connection,
             crate::credentials::detect_all_blockchains, // NEW: Parallel detection
             crate::credentials::select_folder_dialog, // NEW: Folder selection
             crate::credentials::detect_blockchain_from_path, // NEW: Custom path detection
             get_login_identities_fast, // NEW: Fast loading without balances
+            get_login_identities_page,
             get_login_identities, // Correct name used here
             get_identity_balance, // NEW: Individual balance fetching
+            get_total_private_balance,
             get_private_balance, // Add the new balance command
             get_pending_balance, // Add the new pending balance command
+            get_transparent_balance,
+            get_address_deltas,
             check_identity_eligibility,
+            quick_messageable_check,
             check_identity_exists, // NEW: For name/referral validation
             get_chat_history,
             get_new_received_messages,
             send_private_message, // Added send message command
+            // Diagnostics
+            crate::log_redaction::set_log_level,
+            crate::log_redaction::get_recent_logs,
             // New Settings Commands
             crate::settings::save_persistence_setting,
             crate::settings::load_persistence_setting,
@@ -363,33 +818,94 @@ pub fn run() {
             crate::settings::save_messages_for_conversation,
             crate::settings::load_messages_for_conversation,
             crate::settings::delete_chat_data,
+            crate::message_state::mark_read,
+            crate::message_state::mark_unread,
+            crate::message_state::get_read_txids,
+            crate::message_state::unlock_message_store,
+            crate::message_state::lock_message_store,
+            crate::message_state::get_message_store_status,
             get_utxo_info,
+            get_wallet_utxo_summary,
+            estimate_message_capacity,
+            consolidate_utxos,
+            sweep_to_address,
+            split_utxos,
+            import_z_key,
             // Namespace commands
             crate::namespace_rpc::get_available_namespaces,
+            crate::namespace_rpc::get_namespace,
             crate::namespace_rpc::get_root_currency,
+            crate::namespace_rpc::get_registration_options,
             crate::namespace_rpc::get_currency,
+            crate::namespace_rpc::get_currency_reserves,
+            crate::namespace_rpc::estimate_registration_cost,
+            crate::namespace_rpc::find_conversion_path,
             // Identity registration commands
             crate::identity_rpc::get_new_address,
             crate::identity_rpc::get_new_private_address,
+            crate::identity_rpc::prepare_subid_registration,
             crate::identity_rpc::register_name_commitment,
             crate::identity_rpc::register_identity,
             crate::identity_rpc::get_transaction_confirmations,
+            crate::identity_rpc::get_transaction_status,
             crate::identity_rpc::wait_for_confirmations,
             crate::identity_rpc::get_identity,
+            crate::identity_rpc::clear_identity_cache,
+            crate::identity_rpc::get_identity_content,
+            crate::identity_rpc::set_identity_content,
+            crate::identity_rpc::update_identity_private_address,
             crate::identity_rpc::check_identity_ready,
             crate::identity_rpc::wait_for_identity_ready,
             crate::identity_rpc::dump_privkey,
             crate::identity_rpc::export_z_key,
+            crate::identity_rpc::export_identity_backup,
+            crate::identity_rpc::recommended_confirmations,
             // Wallet commands
             crate::wallet_rpc::get_wallet_info,
             // Currency conversion commands
             crate::wallet_rpc::estimate_currency_conversion,
+            crate::wallet_rpc::estimate_conversion_input,
+            crate::wallet_rpc::estimate_currency_conversion_quote,
+            crate::price_history::sample_price,
+            crate::price_history::get_price_history,
+            crate::currency_names::resolve_currency_name,
+            crate::currency_names::resolve_currency_names,
             crate::wallet_rpc::get_wallet_addresses, // NEW
             crate::wallet_rpc::get_address_currency_balances, // NEW
             crate::wallet_rpc::get_address_currency_balance, // NEW - single currency balance
             crate::wallet_rpc::send_currency_conversion, // NEW
+            crate::wallet_rpc::validate_address,
+            crate::wallet_rpc::get_chain_info,
             crate::wallet_rpc::get_current_block_height, // NEW
             crate::wallet_rpc::wait_for_block_increase, // NEW
+            crate::wallet_rpc::get_sync_status,
+            crate::wallet_rpc::get_network_health,
+            crate::wallet_rpc::wait_for_sync,
+            crate::wallet_rpc::get_transaction_history,
+            crate::wallet_rpc::get_transaction_fee,
+            crate::wallet_rpc::get_transaction_reserve_transfers,
+            crate::wallet_rpc::export_transactions_csv,
+            crate::wallet_rpc::unlock_wallet,
+            crate::wallet_rpc::lock_wallet,
+            // Async messaging commands
+            crate::messaging_rpc::send_private_message_async,
+            crate::messaging_rpc::send_private_message_multi,
+            crate::messaging_rpc::send_long_message_async,
+            crate::messaging_rpc::check_memo,
+            crate::messaging_rpc::start_message_watcher,
+            crate::messaging_rpc::stop_message_watcher,
+            crate::messaging_rpc::get_operation_status,
+            crate::messaging_rpc::list_operations,
+            wait_for_operation,
+            view_shielded_transaction,
+            crate::wait_cancel::cancel_wait,
+            shutdown,
+            get_operation_result,
+            sign_message,
+            verify_message,
+            get_identity_history,
+            crate::messaging_rpc::get_received_messages,
+            crate::messaging_rpc::get_conversations,
 
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,304 @@
+// File: src-tauri/src/log_redaction.rs
+// Description: Logger wrapper that scrubs RPC credentials and exported keys from all log output.
+// Changes:
+// - Created file: RedactingLogger wraps env_logger and replaces registered secrets (rpc_user/rpc_pass,
+//   exported private keys) and any HTTP Basic auth token with a placeholder before a line is written.
+// - Added set_log_level (runtime level override on top of RUST_LOG, "default" to clear it) and
+//   get_recent_logs (tail of an in-memory buffer of the last MAX_RECENT_LOGS redacted lines)
+// - Basic auth redaction only blanks Authorization header values and base64-looking tokens after "Basic "
+//   (prose using the word is kept); key_fingerprint shows a SHA-256 prefix instead of the key's first characters
+// - set_log_level / get_recent_logs return CommandError (INVALID_ARGUMENT for an unknown level); init records
+//   the RUST_LOG level even when a logger was already installed
+
+use log::{LevelFilter, Log, Metadata, Record};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::CommandError;
+
+const REDACTED: &str = "[REDACTED]";
+
+// Secrets shorter than this are not scrubbed - replacing a 1-3 character string would mangle
+// unrelated words in every log line
+const MIN_SECRET_LEN: usize = 4;
+
+// Everything registered so far (credentials survive re-registration, so this only grows)
+static SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+// Runtime level override: 0 = none (RUST_LOG decides), otherwise LevelFilter as usize + 1
+static LEVEL_OVERRIDE: AtomicUsize = AtomicUsize::new(0);
+
+// Max level RUST_LOG allows, restored when the override is cleared (Error until init runs)
+static ENV_MAX_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Error as usize);
+
+// Lines kept for get_recent_logs (support panel)
+const MAX_RECENT_LOGS: usize = 1000;
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::Off,
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+fn level_override() -> Option<LevelFilter> {
+    match LEVEL_OVERRIDE.load(Ordering::Relaxed) {
+        0 => None,
+        n => LEVELS.get(n - 1).copied(),
+    }
+}
+
+struct RedactingLogger {
+    env: env_logger::Logger,    // RUST_LOG filter, used while no override is set
+    writer: env_logger::Logger, // Same output config with everything enabled; does the writing
+}
+
+impl Log for RedactingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        match level_override() {
+            Some(level) => metadata.level() <= level,
+            None => self.env.enabled(metadata),
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        let wanted = match level_override() {
+            Some(level) => record.level() <= level,
+            None => self.env.matches(record),
+        };
+        if !wanted {
+            return;
+        }
+        let message = redact(&record.args().to_string());
+        remember(record, &message);
+        self.writer.log(
+            &Record::builder()
+                .args(format_args!("{}", message))
+                .level(record.level())
+                .target(record.target())
+                .module_path(record.module_path())
+                .file(record.file())
+                .line(record.line())
+                .build(),
+        );
+    }
+
+    fn flush(&self) {
+        self.writer.flush();
+    }
+}
+
+// Append a (redacted) line to the recent-logs buffer, dropping the oldest past MAX_RECENT_LOGS
+fn remember(record: &Record, message: &str) {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let line = format!("[{}] {} {}: {}", secs, record.level(), record.target(), message);
+    let mut recent = RECENT_LOGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if recent.len() >= MAX_RECENT_LOGS {
+        recent.pop_front();
+    }
+    recent.push_back(line);
+}
+
+/// Install the redacting logger (env_logger configuration via RUST_LOG). Safe to call more than once.
+pub fn init() {
+    let env = env_logger::Builder::from_default_env().build();
+    // RUST_LOG module directives still apply to the writer; only the global level is opened up
+    let writer = env_logger::Builder::from_default_env().filter_level(LevelFilter::Trace).build();
+    let max_level = env.filter();
+    // Stored even if a logger is already installed, so "default" never falls back to Off
+    ENV_MAX_LEVEL.store(max_level as usize, Ordering::Relaxed);
+    if log::set_boxed_logger(Box::new(RedactingLogger { env, writer })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Change the log level at runtime: off/error/warn/info/debug/trace, or "default" to go back to RUST_LOG.
+/// Returns the level now in effect.
+#[tauri::command]
+pub async fn set_log_level(level: String) -> Result<String, CommandError> {
+    let level = level.trim().to_ascii_lowercase();
+    let effective = if level == "default" {
+        LEVEL_OVERRIDE.store(0, Ordering::Relaxed);
+        LEVELS.get(ENV_MAX_LEVEL.load(Ordering::Relaxed)).copied().unwrap_or(LevelFilter::Error)
+    } else {
+        let filter: LevelFilter = level.parse().map_err(|_| {
+            CommandError::invalid_argument(format!(
+                "Unknown log level '{}' (expected off, error, warn, info, debug, trace or default)",
+                level
+            ))
+        })?;
+        LEVEL_OVERRIDE.store(filter as usize + 1, Ordering::Relaxed);
+        filter
+    };
+    log::set_max_level(effective);
+    log::info!("Log level set to {}", effective);
+    Ok(effective.to_string().to_lowercase())
+}
+
+/// The most recent log lines (oldest first), already redacted; `lines` defaults to 200
+#[tauri::command]
+pub async fn get_recent_logs(lines: Option<usize>) -> Result<Vec<String>, CommandError> {
+    let lines = lines.unwrap_or(200).min(MAX_RECENT_LOGS);
+    let recent = RECENT_LOGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    Ok(recent.iter().skip(recent.len().saturating_sub(lines)).cloned().collect())
+}
+
+/// Register a value that must never appear in logs
+pub fn register_secret(secret: &str) {
+    if secret.len() < MIN_SECRET_LEN {
+        return;
+    }
+    let mut secrets = SECRETS.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if !secrets.iter().any(|s| s == secret) {
+        secrets.push(secret.to_string());
+        // Longest first, so a secret containing another one is replaced whole
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    }
+}
+
+/// Register RPC credentials for scrubbing
+pub fn register_credentials(rpc_user: &str, rpc_pass: &str) {
+    register_secret(rpc_user);
+    register_secret(rpc_pass);
+}
+
+/// Replace registered secrets and Basic auth tokens in a message
+pub fn redact(message: &str) -> String {
+    let mut redacted = redact_basic_auth(message);
+    let secrets = SECRETS.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+    for secret in secrets.iter() {
+        if redacted.contains(secret.as_str()) {
+            redacted = redacted.replace(secret.as_str(), REDACTED);
+        }
+    }
+    redacted
+}
+
+/// Short, non-reversible label for a private key in logs (first 8 hex digits of its SHA-256, and length)
+pub fn key_fingerprint(key: &str) -> String {
+    let digest = Sha256::digest(key.as_bytes());
+    format!("{}…({} chars)", hex::encode(&digest[..4]), key.chars().count())
+}
+
+// Header name whose value is always blanked (matched case-insensitively)
+const AUTHORIZATION_HEADER: &str = "authorization:";
+
+// Auth scheme (matched case-insensitively) followed by the base64 user:pass token
+const BASIC_SCHEME: &str = "basic ";
+
+// Shortest base64 run after BASIC_SCHEME that is treated as a token ("a:bc" encodes to 8 characters)
+const MIN_BASIC_TOKEN_LEN: usize = 8;
+
+// Blank HTTP Basic credentials: the value of an Authorization header, and a base64 token after "Basic ".
+// Ordinary prose mentioning "basic" is left alone.
+fn redact_basic_auth(message: &str) -> String {
+    redact_basic_tokens(&redact_authorization_headers(message))
+}
+
+// "Authorization: <value>" keeps the header name; the value runs to the end of the line or a quote
+fn redact_authorization_headers(message: &str) -> String {
+    // ASCII lowercasing keeps byte offsets, so positions found in `lower` index `message`
+    let lower = message.to_ascii_lowercase();
+    let mut result = String::with_capacity(message.len());
+    let mut rest = 0;
+    while let Some(found) = lower[rest..].find(AUTHORIZATION_HEADER) {
+        let name_end = rest + found + AUTHORIZATION_HEADER.len();
+        let value_start = message.len() - message[name_end..].trim_start_matches([' ', '\t']).len();
+        let value_end = message[value_start..]
+            .find(['\r', '\n', '"', '\''])
+            .map(|offset| value_start + offset)
+            .unwrap_or(message.len());
+        result.push_str(&message[rest..value_start]);
+        if value_end > value_start {
+            result.push_str(REDACTED);
+        }
+        rest = value_end;
+    }
+    result.push_str(&message[rest..]);
+    result
+}
+
+// "Basic <token>" where the token looks like base64 rather than a word (see looks_like_basic_token)
+fn redact_basic_tokens(message: &str) -> String {
+    let lower = message.to_ascii_lowercase();
+    let mut result = String::with_capacity(message.len());
+    let mut rest = 0;
+    let mut search = 0;
+    while let Some(found) = lower[search..].find(BASIC_SCHEME) {
+        let token_start = search + found + BASIC_SCHEME.len();
+        let token_end = message[token_start..]
+            .find(|c: char| !is_base64_char(c))
+            .map(|offset| token_start + offset)
+            .unwrap_or(message.len());
+        if looks_like_basic_token(&message[token_start..token_end]) {
+            result.push_str(&message[rest..token_start]);
+            result.push_str(REDACTED);
+            rest = token_end;
+        }
+        search = token_end;
+    }
+    result.push_str(&message[rest..]);
+    result
+}
+
+fn is_base64_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=')
+}
+
+// At least MIN_BASIC_TOKEN_LEN base64 characters, and not shaped like a word: past the first character
+// there is a digit, an uppercase letter, '+', '/' or '=' ("Basic Configuration" stays readable)
+fn looks_like_basic_token(token: &str) -> bool {
+    token.len() >= MIN_BASIC_TOKEN_LEN
+        && token.chars().all(is_base64_char)
+        && token.chars().skip(1).any(|c| !c.is_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_authorization_header_values() {
+        assert_eq!(
+            redact_basic_auth("POST / HTTP/1.1\r\nAuthorization: Basic dXNlcjpwYXNz\r\nHost: 127.0.0.1"),
+            "POST / HTTP/1.1\r\nAuthorization: [REDACTED]\r\nHost: 127.0.0.1"
+        );
+        assert_eq!(redact_basic_auth("authorization:Bearer abc.def"), "authorization:[REDACTED]");
+    }
+
+    #[test]
+    fn redacts_basic_tokens_outside_a_header() {
+        assert_eq!(
+            redact_basic_auth(r#"headers: {"authorization": "Basic dXNlcjpwYXNz"}"#),
+            r#"headers: {"authorization": "Basic [REDACTED]"}"#
+        );
+        assert_eq!(redact_basic_auth("sent basic dXNlcjpwYXNz=, retrying"), "sent basic [REDACTED], retrying");
+    }
+
+    #[test]
+    fn leaves_prose_mentioning_basic_alone() {
+        for message in [
+            "Using basic fee estimation",
+            "Basic Configuration loaded from settings",
+            "fell back to basic mode",
+            "a basic understanding of the basic scheme",
+        ] {
+            assert_eq!(redact_basic_auth(message), message);
+        }
+    }
+
+    #[test]
+    fn key_fingerprint_hides_the_key() {
+        let key = "secret-extended-key-main1qwertyuiop";
+        let fingerprint = key_fingerprint(key);
+        assert!(!fingerprint.contains("secr"));
+        assert!(fingerprint.ends_with(&format!("…({} chars)", key.chars().count())));
+        assert_eq!(fingerprint, key_fingerprint(key));
+        assert_ne!(fingerprint, key_fingerprint("secret-extended-key-main1asdfghjkl"));
+    }
+}
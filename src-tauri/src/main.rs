@@ -1,9 +1,6 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod credentials;
-mod settings;
-
 fn main() {
     chat_dapp_lib::run()
 }
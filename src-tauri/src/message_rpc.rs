@@ -11,11 +11,17 @@
 // - BREAKING: Extended message format to {message_text}//f//{sender_identity}//t//{unix_timestamp}//{signature}
 // - Zero-trust approach: Only verified messages are displayed, unverified messages are silently filtered
 // - Message sending fails if signing fails (no fallback to unsigned messages)
+// - RPC calls now take an rpc_host argument (configurable daemon host)
+// - send_private_message validates both z-addresses before signing (InvalidFormat on a malformed address)
+// - send_private_message checks the sender's shielded balance covers amount + fee first (InsufficientFunds)
+// - The signed memo is checked with messaging_rpc::validate_memo before sending (InvalidArgument with the byte count)
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use hex;
 use super::rpc_client::{make_rpc_call, sign_message, verify_message, VerusRpcError};
+use super::messaging_rpc::validate_memo;
+use super::wallet_rpc::{ensure_sufficient_funds, ensure_valid_address, get_private_balance, DEFAULT_TX_FEE};
 
 // Struct for imported chat messages
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -46,6 +52,7 @@ pub struct ReceivedByAddressEntry {
 async fn parse_and_verify_message(
     rpc_user: &str,
     rpc_pass: &str,
+    rpc_host: &str,
     rpc_port: u16,
     memo: &str,
     txid: &str,
@@ -69,7 +76,7 @@ async fn parse_and_verify_message(
                     let original_message = format!("{}//f//{}//t//{}", message_text, sender_id, timestamp);
                     
                     // Verify the signature
-                    match verify_message(rpc_user, rpc_pass, rpc_port, sender_id, signature, &original_message).await {
+                    match verify_message(rpc_user, rpc_pass, rpc_host, rpc_port, sender_id, signature, &original_message).await {
                         Ok(true) => {
                             log::debug!("Message verification successful for tx {}: '{}' from {} at timestamp {}", 
                                 txid, message_text, sender_id, timestamp);
@@ -107,6 +114,7 @@ async fn parse_and_verify_message(
 pub async fn get_chat_history(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
     target_identity_name: String, // The user we want history *from*
     own_private_address: String, // The logged-in user's z-addr
@@ -117,6 +125,7 @@ pub async fn get_chat_history(
     let received_txs: Vec<ReceivedByAddressEntry> = make_rpc_call(
         &rpc_user,
         &rpc_pass,
+        &rpc_host,
         rpc_port,
         "z_listreceivedbyaddress",
         params,
@@ -131,7 +140,7 @@ pub async fn get_chat_history(
         if let Some(memostr) = tx.memostr {
             // Parse and verify message - only verified messages are processed
             if let Some((message_text, sender_id, timestamp, _signature)) = 
-                parse_and_verify_message(&rpc_user, &rpc_pass, rpc_port, &memostr, &tx.txid).await {
+                parse_and_verify_message(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &memostr, &tx.txid).await {
                 
                 // Only process if this message is from the target identity
                 if sender_id == target_identity_name {
@@ -161,6 +170,7 @@ pub async fn get_chat_history(
 pub async fn get_new_received_messages(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
     own_private_address: String, // The logged-in user's z-addr
 ) -> Result<Vec<ChatMessage>, VerusRpcError> {
@@ -171,6 +181,7 @@ pub async fn get_new_received_messages(
     let received_txs: Vec<ReceivedByAddressEntry> = match make_rpc_call(
         &rpc_user,
         &rpc_pass,
+        &rpc_host,
         rpc_port,
         "z_listreceivedbyaddress",
         params,
@@ -192,7 +203,7 @@ pub async fn get_new_received_messages(
         if let Some(memostr) = tx.memostr {
             // Parse and verify message - only verified messages are processed
             if let Some((message_text, sender_id, timestamp, _signature)) = 
-                parse_and_verify_message(&rpc_user, &rpc_pass, rpc_port, &memostr, &tx.txid).await {
+                parse_and_verify_message(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &memostr, &tx.txid).await {
                 
                 // Validate sender format
                 let is_valid_sender = sender_id.ends_with('@') && sender_id.len() > 1;
@@ -235,6 +246,7 @@ pub async fn get_new_received_messages(
 pub async fn send_private_message(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
     sender_z_address: String,      // Logged-in user's private address
     recipient_z_address: String, // Target user's private address
@@ -254,6 +266,13 @@ pub async fn send_private_message(
     );
     log::debug!("Original memo text: \"{}\"", memo_text);
 
+    ensure_valid_address(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &sender_z_address).await?;
+    ensure_valid_address(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &recipient_z_address).await?;
+
+    // Shielded sends pay the default fee in the native currency, same as the amount
+    let available = get_private_balance(rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port, sender_z_address.clone(), None).await?;
+    ensure_sufficient_funds(available, amount, DEFAULT_TX_FEE, true)?;
+
     // 1. Generate UTC timestamp when sending to blockchain
     let timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -265,7 +284,7 @@ pub async fn send_private_message(
     log::debug!("Base message for signing: \"{}\" (timestamp: {})", base_message, timestamp);
 
     // 3. MANDATORY SIGNING: Sign the base message
-    let signature_response = match sign_message(&rpc_user, &rpc_pass, rpc_port, &sender_identity, &base_message).await {
+    let signature_response = match sign_message(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &sender_identity, &base_message).await {
         Ok(sig) => {
             log::info!("Message signed successfully. Hash: {}", sig.hash);
             sig
@@ -281,10 +300,10 @@ pub async fn send_private_message(
     log::debug!("Constructed signed memo string: \"{}\"", full_memo);
 
     // 5. Convert the memo string to its hexadecimal representation
-    // Ensure the memo is not too long - z_sendmany memo limit is typically 512 bytes.
-    // Hex encoding doubles the length, so the original memo should be < 256 bytes.
-    // The frontend already limits input to 412 characters, which is safe.
-    let memo_hex = hex::encode(full_memo.as_bytes());
+    // z_sendmany memos are at most 512 bytes; the signature and header count against that, so check
+    // the full memo rather than trusting the frontend's character limit.
+    let memo_bytes = validate_memo(&full_memo).map_err(|e| VerusRpcError::InvalidArgument(e.to_string()))?;
+    let memo_hex = hex::encode(memo_bytes);
     log::debug!("Hex encoded memo: {}", memo_hex);
 
     // 6. Construct the parameters for the z_sendmany RPC call
@@ -305,7 +324,7 @@ pub async fn send_private_message(
 
     // 7. Make the RPC call
     log::info!("Executing z_sendmany with signed message...");
-    match make_rpc_call::<String>(&rpc_user, &rpc_pass, rpc_port, "z_sendmany", params).await {
+    match make_rpc_call::<String>(&rpc_user, &rpc_pass, &rpc_host, rpc_port, "z_sendmany", params).await {
         Ok(txid) => {
             log::info!("z_sendmany successful with signed message, txid: {}", txid);
             Ok(txid)
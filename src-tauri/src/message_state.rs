@@ -0,0 +1,292 @@
+// File: src-tauri/src/message_state.rs
+// Description: Local read/unread state for messages (memos on-chain carry no read flag).
+// Changes:
+// - Created file with mark_read, mark_unread and get_read_txids commands backed by
+//   message_state.json in the app data dir (same dir as the credentials/settings store).
+// - Writes go to a temp file first and are renamed into place so a crash can't corrupt the store.
+// - Optional encryption at rest: unlock_message_store derives a key from a passphrase (Argon2id) and the
+//   store is written as XChaCha20-Poly1305 ciphertext from then on. The key lives only in memory for the
+//   session (lock_message_store forgets it); without a passphrase the store stays plaintext, which
+//   get_message_store_status reports.
+// - Commands run on the blocking pool (Argon2 and file I/O would otherwise stall the async runtime while
+//   holding STATE_LOCK); the session key and passphrase are zeroed when dropped.
+// - Commands return CommandError (see the MessageStateError conversion in lib.rs)
+
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime};
+use zeroize::Zeroizing;
+use crate::CommandError;
+
+// File name inside the app data dir
+const STATE_FILE: &str = "message_state.json";
+
+// Serializes read-modify-write cycles so concurrent mark_* calls don't drop updates
+static STATE_LOCK: Mutex<()> = Mutex::new(());
+
+// Version of the encrypted envelope (bump if the KDF or cipher changes)
+const ENCRYPTION_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24; // XChaCha20-Poly1305
+
+// Store key for this session, set by unlock_message_store. Never written anywhere.
+static SESSION_KEY: Mutex<Option<SessionKey>> = Mutex::new(None);
+
+struct SessionKey {
+    key: Zeroizing<[u8; 32]>, // Wiped when the session is locked or replaced
+    salt: Vec<u8>, // Salt the key was derived with, reused on every write
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct MessageState {
+    #[serde(default)]
+    read_txids: HashSet<String>,
+}
+
+// Encrypted on-disk form: {"encrypted": {...}} (all byte fields hex-encoded)
+#[derive(Serialize, Deserialize, Debug)]
+struct EncryptedState {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+// What message_state.json holds; a bare MessageState is the plaintext (pre-passphrase) form
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+enum StoredState {
+    Encrypted { encrypted: EncryptedState },
+    Plain(MessageState),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MessageStoreStatus {
+    pub encrypted: bool, // false = plaintext on disk (no passphrase set)
+    pub unlocked: bool,  // The session key is in memory
+}
+
+#[derive(Debug, thiserror::Error, Serialize)]
+pub enum MessageStateError {
+    #[error("Could not resolve app data directory: {0}")]
+    Path(String),
+    #[error("I/O error: {0}")]
+    Io(String),
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+    #[error("Message store is locked - unlock it with your passphrase")]
+    Locked,
+    #[error("Wrong passphrase for the message store")]
+    WrongPassphrase,
+    #[error("Passphrase must not be empty")]
+    EmptyPassphrase,
+    #[error("Encryption error: {0}")]
+    Crypto(String),
+}
+
+impl From<std::io::Error> for MessageStateError {
+    fn from(error: std::io::Error) -> Self {
+        MessageStateError::Io(error.to_string())
+    }
+}
+
+// --- Helper Functions ---
+
+fn state_file_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, MessageStateError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| MessageStateError::Path(e.to_string()))?;
+    Ok(dir.join(STATE_FILE))
+}
+
+fn session_key() -> std::sync::MutexGuard<'static, Option<SessionKey>> {
+    SESSION_KEY.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, MessageStateError> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| MessageStateError::Crypto(e.to_string()))?;
+    Ok(key)
+}
+
+fn encrypt_state(session: &SessionKey, state: &MessageState) -> Result<EncryptedState, MessageStateError> {
+    let plaintext = serde_json::to_vec(state).map_err(|e| MessageStateError::Serialization(e.to_string()))?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(session.key.as_ref()));
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng); // Fresh nonce per write
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|e| MessageStateError::Crypto(e.to_string()))?;
+    Ok(EncryptedState {
+        version: ENCRYPTION_VERSION,
+        salt: hex::encode(&session.salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+// Authentication failure means the key is wrong (or the file was tampered with)
+fn decrypt_state(key: &[u8; 32], encrypted: &EncryptedState) -> Result<MessageState, MessageStateError> {
+    if encrypted.version != ENCRYPTION_VERSION {
+        return Err(MessageStateError::Crypto(format!("unsupported store version {}", encrypted.version)));
+    }
+    let corrupt = |what: &str| MessageStateError::Serialization(format!("invalid {} in encrypted store", what));
+    let nonce = hex::decode(&encrypted.nonce).map_err(|_| corrupt("nonce"))?;
+    if nonce.len() != NONCE_LEN {
+        return Err(corrupt("nonce"));
+    }
+    let ciphertext = hex::decode(&encrypted.ciphertext).map_err(|_| corrupt("ciphertext"))?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(key));
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| MessageStateError::WrongPassphrase)?;
+    serde_json::from_slice(&plaintext).map_err(|e| MessageStateError::Serialization(e.to_string()))
+}
+
+fn read_stored(path: &Path) -> Result<StoredState, MessageStateError> {
+    if !path.exists() {
+        return Ok(StoredState::Plain(MessageState::default()));
+    }
+    let contents = fs::read_to_string(path)?;
+    serde_json::from_str(&contents).map_err(|e| MessageStateError::Serialization(e.to_string()))
+}
+
+fn read_state(path: &Path) -> Result<MessageState, MessageStateError> {
+    match read_stored(path)? {
+        StoredState::Plain(state) => Ok(state),
+        StoredState::Encrypted { encrypted } => match session_key().as_ref() {
+            Some(session) => decrypt_state(&session.key, &encrypted),
+            None => Err(MessageStateError::Locked),
+        },
+    }
+}
+
+// Write via temp file + rename (atomic on the same filesystem). Encrypted whenever a session key is set.
+fn write_state(path: &Path, state: &MessageState) -> Result<(), MessageStateError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents = match session_key().as_ref() {
+        Some(session) => serde_json::to_string(&StoredState::Encrypted { encrypted: encrypt_state(session, state)? }),
+        None => serde_json::to_string(state),
+    }
+    .map_err(|e| MessageStateError::Serialization(e.to_string()))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn update_state<R: Runtime>(
+    app: &AppHandle<R>,
+    update: impl FnOnce(&mut MessageState) -> bool,
+) -> Result<(), MessageStateError> {
+    let _guard = STATE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let path = state_file_path(app)?;
+    let mut state = read_state(&path)?;
+    if update(&mut state) {
+        write_state(&path, &state)?;
+    }
+    Ok(())
+}
+
+// Run store work on the blocking pool: Argon2 derivation and file I/O must not stall the async runtime
+async fn run_blocking<T: Send + 'static>(
+    work: impl FnOnce() -> Result<T, MessageStateError> + Send + 'static,
+) -> Result<T, MessageStateError> {
+    tauri::async_runtime::spawn_blocking(work)
+        .await
+        .map_err(|e| MessageStateError::Io(format!("message store task failed: {}", e)))?
+}
+
+/// Load the set of txids the user has read
+pub fn load_read_txids<R: Runtime>(app: &AppHandle<R>) -> Result<HashSet<String>, MessageStateError> {
+    let _guard = STATE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let path = state_file_path(app)?;
+    Ok(read_state(&path)?.read_txids)
+}
+
+// --- Tauri Commands ---
+
+#[tauri::command]
+pub async fn mark_read<R: Runtime>(app: AppHandle<R>, txid: String) -> Result<(), CommandError> {
+    log::debug!("Marking message {} as read", txid);
+    Ok(run_blocking(move || update_state(&app, |state| state.read_txids.insert(txid))).await?)
+}
+
+#[tauri::command]
+pub async fn mark_unread<R: Runtime>(app: AppHandle<R>, txid: String) -> Result<(), CommandError> {
+    log::debug!("Marking message {} as unread", txid);
+    Ok(run_blocking(move || update_state(&app, |state| state.read_txids.remove(&txid))).await?)
+}
+
+#[tauri::command]
+pub async fn get_read_txids<R: Runtime>(app: AppHandle<R>) -> Result<HashSet<String>, CommandError> {
+    Ok(run_blocking(move || load_read_txids(&app)).await?)
+}
+
+/// Unlock an encrypted store, or encrypt a plaintext one under this passphrase (first use).
+/// The derived key is kept in memory until lock_message_store or app exit.
+#[tauri::command]
+pub async fn unlock_message_store<R: Runtime>(app: AppHandle<R>, passphrase: String) -> Result<MessageStoreStatus, CommandError> {
+    let passphrase = Zeroizing::new(passphrase);
+    if passphrase.is_empty() {
+        return Err(MessageStateError::EmptyPassphrase.into());
+    }
+    Ok(run_blocking(move || unlock_store(&app, &passphrase)).await?)
+}
+
+fn unlock_store<R: Runtime>(app: &AppHandle<R>, passphrase: &str) -> Result<MessageStoreStatus, MessageStateError> {
+    let _guard = STATE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let path = state_file_path(app)?;
+
+    match read_stored(&path)? {
+        StoredState::Encrypted { encrypted } => {
+            let salt = hex::decode(&encrypted.salt)
+                .map_err(|_| MessageStateError::Serialization("invalid salt in encrypted store".to_string()))?;
+            let key = derive_key(passphrase, &salt)?;
+            // Only keep the key once it has actually opened the store
+            decrypt_state(&key, &encrypted)?;
+            *session_key() = Some(SessionKey { key, salt });
+            log::info!("Message store unlocked");
+        }
+        StoredState::Plain(state) => {
+            let mut salt = vec![0u8; SALT_LEN];
+            OsRng.fill_bytes(&mut salt);
+            let key = derive_key(passphrase, &salt)?;
+            *session_key() = Some(SessionKey { key, salt });
+            write_state(&path, &state)?;
+            log::info!("Message store encrypted with a new passphrase");
+        }
+    }
+    Ok(MessageStoreStatus { encrypted: true, unlocked: true })
+}
+
+/// Forget the session key; an encrypted store is unreadable until unlocked again
+#[tauri::command]
+pub async fn lock_message_store() -> Result<(), CommandError> {
+    *session_key() = None;
+    log::info!("Message store locked");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_message_store_status<R: Runtime>(app: AppHandle<R>) -> Result<MessageStoreStatus, CommandError> {
+    let status = run_blocking(move || {
+        let _guard = STATE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let path = state_file_path(&app)?;
+        let encrypted = matches!(read_stored(&path)?, StoredState::Encrypted { .. });
+        Ok(MessageStoreStatus { encrypted, unlocked: session_key().is_some() })
+    })
+    .await?;
+    Ok(status)
+}
@@ -0,0 +1,1202 @@
+// File: src-tauri/src/messaging_rpc.rs
+// Description: Asynchronous shielded memo sending via z_sendmany with operation tracking.
+// Changes:
+// - Created file with send_private_message_async (z_sendmany -> opid), get_operation_status
+//   (z_getoperationstatus) and wait_for_operation (polling, mirrors wait_for_confirmations).
+// - Memos over 512 bytes are rejected before any RPC call is made.
+// - Added fetch_operation_result (z_getoperationresult) returning a typed OperationResult with the final txid.
+// - Added get_received_messages (z_listreceivedbyaddress) with memo decoding; change and empty memos are skipped.
+// - Added Message/Conversation and build_conversations: groups memos by counterparty, attributes memos
+//   carrying a sender VerusID via check_identity_eligibility, newest conversation first.
+// - build_conversations takes the locally stored read txids (message_state) when computing unread_count.
+// - send_private_message_async reports a locked wallet with the bare WalletLocked message.
+// - wait_for_operation accepts an optional cancel_key (see wait_cancel).
+// - wait_for_operation moved to wait_for_operation_completion (poll_until based); failed/cancelled
+//   operations return VerusRpcError::OperationFailed with the daemon's code and message.
+// - wait_for_operation_completion maps each status through operation_outcome (pure, tested)
+// - send_private_message_async validates both addresses before queueing z_sendmany.
+// - send_private_message_async takes an optional sender_identity, sent as a "from:<id@>" first memo line
+//   (counted against the 512-byte limit); get_received_messages strips it into ReceivedMessage.sender.
+// - Added send_long_message_async: messages over 512 bytes (up to MAX_CHUNKED_MESSAGE_BYTES) are split into
+//   "chunk:<id>:<seq>/<total>" memos; get_received_messages reassembles them (partial until all arrive).
+// - Added start_message_watcher / stop_message_watcher: one background poller emitting new-message events
+//   for memos not seen before (get_received_messages decoding shared via fetch_received_messages).
+// - Commands return CommandError (code from VerusRpcError::code) instead of a formatted String
+// - Received listings also drop notes from transactions the wallet itself sent (listtransactions "send"
+//   entries); get_received_messages takes include_change to keep change/self-sends for debugging.
+// - Added fetch_shielded_transaction (z_viewtransaction outputs with decoded memos; non-wallet txid -> NotFoundOrIneligible).
+// - Added list_operations (z_listoperationids + one z_getoperationstatus call); pending only unless include_finished.
+// - send_private_message_async: from_address is optional; None sends from "*" (any transparent wallet address),
+//   which requires allow_wildcard_source. The source must have a usable UTXO covering amount + fee.
+// - OperationResult carries the fee the transaction paid (looked up after a successful operation, best effort).
+// - Added validate_memo / MemoError (byte limit with the actual count, no NULs so the memo reads back intact);
+//   encode_memo uses it, and the check_memo command exposes it for the UI's byte counter.
+// - chunk_memo / encode_memo return MemoError (commands convert it to CommandError)
+// - Added abort_message_watcher (sync stop, used by the shutdown command)
+// - Added send_private_message_multi: one z_sendmany with an output (memo + amount_each) per recipient,
+//   capped at MAX_MULTI_RECIPIENTS; duplicate recipients are rejected since z_sendmany refuses them
+// - send_long_message_async checks the source covers amount plus one fee per chunk before the first send
+// - check_memo returns CommandError (MEMO_TOO_LONG / MEMO_CONTAINS_NUL) like the send commands
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tauri::Emitter;
+use tokio::time::Duration;
+use super::rpc_client::{make_rpc_call, VerusRpcError};
+use super::identity_rpc::check_identity_eligibility;
+use super::wallet_rpc::{
+    ensure_sufficient_funds, ensure_valid_address, fetch_transaction_fee, get_utxo_info, get_wildcard_utxo_info, list_wallet_transactions,
+    DEFAULT_MIN_USABLE_UTXO, DEFAULT_TX_FEE,
+};
+use crate::wait_cancel::{poll_until, WaitCancel};
+use crate::CommandError;
+
+// Maximum memo size accepted by z_sendmany (in bytes, before hex encoding)
+pub const MAX_MEMO_BYTES: usize = 512;
+
+// Upper bound for a chunked message (bounds fees and note usage)
+pub const MAX_CHUNKED_MESSAGE_BYTES: usize = 10 * 1024;
+
+// First line of each memo of a chunked message: "chunk:<8 hex id>:<seq>/<total>\n", seq 1-based.
+// Sent with fixed-width (2-digit) counters so every chunk has the same payload budget.
+const CHUNK_HEADER_PREFIX: &str = "chunk:";
+const CHUNK_HEADER_LEN: usize = 21;
+
+// Give the wallet time to build each chunk's transaction before queueing the next one
+const CHUNK_SEND_INTERVAL_SECS: u64 = 2;
+const CHUNK_SEND_TIMEOUT_SECS: u64 = 120;
+
+// Most outputs in one multi-recipient z_sendmany; each shielded output adds a proof, so this bounds
+// the transaction's size and build time
+pub const MAX_MULTI_RECIPIENTS: usize = 20;
+
+// First memo line naming the sender: "from:<id@>\n" (the prefix memo_sender_identity also recognises)
+const SENDER_HEADER_PREFIX: &str = "from:";
+
+// Txid payload of a successful operation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OperationTxResult {
+    pub txid: String,
+}
+
+// Error payload of a failed operation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OperationError {
+    pub code: i32,
+    pub message: String,
+}
+
+// Single entry of z_getoperationstatus
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OperationStatus {
+    pub id: String,
+    pub status: String, // "queued" | "executing" | "success" | "failed" | "cancelled"
+    pub creation_time: Option<u64>,
+    pub result: Option<OperationTxResult>,
+    pub error: Option<OperationError>,
+    pub execution_secs: Option<f64>,
+}
+
+// One entry of the wallet's operation queue, as returned by list_operations
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OperationSummary {
+    pub opid: String,
+    pub status: String,
+    pub creation_time: Option<u64>,
+}
+
+// Final outcome of a completed operation (drained via z_getoperationresult)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OperationResult {
+    pub id: String,
+    pub status: String,
+    pub txid: Option<String>, // Set when status is "success"
+    #[serde(default)]
+    pub fee: Option<f64>, // Fee the transaction paid, when it could be determined
+    pub error: Option<OperationError>,
+    pub execution_secs: Option<f64>,
+}
+
+impl From<OperationStatus> for OperationResult {
+    fn from(status: OperationStatus) -> Self {
+        OperationResult {
+            id: status.id,
+            status: status.status,
+            txid: status.result.map(|r| r.txid),
+            fee: None,
+            error: status.error,
+            execution_secs: status.execution_secs,
+        }
+    }
+}
+
+// Raw entry of z_listreceivedbyaddress (only the fields we use)
+#[derive(Deserialize, Debug)]
+struct ReceivedNote {
+    txid: String,
+    amount: f64,
+    confirmations: i64,
+    #[serde(default)]
+    memo: Option<String>, // Hex, padded to 512 bytes by the daemon
+    #[serde(default)]
+    change: bool,
+}
+
+// Incoming memo as returned to the frontend
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReceivedMessage {
+    pub txid: String,
+    pub amount: f64,
+    pub memo_hex: String,
+    pub memo_text: String, // UTF-8 text (sender header stripped), or the raw hex when the memo isn't valid UTF-8
+    pub confirmations: i64,
+    #[serde(default)]
+    pub sender: Option<String>, // VerusID claimed by the sender header - unverified
+    #[serde(default)]
+    pub partial: bool, // Chunked message still missing parts
+}
+
+// Strip memo padding: trailing zero bytes, and the 0xf6 "no memo" marker Zcash uses
+fn trim_memo_padding(bytes: &[u8]) -> &[u8] {
+    let end = bytes
+        .iter()
+        .rposition(|&b| b != 0x00 && b != 0xf6)
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    &bytes[..end]
+}
+
+// Decode a hex memo into (trimmed hex, text). Returns None for empty/padding-only memos.
+pub fn decode_memo(memo_hex: &str) -> Option<(String, String)> {
+    let bytes = match hex::decode(memo_hex) {
+        Ok(b) => b,
+        Err(e) => {
+            log::warn!("Memo is not valid hex ({}), keeping it as-is", e);
+            return Some((memo_hex.to_string(), memo_hex.to_string()));
+        }
+    };
+    let trimmed = trim_memo_padding(&bytes);
+    if trimmed.is_empty() {
+        return None;
+    }
+    let trimmed_hex = hex::encode(trimmed);
+    let text = match std::str::from_utf8(trimmed) {
+        Ok(s) => s.to_string(),
+        Err(_) => trimmed_hex.clone(),
+    };
+    Some((trimmed_hex, text))
+}
+
+// Prepend the sender header to a memo
+fn with_sender_header(memo: &str, sender_identity: Option<&str>) -> Result<String, CommandError> {
+    match sender_identity {
+        None => Ok(memo.to_string()),
+        Some(sender) => {
+            let sender = sender.trim();
+            if !sender.ends_with('@') || sender.len() <= 1 || sender.contains(char::is_whitespace) {
+                return Err(CommandError::invalid_argument(format!("Invalid sender identity: {}", sender)));
+            }
+            Ok(format!("{}{}\n{}", SENDER_HEADER_PREFIX, sender, memo))
+        }
+    }
+}
+
+// Split a decoded memo into (sender, body). Memos without a well-formed header come back unchanged.
+fn split_sender_header(memo_text: &str) -> (Option<String>, String) {
+    if let Some(rest) = memo_text.strip_prefix(SENDER_HEADER_PREFIX) {
+        if let Some((sender, body)) = rest.split_once('\n') {
+            if sender.ends_with('@') && sender.len() > 1 && !sender.contains(char::is_whitespace) {
+                return (Some(sender.to_string()), body.to_string());
+            }
+        }
+    }
+    (None, memo_text.to_string())
+}
+
+// One memo of a chunked message
+struct MemoChunk {
+    id: String,
+    seq: usize,
+    total: usize,
+    payload: String,
+}
+
+fn parse_chunk(memo_text: &str) -> Option<MemoChunk> {
+    let rest = memo_text.strip_prefix(CHUNK_HEADER_PREFIX)?;
+    let (header, payload) = rest.split_once('\n')?;
+    let (id, counters) = header.split_once(':')?;
+    let (seq, total) = counters.split_once('/')?;
+    let seq: usize = seq.parse().ok()?;
+    let total: usize = total.parse().ok()?;
+    if id.is_empty() || seq == 0 || seq > total {
+        return None;
+    }
+    Some(MemoChunk { id: id.to_string(), seq, total, payload: payload.to_string() })
+}
+
+// Random-enough id to tell concurrent chunked messages apart (no rand crate: RandomState is seeded per process)
+fn new_chunk_id() -> String {
+    use std::hash::{BuildHasher, Hasher};
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0),
+    );
+    format!("{:08x}", hasher.finish() as u32)
+}
+
+// Split text into pieces of at most max_bytes without cutting a UTF-8 character
+fn split_utf8(text: &str, max_bytes: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut end = rest.len().min(max_bytes);
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        pieces.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    pieces
+}
+
+// Memos for a message: the message itself if it fits in one memo, otherwise its chunks
+pub fn chunk_memo(memo: &str) -> Result<Vec<String>, MemoError> {
+    if memo.len() <= MAX_MEMO_BYTES {
+        return Ok(vec![memo.to_string()]);
+    }
+    if memo.len() > MAX_CHUNKED_MESSAGE_BYTES {
+        return Err(MemoError::TooLong { bytes: memo.len(), max: MAX_CHUNKED_MESSAGE_BYTES });
+    }
+    let id = new_chunk_id();
+    let pieces = split_utf8(memo, MAX_MEMO_BYTES - CHUNK_HEADER_LEN);
+    let total = pieces.len();
+    Ok(pieces
+        .into_iter()
+        .enumerate()
+        .map(|(i, piece)| format!("{}{}:{:02}/{:02}\n{}", CHUNK_HEADER_PREFIX, id, i + 1, total, piece))
+        .collect())
+}
+
+// Merge chunks sharing an id into one message, in place of the first chunk seen. The merged message
+// takes the lowest seq's txid, the summed amount and the lowest confirmation count; it stays
+// partial until every seq is present.
+fn reassemble_chunks(messages: Vec<ReceivedMessage>) -> Vec<ReceivedMessage> {
+    enum Slot {
+        Whole(ReceivedMessage),
+        Chunked(String),
+    }
+    // Declared total, and the received chunks (message + payload) by seq
+    type ChunkGroup = (usize, std::collections::BTreeMap<usize, (ReceivedMessage, String)>);
+    let mut slots = Vec::new();
+    let mut groups: HashMap<String, ChunkGroup> = HashMap::new();
+
+    for message in messages {
+        match parse_chunk(&message.memo_text) {
+            Some(chunk) => {
+                let group = groups.entry(chunk.id.clone()).or_insert_with(|| {
+                    slots.push(Slot::Chunked(chunk.id.clone()));
+                    (chunk.total, Default::default())
+                });
+                group.1.insert(chunk.seq, (message, chunk.payload));
+            }
+            None => slots.push(Slot::Whole(message)),
+        }
+    }
+
+    slots
+        .into_iter()
+        .filter_map(|slot| match slot {
+            Slot::Whole(message) => Some(message),
+            Slot::Chunked(id) => {
+                let (total, chunks) = groups.remove(&id)?;
+                let received = chunks.len();
+                let partial = received < total;
+                let mut chunks = chunks.into_values();
+                let (mut merged, mut text) = chunks.next()?;
+                for (chunk, payload) in chunks {
+                    merged.amount += chunk.amount;
+                    merged.confirmations = merged.confirmations.min(chunk.confirmations);
+                    text.push_str(&payload);
+                }
+                if partial {
+                    log::debug!("Chunked message {} incomplete ({} of {} parts)", id, received, total);
+                }
+                merged.memo_hex = hex::encode(text.as_bytes());
+                merged.memo_text = text;
+                merged.partial = partial;
+                Some(merged)
+            }
+        })
+        .collect()
+}
+
+// A sent or received memo, as fed into build_conversations
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Message {
+    pub txid: String,
+    pub counterparty: String, // z-address (or VerusID) of the other side
+    pub direction: String, // "received" | "sent"
+    pub memo_text: String,
+    pub amount: f64,
+    pub block_height: Option<u64>, // None while unconfirmed
+    pub timestamp: u64, // Block time (or local send time for unconfirmed sends)
+    #[serde(default)]
+    pub read: bool,
+    #[serde(default)]
+    pub sender: Option<String>, // From the memo's sender header (ReceivedMessage.sender), if any
+}
+
+// Chat thread with a single counterparty
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Conversation {
+    pub counterparty: String, // VerusID when known, otherwise the z-address
+    pub identity: Option<String>, // Verified VerusID of the counterparty
+    pub messages: Vec<Message>, // Oldest first
+    pub last_message_at: u64,
+    pub unread_count: usize,
+}
+
+// Extract a sender VerusID claimed inside a memo: "from:<id@> ..." or the "...//f//<id@>//t//..." format
+fn memo_sender_identity(memo_text: &str) -> Option<String> {
+    let trimmed = memo_text.trim_start();
+    let candidate = if let Some(rest) = trimmed.strip_prefix("from:") {
+        rest.split_whitespace().next()?
+    } else {
+        let after = &memo_text[memo_text.find("//f//")? + 5..]; // 5 = "//f//".len()
+        after.split("//").next()?.trim()
+    };
+    if candidate.ends_with('@') && candidate.len() > 1 {
+        Some(candidate.to_string())
+    } else {
+        None
+    }
+}
+
+/// Group messages into conversations keyed by counterparty, newest conversation first
+pub async fn build_conversations(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    messages: Vec<Message>,
+    read_txids: &HashSet<String>,
+) -> Vec<Conversation> {
+    // Resolve each claimed identity once; None = not eligible / unknown
+    let mut identity_cache: HashMap<String, Option<String>> = HashMap::new();
+    let mut grouped: HashMap<String, (Option<String>, Vec<Message>)> = HashMap::new();
+
+    for mut message in messages {
+        message.read = message.read || read_txids.contains(&message.txid);
+        let mut identity = None;
+        if message.direction == "received" {
+            let claimed = message.sender.clone().or_else(|| memo_sender_identity(&message.memo_text));
+            if let Some(claimed) = claimed {
+                if !identity_cache.contains_key(&claimed) {
+                    let resolved = match check_identity_eligibility(
+                        rpc_user.to_string(),
+                        rpc_pass.to_string(),
+                        rpc_host.to_string(),
+                        rpc_port,
+                        claimed.clone(),
+                    )
+                    .await
+                    {
+                        Ok(formatted) => Some(formatted.formatted_name),
+                        Err(e) => {
+                            log::warn!("Could not attribute memo to {}: {:?}", claimed, e);
+                            None
+                        }
+                    };
+                    identity_cache.insert(claimed.clone(), resolved);
+                }
+                identity = identity_cache.get(&claimed).cloned().flatten();
+            }
+        }
+
+        let key = identity.clone().unwrap_or_else(|| message.counterparty.clone());
+        let entry = grouped.entry(key).or_insert_with(|| (None, Vec::new()));
+        if entry.0.is_none() {
+            entry.0 = identity;
+        }
+        entry.1.push(message);
+    }
+
+    let mut conversations: Vec<Conversation> = grouped
+        .into_iter()
+        .map(|(counterparty, (identity, mut messages))| {
+            // Confirmed messages by height, unconfirmed last; txid breaks ties
+            messages.sort_by(|a, b| {
+                let ha = a.block_height.unwrap_or(u64::MAX);
+                let hb = b.block_height.unwrap_or(u64::MAX);
+                ha.cmp(&hb).then_with(|| a.txid.cmp(&b.txid))
+            });
+            let last_message_at = messages.iter().map(|m| m.timestamp).max().unwrap_or(0);
+            let unread_count = messages
+                .iter()
+                .filter(|m| m.direction == "received" && !m.read)
+                .count();
+            Conversation { counterparty, identity, messages, last_message_at, unread_count }
+        })
+        .collect();
+
+    conversations.sort_by_key(|c| std::cmp::Reverse(c.last_message_at));
+    log::info!("build_conversations: {} conversations", conversations.len());
+    conversations
+}
+
+#[derive(Debug, thiserror::Error, Serialize)]
+#[serde(tag = "kind")]
+pub enum MemoError {
+    #[error("Memo is too long: {bytes} bytes (maximum is {max} bytes)")]
+    TooLong { bytes: usize, max: usize },
+    // Memos are zero-padded on chain and the padding is stripped when read, so a NUL would truncate it
+    #[error("Memo contains a NUL character at byte {at}")]
+    ContainsNul { at: usize },
+}
+
+// The exact bytes z_sendmany will carry, or why the memo can't be sent as-is. Input is a &str, so
+// it's valid UTF-8; with no NULs the receiver's decode gives back exactly this text.
+pub fn validate_memo(text: &str) -> Result<Vec<u8>, MemoError> {
+    let bytes = text.as_bytes().to_vec();
+    if bytes.len() > MAX_MEMO_BYTES {
+        return Err(MemoError::TooLong { bytes: bytes.len(), max: MAX_MEMO_BYTES });
+    }
+    if let Some(at) = bytes.iter().position(|&b| b == 0) {
+        return Err(MemoError::ContainsNul { at });
+    }
+    Ok(bytes)
+}
+
+// Validate memo size and hex-encode it for z_sendmany
+pub fn encode_memo(memo: &str) -> Result<String, MemoError> {
+    validate_memo(memo).map(hex::encode)
+}
+
+/// Byte length of a memo as it would be sent, so the UI counter matches the backend limit exactly.
+/// Pass the text including any sender header the send will add.
+#[tauri::command]
+pub async fn check_memo(text: String) -> Result<usize, CommandError> {
+    Ok(validate_memo(&text)?.len())
+}
+
+// RPC_INVALID_ADDRESS_OR_KEY: z_viewtransaction doesn't know the txid (not a wallet transaction)
+const RPC_INVALID_ADDRESS_OR_KEY: i32 = -5;
+
+// One shielded output as reported by z_viewtransaction
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShieldedOutput {
+    #[serde(default)]
+    pub address: Option<String>, // Omitted for outputs the wallet can't attribute
+    pub value: f64,
+    #[serde(default)]
+    pub memo: Option<String>, // Raw hex as returned by the daemon
+    #[serde(default, alias = "memoStr")]
+    pub memo_str: Option<String>, // Decoded memo text (padding stripped)
+    #[serde(default)]
+    pub outgoing: bool, // Sent by this wallet to someone else
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShieldedTx {
+    pub txid: String,
+    #[serde(default)]
+    pub outputs: Vec<ShieldedOutput>,
+}
+
+// z_viewtransaction: what a wallet shielded transaction actually delivered (e.g. to check a sent memo)
+pub async fn fetch_shielded_transaction(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    txid: &str,
+) -> Result<ShieldedTx, VerusRpcError> {
+    let mut tx: ShieldedTx = match make_rpc_call(rpc_user, rpc_pass, rpc_host, rpc_port, "z_viewtransaction", vec![json!(txid)]).await {
+        Ok(tx) => tx,
+        Err(VerusRpcError::Rpc { code: RPC_INVALID_ADDRESS_OR_KEY, message }) => {
+            log::warn!("z_viewtransaction({}): not a wallet shielded transaction ({})", txid, message);
+            return Err(VerusRpcError::NotFoundOrIneligible);
+        }
+        Err(e) => return Err(e),
+    };
+
+    // The daemon only sets memoStr for UTF-8 memos; decode the hex the same way received memos are
+    // (padding trimmed, hex fallback, None when empty)
+    for output in &mut tx.outputs {
+        if let Some(memo) = output.memo.as_deref() {
+            output.memo_str = decode_memo(memo).map(|(_, text)| text);
+        }
+    }
+
+    log::info!("z_viewtransaction({}): {} output(s)", txid, tx.outputs.len());
+    Ok(tx)
+}
+
+// Fetch the status of a single z_sendmany operation
+pub async fn fetch_operation_status(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    opid: &str,
+) -> Result<OperationStatus, VerusRpcError> {
+    let statuses: Vec<OperationStatus> = make_rpc_call(
+        rpc_user,
+        rpc_pass,
+        rpc_host,
+        rpc_port,
+        "z_getoperationstatus",
+        vec![json!([opid])],
+    )
+    .await?;
+
+    statuses
+        .into_iter()
+        .find(|s| s.id == opid)
+        .ok_or(VerusRpcError::NotFoundOrIneligible)
+}
+
+// Drain the result of a finished operation via z_getoperationresult.
+// The daemon forgets an operation once its result has been read, so an empty
+// array means the opid is unknown or already consumed -> NotFoundOrIneligible.
+pub async fn fetch_operation_result(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    opid: &str,
+) -> Result<OperationResult, VerusRpcError> {
+    let results: Vec<OperationStatus> = make_rpc_call(
+        rpc_user,
+        rpc_pass,
+        rpc_host,
+        rpc_port,
+        "z_getoperationresult",
+        vec![json!([opid])],
+    )
+    .await?;
+
+    match results.into_iter().find(|r| r.id == opid) {
+        Some(status) => {
+            log::info!("Operation {} finished with status {}", opid, status.status);
+            let mut result: OperationResult = status.into();
+            // The result is gone once drained, so attach the fee now; a lookup failure isn't fatal
+            if let Some(txid) = &result.txid {
+                match fetch_transaction_fee(rpc_user, rpc_pass, rpc_host, rpc_port, txid).await {
+                    Ok(fee) => result.fee = Some(fee),
+                    Err(e) => log::warn!("Could not determine fee for {}: {}", txid, e),
+                }
+            }
+            Ok(result)
+        }
+        None => {
+            log::warn!("z_getoperationresult returned nothing for {} (unknown, still running or already consumed)", opid);
+            Err(VerusRpcError::NotFoundOrIneligible)
+        }
+    }
+}
+
+// z_sendmany source meaning "any transparent, non-identity address in the wallet"
+const WILDCARD_SOURCE: &str = "*";
+
+/// Send a shielded memo (and optional amount) to a single recipient; returns the z_sendmany opid.
+/// from_address None sends from "*", letting the daemon pick transparent inputs across the wallet when
+/// the identity's own address is short on UTXOs. That spends from (and so publicly links) arbitrary
+/// transparent wallet addresses, so it's refused unless allow_wildcard_source is set.
+#[tauri::command]
+pub async fn send_private_message_async(
+    app: tauri::AppHandle,
+    from_address: Option<String>,
+    to_address: String,
+    memo: String,
+    amount: f64,
+    sender_identity: Option<String>,
+    allow_wildcard_source: Option<bool>,
+) -> Result<String, CommandError> {
+    let from_address = from_address.unwrap_or_else(|| WILDCARD_SOURCE.to_string());
+    let wildcard = from_address == WILDCARD_SOURCE;
+    log::info!("send_private_message_async: from={}, to={}, amount={}", from_address, to_address, amount);
+    if wildcard && !allow_wildcard_source.unwrap_or(false) {
+        return Err(CommandError::new(
+            "WILDCARD_SOURCE_NOT_ALLOWED",
+            "Sending from any wallet address (\"*\") links those addresses publicly; pass allow_wildcard_source to confirm",
+        ));
+    }
+
+    // Reject oversized memos (header included) before touching the daemon
+    let memo_hex = encode_memo(&with_sender_header(&memo, sender_identity.as_deref())?)?;
+
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    if wildcard {
+        ensure_valid_address(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &to_address)
+            .await
+            .map_err(|e| CommandError::with_context(format!("Invalid address {}", to_address), e))?;
+    } else {
+        ensure_valid_addresses(&creds, &from_address, &to_address).await?;
+    }
+    ensure_usable_source(&creds, &from_address, amount, DEFAULT_TX_FEE).await?;
+    let opid = queue_memo(&creds, &from_address, &to_address, amount, &memo_hex).await?;
+
+    log::info!("send_private_message_async queued operation: {}", opid);
+    Ok(opid)
+}
+
+/// Send memos to several recipients in one transaction (e.g. a group announcement); returns the single
+/// opid covering every output. recipients are (z-address, memo) pairs, each receiving amount_each. Every
+/// memo must fit one output (MAX_MEMO_BYTES) and an address may appear only once.
+#[tauri::command]
+pub async fn send_private_message_multi(
+    app: tauri::AppHandle,
+    from_address: String,
+    recipients: Vec<(String, String)>,
+    amount_each: f64,
+) -> Result<String, CommandError> {
+    log::info!("send_private_message_multi: from={}, {} recipient(s), amount_each={}", from_address, recipients.len(), amount_each);
+    if recipients.is_empty() {
+        return Err(CommandError::invalid_argument("No recipients given"));
+    }
+    if recipients.len() > MAX_MULTI_RECIPIENTS {
+        return Err(CommandError::invalid_argument(format!("Too many recipients: {} (maximum is {})", recipients.len(), MAX_MULTI_RECIPIENTS)));
+    }
+    let mut seen = HashSet::new();
+    if let Some((duplicate, _)) = recipients.iter().find(|(address, _)| !seen.insert(address.as_str())) {
+        return Err(CommandError::invalid_argument(format!("Recipient {} is listed more than once", duplicate)));
+    }
+
+    // Reject oversized memos before touching the daemon
+    let mut outputs = Vec::with_capacity(recipients.len());
+    for (address, memo) in &recipients {
+        let memo_hex = encode_memo(memo).map_err(|e| {
+            let error = CommandError::from(e);
+            CommandError::new(error.code, format!("Memo for {}: {}", address, error.message))
+        })?;
+        outputs.push(json!({
+            "address": address,
+            "amount": amount_each,
+            "memo": memo_hex
+        }));
+    }
+
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    for address in std::iter::once(&from_address).chain(recipients.iter().map(|(address, _)| address)) {
+        ensure_valid_address(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, address)
+            .await
+            .map_err(|e| CommandError::with_context(format!("Invalid address {}", address), e))?;
+    }
+    ensure_usable_source(&creds, &from_address, amount_each * recipients.len() as f64, DEFAULT_TX_FEE).await?;
+
+    let opid = make_rpc_call::<String>(
+        &creds.rpc_user,
+        &creds.rpc_pass,
+        &creds.rpc_host,
+        creds.rpc_port,
+        "z_sendmany",
+        vec![json!(from_address), Value::Array(outputs), json!(1)],
+    )
+    .await
+    .map_err(|e| CommandError::rpc_failure("z_sendmany", e))?;
+
+    log::info!("send_private_message_multi queued operation: {}", opid);
+    Ok(opid)
+}
+
+/// Send a message of any length up to MAX_CHUNKED_MESSAGE_BYTES; returns one opid per memo.
+/// A message that fits one memo goes out as a single z_sendmany; unlike send_private_message_async
+/// there is no "*" source. Longer ones go out as one z_sendmany per chunk: the daemon rejects repeated
+/// recipient addresses in a single z_sendmany, so the chunks can't share a transaction. Each chunk is
+/// queued once the previous operation has finished, so the wallet doesn't select the same notes twice.
+/// amount rides on the first chunk, and the source must cover it plus one fee per chunk up front.
+#[tauri::command]
+pub async fn send_long_message_async(
+    app: tauri::AppHandle,
+    from_address: String,
+    to_address: String,
+    memo: String,
+    amount: f64,
+    sender_identity: Option<String>,
+) -> Result<Vec<String>, CommandError> {
+    let memos = chunk_memo(&with_sender_header(&memo, sender_identity.as_deref())?)?;
+    log::info!("send_long_message_async: from={}, to={}, amount={}, {} memo(s)", from_address, to_address, amount, memos.len());
+    let memos_hex = memos.iter().map(|m| encode_memo(m)).collect::<Result<Vec<_>, _>>()?;
+
+    let creds = crate::credentials::load_credentials(app).await?;
+    ensure_valid_addresses(&creds, &from_address, &to_address).await?;
+    ensure_usable_source(&creds, &from_address, amount, DEFAULT_TX_FEE * memos_hex.len() as f64).await?;
+
+    let mut opids: Vec<String> = Vec::with_capacity(memos_hex.len());
+    for (i, memo_hex) in memos_hex.iter().enumerate() {
+        if let Some(previous) = opids.last() {
+            let finished = wait_for_operation_completion(
+                &creds.rpc_user,
+                &creds.rpc_pass,
+                &creds.rpc_host,
+                creds.rpc_port,
+                previous,
+                CHUNK_SEND_INTERVAL_SECS,
+                CHUNK_SEND_TIMEOUT_SECS,
+                None,
+            )
+            .await
+            .map_err(|e| CommandError::with_context(format!("Chunk {} of {} failed", i, memos_hex.len()), e))?;
+            if !finished {
+                return Err(CommandError::new(
+                    VerusRpcError::Timeout.code(),
+                    format!("Chunk {} of {} did not complete in time", i, memos_hex.len()),
+                ));
+            }
+        }
+        let chunk_amount = if i == 0 { amount } else { 0.0 };
+        opids.push(queue_memo(&creds, &from_address, &to_address, chunk_amount, memo_hex).await?);
+    }
+
+    log::info!("send_long_message_async queued operations: {:?}", opids);
+    Ok(opids)
+}
+
+async fn ensure_valid_addresses(
+    creds: &crate::credentials::Credentials,
+    from_address: &str,
+    to_address: &str,
+) -> Result<(), CommandError> {
+    for address in [from_address, to_address] {
+        ensure_valid_address(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, address)
+            .await
+            .map_err(|e| CommandError::with_context(format!("Invalid address {}", address), e))?;
+    }
+    Ok(())
+}
+
+// The source needs at least one usable UTXO, and enough in them for amount + fee (the total fee of
+// every transaction the send takes)
+async fn ensure_usable_source(
+    creds: &crate::credentials::Credentials,
+    from_address: &str,
+    amount: f64,
+    fee: f64,
+) -> Result<(), CommandError> {
+    let utxo_info = if from_address == WILDCARD_SOURCE {
+        get_wildcard_utxo_info(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, DEFAULT_MIN_USABLE_UTXO).await
+    } else {
+        get_utxo_info(
+            creds.rpc_user.clone(),
+            creds.rpc_pass.clone(),
+            creds.rpc_host.clone(),
+            creds.rpc_port,
+            from_address.to_string(),
+            DEFAULT_MIN_USABLE_UTXO,
+        )
+        .await
+    }
+    .map_err(|e| CommandError::with_context(format!("Failed to check UTXOs for {}", from_address), e))?;
+
+    if utxo_info.usable_utxos == 0 {
+        return Err(CommandError::new(
+            "NO_USABLE_UTXOS",
+            format!("No usable UTXOs on {} (wait for change to confirm or consolidate)", from_address),
+        ));
+    }
+    ensure_sufficient_funds(utxo_info.total_spendable_value, amount, fee, true).map_err(CommandError::from)
+}
+
+// Queue a single-output z_sendmany carrying an already hex-encoded memo; returns the opid
+async fn queue_memo(
+    creds: &crate::credentials::Credentials,
+    from_address: &str,
+    to_address: &str,
+    amount: f64,
+    memo_hex: &str,
+) -> Result<String, CommandError> {
+    let amounts_param: Value = json!([
+        {
+            "address": to_address,
+            "amount": amount,
+            "memo": memo_hex
+        }
+    ]);
+
+    make_rpc_call::<String>(
+        &creds.rpc_user,
+        &creds.rpc_pass,
+        &creds.rpc_host,
+        creds.rpc_port,
+        "z_sendmany",
+        vec![json!(from_address), amounts_param, json!(1)],
+    )
+    .await
+    .map_err(|e| CommandError::rpc_failure("z_sendmany", e))
+}
+
+// How many recent wallet transactions are checked for outgoing sends
+const OUTGOING_SEND_WINDOW: u32 = 1000;
+
+// Txids of recent transactions the wallet sent (listtransactions "send" entries)
+async fn fetch_outgoing_txids(creds: &crate::credentials::Credentials) -> Result<HashSet<String>, VerusRpcError> {
+    let sends = list_wallet_transactions(
+        creds.rpc_user.clone(),
+        creds.rpc_pass.clone(),
+        creds.rpc_host.clone(),
+        creds.rpc_port,
+        OUTGOING_SEND_WINDOW,
+        0,
+        Some("send".to_string()),
+    )
+    .await?;
+    Ok(sends.into_iter().map(|t| t.txid).collect())
+}
+
+// z_listreceivedbyaddress decoded into messages (chunks reassembled, sender header split off).
+// Empty and padding-only memos are always skipped. Unless include_change is set, a note is also treated
+// as change / a self-send and skipped when:
+// - the daemon flags it as change (the same transaction spends a note of this address), or
+// - its txid appears as a "send" among the last OUTGOING_SEND_WINDOW listtransactions entries
+//   (transactions funded from this wallet's transparent addresses, e.g. consolidations).
+// A genuine incoming memo in a transaction this wallet also funded is dropped too - report such cases.
+pub async fn fetch_received_messages(
+    creds: &crate::credentials::Credentials,
+    z_address: &str,
+    min_conf: u32,
+    include_change: bool,
+) -> Result<Vec<ReceivedMessage>, VerusRpcError> {
+    let notes: Vec<ReceivedNote> = make_rpc_call(
+        &creds.rpc_user,
+        &creds.rpc_pass,
+        &creds.rpc_host,
+        creds.rpc_port,
+        "z_listreceivedbyaddress",
+        vec![json!(z_address), json!(min_conf)],
+    )
+    .await?;
+
+    let outgoing = if include_change {
+        HashSet::new()
+    } else {
+        // Without the send list only the daemon's change flag applies
+        fetch_outgoing_txids(creds).await.unwrap_or_else(|e| {
+            log::warn!("Could not list outgoing transactions, skipping self-send filter: {}", e);
+            HashSet::new()
+        })
+    };
+
+    let total = notes.len();
+    let messages: Vec<ReceivedMessage> = notes
+        .into_iter()
+        .filter(|n| include_change || (!n.change && !outgoing.contains(&n.txid)))
+        .filter_map(|n| {
+            let (memo_hex, memo_text) = decode_memo(n.memo.as_deref()?)?;
+            Some(ReceivedMessage {
+                txid: n.txid,
+                amount: n.amount,
+                memo_hex,
+                memo_text,
+                confirmations: n.confirmations,
+                sender: None,
+                partial: false,
+            })
+        })
+        .collect();
+
+    // The sender header sits at the start of a chunked message, so split it off after reassembly
+    let messages: Vec<ReceivedMessage> = reassemble_chunks(messages)
+        .into_iter()
+        .map(|mut message| {
+            let (sender, memo_text) = split_sender_header(&message.memo_text);
+            message.sender = sender;
+            message.memo_text = memo_text;
+            message
+        })
+        .collect();
+
+    log::debug!("{}: {} of {} notes carry a memo", z_address, messages.len(), total);
+    Ok(messages)
+}
+
+/// List memos received on a z-address (skips empty memos, and change/self-sends unless include_change)
+#[tauri::command]
+pub async fn get_received_messages(
+    app: tauri::AppHandle,
+    z_address: String,
+    min_conf: u32,
+    include_change: Option<bool>,
+) -> Result<Vec<ReceivedMessage>, CommandError> {
+    log::info!("get_received_messages: address={}, min_conf={}", z_address, min_conf);
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    let messages = fetch_received_messages(&creds, &z_address, min_conf, include_change.unwrap_or(false))
+        .await
+        .map_err(CommandError::from)?;
+
+    log::info!("get_received_messages: {} messages", messages.len());
+    Ok(messages)
+}
+
+// --- Message watcher ---
+
+// Emitted once per newly seen ReceivedMessage (and again when a partial chunked message completes)
+pub const NEW_MESSAGE_EVENT: &str = "new-message";
+
+const MIN_WATCH_INTERVAL_SECS: u64 = 5;
+
+// The running watcher task; starting a new watcher aborts the old one
+static MESSAGE_WATCHER: Mutex<Option<tauri::async_runtime::JoinHandle<()>>> = Mutex::new(None);
+
+fn seen_key(message: &ReceivedMessage) -> String {
+    format!("{}:{}", message.txid, message.partial)
+}
+
+// Poll every address forever. The first successful poll of an address only records what's already
+// there; errors are logged and retried on the next tick.
+async fn watch_messages(app: tauri::AppHandle, addresses: Vec<String>, interval: Duration) {
+    let mut seen: HashMap<String, HashSet<String>> = HashMap::new();
+    loop {
+        match crate::credentials::load_credentials(app.clone()).await {
+            Ok(creds) => {
+                for address in &addresses {
+                    let messages = match fetch_received_messages(&creds, address, 0, false).await {
+                        Ok(messages) => messages,
+                        Err(e) => {
+                            log::warn!("Message watcher: polling {} failed: {}", address, e);
+                            continue;
+                        }
+                    };
+                    let baseline = !seen.contains_key(address);
+                    let seen_here = seen.entry(address.clone()).or_default();
+                    for message in messages {
+                        if seen_here.insert(seen_key(&message)) && !baseline {
+                            log::info!("Message watcher: new message {} on {}", message.txid, address);
+                            if let Err(e) = app.emit(NEW_MESSAGE_EVENT, &message) {
+                                log::warn!("Failed to emit {} event: {}", NEW_MESSAGE_EVENT, e);
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => log::warn!("Message watcher: failed to load credentials: {}", e),
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Start polling addresses for incoming memos, emitting new-message events. Replaces a running watcher.
+#[tauri::command]
+pub async fn start_message_watcher(
+    app: tauri::AppHandle,
+    addresses: Vec<String>,
+    interval_secs: u64,
+) -> Result<(), CommandError> {
+    if addresses.is_empty() {
+        return Err(CommandError::invalid_argument("No addresses to watch"));
+    }
+    let interval = Duration::from_secs(interval_secs.max(MIN_WATCH_INTERVAL_SECS));
+    log::info!("Starting message watcher for {} address(es) every {:?}", addresses.len(), interval);
+
+    let mut watcher = MESSAGE_WATCHER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(previous) = watcher.take() {
+        previous.abort();
+    }
+    *watcher = Some(tauri::async_runtime::spawn(watch_messages(app, addresses, interval)));
+    Ok(())
+}
+
+/// Stop the message watcher; returns false if none was running
+#[tauri::command]
+pub async fn stop_message_watcher() -> Result<bool, CommandError> {
+    Ok(abort_message_watcher())
+}
+
+// Abort the watcher task if one is running (also used on shutdown)
+pub fn abort_message_watcher() -> bool {
+    let mut watcher = MESSAGE_WATCHER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    match watcher.take() {
+        Some(handle) => {
+            log::info!("Stopping message watcher");
+            handle.abort();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Build chat threads from a list of sent and received messages
+#[tauri::command]
+pub async fn get_conversations(app: tauri::AppHandle, messages: Vec<Message>) -> Result<Vec<Conversation>, CommandError> {
+    let creds = crate::credentials::load_credentials(app.clone()).await?;
+    let read_txids = crate::message_state::load_read_txids(&app).unwrap_or_else(|e| {
+        log::warn!("Could not load read state, treating all messages as unread: {}", e);
+        HashSet::new()
+    });
+    Ok(build_conversations(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, messages, &read_txids).await)
+}
+
+// Typed error for an operation that ended without success
+fn operation_failure(status: &OperationStatus) -> VerusRpcError {
+    match &status.error {
+        Some(error) => VerusRpcError::OperationFailed { code: error.code, message: error.message.clone() },
+        // "cancelled" (or a failure without details) carries no error object
+        None => VerusRpcError::OperationFailed { code: 0, message: status.status.clone() },
+    }
+}
+
+// One poll of an operation: Ok(true) once it succeeded, Ok(false) while queued/executing
+// (or any status we don't know), Err(OperationFailed) once it failed or was cancelled
+fn operation_outcome(status: &OperationStatus) -> Result<bool, VerusRpcError> {
+    match status.status.as_str() {
+        "success" => Ok(true),
+        "failed" | "cancelled" => Err(operation_failure(status)),
+        _ => Ok(false),
+    }
+}
+
+/// Wait until an operation succeeds (Ok(true), txid via fetch_operation_result), fails
+/// (OperationFailed), or the timeout elapses / the wait is cancelled (Ok(false))
+#[allow(clippy::too_many_arguments)]
+pub async fn wait_for_operation_completion(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    opid: &str,
+    interval_secs: u64,
+    timeout_secs: u64,
+    cancel_key: Option<String>,
+) -> Result<bool, VerusRpcError> {
+    let cancel = WaitCancel::register(cancel_key);
+    poll_until("wait_for_operation", interval_secs, timeout_secs, &cancel, |_| async move {
+        let status = fetch_operation_status(rpc_user, rpc_pass, rpc_host, rpc_port, opid).await.map_err(|e| {
+            log::error!("wait_for_operation status error for {}: {}", opid, e);
+            e
+        })?;
+
+        let outcome = operation_outcome(&status);
+        match &outcome {
+            Ok(true) => log::info!(
+                "wait_for_operation: {} succeeded, txid={}",
+                opid,
+                status.result.as_ref().map(|r| r.txid.as_str()).unwrap_or("?")
+            ),
+            Ok(false) => log::debug!("wait_for_operation: {} is {}", opid, status.status),
+            Err(error) => log::error!("wait_for_operation: {} {}: {}", opid, status.status, error),
+        }
+        outcome
+    })
+    .await
+}
+
+/// Get the status of a z_sendmany operation via z_getoperationstatus
+#[tauri::command]
+pub async fn get_operation_status(app: tauri::AppHandle, opid: String) -> Result<OperationStatus, CommandError> {
+    log::debug!("get_operation_status({})", opid);
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    fetch_operation_status(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &opid)
+        .await
+        .map_err(|e| CommandError::with_context(format!("z_getoperationstatus failed for {}", opid), e))
+}
+
+/// List the wallet's async operations (z_sendmany and friends), oldest first. Only queued/executing
+/// ones unless include_finished; finished results stay readable until z_getoperationresult drains them.
+/// Lets the UI show in-flight sends and re-attach to them after a restart.
+#[tauri::command]
+pub async fn list_operations(app: tauri::AppHandle, include_finished: Option<bool>) -> Result<Vec<OperationSummary>, CommandError> {
+    let include_finished = include_finished.unwrap_or(false);
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    let opids: Vec<String> = make_rpc_call(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "z_listoperationids", vec![])
+        .await
+        .map_err(CommandError::from)?;
+    if opids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // z_getoperationstatus takes the whole id list in one call (and, unlike z_getoperationresult, doesn't consume it)
+    let statuses: Vec<OperationStatus> = make_rpc_call(
+        &creds.rpc_user,
+        &creds.rpc_pass,
+        &creds.rpc_host,
+        creds.rpc_port,
+        "z_getoperationstatus",
+        vec![json!(opids)],
+    )
+    .await
+    .map_err(CommandError::from)?;
+
+    let mut operations: Vec<OperationSummary> = statuses
+        .into_iter()
+        .filter(|s| include_finished || matches!(s.status.as_str(), "queued" | "executing"))
+        .map(|s| OperationSummary { opid: s.id, status: s.status, creation_time: s.creation_time })
+        .collect();
+    operations.sort_by_key(|op| op.creation_time.unwrap_or(0));
+
+    log::debug!("list_operations: {} of {} operation(s) (include_finished={})", operations.len(), opids.len(), include_finished);
+    Ok(operations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(status: &str, error: Option<OperationError>) -> OperationStatus {
+        OperationStatus {
+            id: "opid-test".to_string(),
+            status: status.to_string(),
+            creation_time: None,
+            result: None,
+            error,
+            execution_secs: None,
+        }
+    }
+
+    #[test]
+    fn success_is_done() {
+        let mut succeeded = status("success", None);
+        succeeded.result = Some(OperationTxResult { txid: "abc".to_string() });
+        assert!(matches!(operation_outcome(&succeeded), Ok(true)));
+    }
+
+    #[test]
+    fn failed_with_error_keeps_code_and_message() {
+        let error = OperationError { code: -6, message: "Insufficient funds".to_string() };
+        match operation_outcome(&status("failed", Some(error))) {
+            Err(VerusRpcError::OperationFailed { code, message }) => {
+                assert_eq!(code, -6);
+                assert_eq!(message, "Insufficient funds");
+            }
+            other => panic!("unexpected outcome {:?}", other),
+        }
+    }
+
+    #[test]
+    fn failed_without_error_reports_status() {
+        match operation_outcome(&status("failed", None)) {
+            Err(VerusRpcError::OperationFailed { code, message }) => {
+                assert_eq!(code, 0);
+                assert_eq!(message, "failed");
+            }
+            other => panic!("unexpected outcome {:?}", other),
+        }
+    }
+
+    #[test]
+    fn cancelled_is_a_failure() {
+        match operation_outcome(&status("cancelled", None)) {
+            Err(VerusRpcError::OperationFailed { code, message }) => {
+                assert_eq!(code, 0);
+                assert_eq!(message, "cancelled");
+            }
+            other => panic!("unexpected outcome {:?}", other),
+        }
+    }
+
+    #[test]
+    fn queued_and_executing_keep_polling() {
+        for pending in ["queued", "executing"] {
+            assert!(matches!(operation_outcome(&status(pending, None)), Ok(false)), "{}", pending);
+        }
+    }
+}
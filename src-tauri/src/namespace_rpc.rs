@@ -9,12 +9,90 @@
 // - Added blockchain ID to currency name mapping for getcurrency calls
 // - Added startblock filtering: only include namespaces where startblock <= current block height
 // - Updated both get_available_namespaces and get_root_currency to filter out future startblocks
+// - RPC calls now pass the configurable rpc_host from credentials
+// - listcurrencies/getcurrency calls retry transient failures with backoff
+// - Reserve fee index detection compares against exact satoshi integers with a tolerance (no float-drift mislabels)
+// - Added tests for reserve_index_from_import_fee (indices 0-9, float drift, 10+ satoshis, fractional, negative / NaN)
+// - get_available_namespaces results are cached in memory for 60s; force_refresh bypasses the cache
+// - get_available_namespaces emits `namespace-progress` ({processed, total}) after each batch and
+//   `namespace-resolved` (NamespaceOption) per namespace; the aggregated return value is unchanged
+// - get_available_namespaces takes optional batch_size / delay_ms (defaults 5 / 100ms)
+// - Added NamespaceFilter (options / proofprotocol / positive reserves, defaulting to the previous hard filter);
+//   with verbose=true the command returns {namespaces, rejected} with a reason per rejected currency
+// - get_root_currency reads the chain's native currency name from getinfo (cached per endpoint); the
+//   hardcoded blockchain map is only a fallback for offline/older daemons
+// - Added get_currency_reserves (reserve basket with names resolved via currencynames); getcurrency parsing shared in fetch_currency
+// - Added estimate_registration_cost (base fee, referral discount, net fee, and cost in the paying currency)
+// - Added find_conversion_path: intermediate currencies (up to two) between two currencies, built from
+//   reserve compositions (getcurrency) and the baskets holding a currency (getcurrencyconverters)
+// - Commands return CommandError (code from VerusRpcError::code) instead of a formatted String
+// - The native currency name comes from fetch_chain_info; the blockchain-id map is only used when the
+//   daemon can't be asked
+// - A namespace whose getcurrency lookup fails is kept with its own currency as the (unverified) fee currency;
+//   NamespaceOption.fee_currency_resolved says whether the fee currency was actually resolved
+// - Added get_namespace: a single getcurrency lookup with the same filters and fee resolution as
+//   get_available_namespaces (None, with the reason logged, when the currency doesn't qualify)
+// - Added get_registration_options: root currency and namespace list fetched concurrently, with a
+//   per-field error so one failing doesn't hide the other
+// - currencynames maps from parsed getcurrency responses are fed into the shared currency_names cache
+// - fetch_currency logs through log:: like find_conversion_path (which now also logs direct conversions)
+// - All output goes through log:: (println! bypassed the redacting logger); per-currency detail is debug level
+// - get_namespace only treats RPC error -5 as "no such namespace"; other daemon errors are returned
+// - The per-endpoint native currency cache lives in wallet_rpc::fetch_native_currency_name (shared with conversions)
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use super::rpc_client::make_rpc_call;
-use super::wallet_rpc::connect_and_get_block_height;
+use super::rpc_client::{make_rpc_call, make_rpc_call_retry, VerusRpcError, DEFAULT_RETRY_POLICY};
+use super::wallet_rpc::{connect_and_get_block_height, estimate_required_input, EstimateConversionRequest};
 use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+use crate::CommandError;
+
+// Default getcurrency concurrency and pause between batches. A local daemon handles far more;
+// a remote daemon (or a proxy in front of it) may rate-limit larger batches.
+pub const DEFAULT_NAMESPACE_BATCH_SIZE: usize = 5;
+pub const DEFAULT_NAMESPACE_BATCH_DELAY_MS: u64 = 100;
+
+// Frontend events emitted while namespaces are resolved
+const NAMESPACE_PROGRESS_EVENT: &str = "namespace-progress";
+const NAMESPACE_RESOLVED_EVENT: &str = "namespace-resolved";
+
+// How long a get_available_namespaces result stays fresh
+const NAMESPACE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+// Last namespace fetch: when, from which endpoint (host:port), with which filter, and its result
+struct NamespaceCacheEntry {
+    fetched_at: Instant,
+    endpoint: String,
+    filter: NamespaceFilter,
+    namespaces: Vec<NamespaceOption>,
+    rejected: Vec<NamespaceRejection>,
+}
+
+static NAMESPACE_CACHE: Mutex<Option<NamespaceCacheEntry>> = Mutex::new(None);
+
+// Satoshis per coin, used to interpret idimportfees as an integer
+const SATS_PER_COIN: f64 = 100_000_000.0;
+
+// Max distance (in satoshis) from an exact integer for idimportfees to count as a reserve index
+const RESERVE_INDEX_TOLERANCE_SATS: f64 = 0.001;
+
+// idimportfees of 0-9 satoshis means "fee is paid in reserve currency N" rather than an amount.
+// Scale to satoshis and only accept values that sit on an exact integer (within tolerance),
+// so drift like 2.9999999e-8 still maps to 3 and fractional values map to nothing.
+pub fn reserve_index_from_import_fee(idimportfees: f64) -> Option<usize> {
+    if !idimportfees.is_finite() || idimportfees < 0.0 {
+        return None;
+    }
+    let sats = idimportfees * SATS_PER_COIN;
+    let nearest = sats.round();
+    if (sats - nearest).abs() > RESERVE_INDEX_TOLERANCE_SATS || nearest > 9.0 {
+        return None;
+    }
+    Some(nearest as usize)
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CurrencyDefinition {
@@ -120,10 +198,146 @@ pub struct NamespaceOption {
     pub registration_fee: f64,
     pub fully_qualified_name: String,
     pub fee_currency_name: String,
+    #[serde(default = "default_true")]
+    pub fee_currency_resolved: bool, // false = getcurrency failed and fee_currency_name is a fallback guess
     pub options: u32,
     pub id_referral_levels: u32,
 }
 
+fn default_true() -> bool {
+    true
+}
+
+// Which currencies count as registrable namespaces; the default matches the original hard filter
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct NamespaceFilter {
+    pub require_positive_reserves: bool, // Require a non-empty reserve list with every reserve > 0
+    pub allowed_options: Vec<u32>,       // Currency `options` values to accept (33, 41 = basket currencies)
+    pub allowed_proof_protocols: Vec<u32>,
+}
+
+impl Default for NamespaceFilter {
+    fn default() -> Self {
+        NamespaceFilter {
+            require_positive_reserves: true,
+            allowed_options: vec![33, 41],
+            allowed_proof_protocols: vec![1],
+        }
+    }
+}
+
+// A currency excluded from the namespace list, and why
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NamespaceRejection {
+    pub name: String,
+    pub currency_id: String,
+    pub reason: String,
+}
+
+// get_available_namespaces result: a plain list (backward compatible) or, when verbose, the list plus rejections
+#[derive(Debug, Serialize, Clone)]
+#[serde(untagged)]
+pub enum NamespaceListing {
+    Plain(Vec<NamespaceOption>),
+    Verbose {
+        namespaces: Vec<NamespaceOption>,
+        rejected: Vec<NamespaceRejection>,
+    },
+}
+
+impl NamespaceListing {
+    fn new(namespaces: Vec<NamespaceOption>, rejected: Vec<NamespaceRejection>, verbose: bool) -> Self {
+        if verbose {
+            NamespaceListing::Verbose { namespaces, rejected }
+        } else {
+            NamespaceListing::Plain(namespaces)
+        }
+    }
+}
+
+// Everything the registration screen needs; each half carries its own error instead of failing the whole call
+#[derive(Debug, Serialize, Clone)]
+pub struct RegistrationOptions {
+    pub root: Option<NamespaceOption>,
+    pub root_error: Option<String>,
+    pub namespaces: Vec<NamespaceOption>,
+    pub namespaces_error: Option<String>, // Set when namespaces is empty because the listing failed
+}
+
+// Why a currency fails the namespace filter (None = accepted)
+fn namespace_rejection_reason(
+    def: &CurrencyDefinition,
+    reservecurrencies: Option<&[ReserveCurrency]>,
+    filter: &NamespaceFilter,
+    current_block_height: u64,
+) -> Option<String> {
+    if def.startblock > current_block_height {
+        return Some(format!("startblock {} is after current height {}", def.startblock, current_block_height));
+    }
+    if !filter.allowed_options.contains(&def.options) {
+        return Some(format!("options {} not in {:?}", def.options, filter.allowed_options));
+    }
+    if !filter.allowed_proof_protocols.contains(&def.proofprotocol) {
+        return Some(format!("proofprotocol {} not in {:?}", def.proofprotocol, filter.allowed_proof_protocols));
+    }
+    if filter.require_positive_reserves {
+        match reservecurrencies {
+            None => return Some("no reserves field".to_string()),
+            Some([]) => return Some("empty reserves array".to_string()),
+            Some(reserves) => {
+                if let Some((i, reserve)) = reserves.iter().enumerate().find(|(_, r)| r.reserves <= 0.0) {
+                    return Some(format!("reserve {} ({}) is {}", i, reserve.currencyid, reserve.reserves));
+                }
+            }
+        }
+    }
+    None
+}
+
+// One reserve currency of a basket, as returned by get_currency_reserves
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReserveBalance {
+    pub currency_id: String,
+    pub name: String, // Resolved via currencynames, falls back to the currency ID
+    pub weight: f64,
+    pub reserves: f64,
+    pub price_in_reserve: f64,
+}
+
+// Breakdown returned by estimate_registration_cost
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RegistrationCostEstimate {
+    pub base_fee: f64,          // idregistrationfees, in fee_currency
+    pub referral_discount: f64, // Amount saved by using a referral, in fee_currency
+    pub net_fee: f64,           // base_fee - referral_discount, in fee_currency
+    pub converted_amount: f64,  // Amount of pay_currency needed to cover net_fee
+    pub fee_currency: String,
+}
+
+// Standard Verus referral schedule: the fee is split into (idreferrallevels + 2) shares and a
+// registrant using a referral saves one share (e.g. 100 VRSC with 3 levels -> 20 VRSC off).
+pub(crate) fn referral_discount(base_fee: f64, referral_levels: u32) -> f64 {
+    if referral_levels == 0 {
+        return 0.0;
+    }
+    base_fee / (referral_levels as f64 + 2.0)
+}
+
+// Payload of the namespace-progress event
+#[derive(Debug, Serialize, Clone)]
+pub struct NamespaceProgress {
+    pub processed: usize,
+    pub total: usize,
+}
+
+// Emit a namespace event; a failed emit only affects the incremental UI, so just log it
+fn emit_namespace_event<S: Serialize + Clone>(app: &tauri::AppHandle, event: &str, payload: S) {
+    if let Err(e) = app.emit(event, payload) {
+        log::warn!("Failed to emit {} event: {}", event, e);
+    }
+}
+
 // Response structure for getcurrency (for root currency)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RootCurrencyResponse {
@@ -137,119 +351,152 @@ pub struct RootCurrencyResponse {
 #[tauri::command]
 pub async fn get_available_namespaces(
     app: tauri::AppHandle,
-) -> Result<Vec<NamespaceOption>, String> {
-    println!("Starting namespace fetch...");
-    
+    force_refresh: Option<bool>,
+    batch_size: Option<usize>,
+    delay_ms: Option<u64>,
+    filter: Option<NamespaceFilter>,
+    verbose: Option<bool>,
+) -> Result<NamespaceListing, CommandError> {
+    let filter = filter.unwrap_or_default();
+    let verbose = verbose.unwrap_or(false);
+    let batch_size = batch_size.unwrap_or(DEFAULT_NAMESPACE_BATCH_SIZE);
+    if batch_size < 1 {
+        return Err(CommandError::invalid_argument("batch_size must be at least 1"));
+    }
+    let delay_ms = delay_ms.unwrap_or(DEFAULT_NAMESPACE_BATCH_DELAY_MS);
+
     // Load credentials first
-    let creds = crate::credentials::load_credentials(app).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+    let creds = crate::credentials::load_credentials(app.clone()).await?;
+    let endpoint = format!("{}:{}", creds.rpc_host, creds.rpc_port);
+
+    // Serve from cache unless it's stale, from another daemon, or the caller forces a refresh
+    if !force_refresh.unwrap_or(false) {
+        let cache = NAMESPACE_CACHE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(entry) = cache.as_ref() {
+            if entry.endpoint == endpoint && entry.filter == filter && entry.fetched_at.elapsed() < NAMESPACE_CACHE_TTL {
+                let namespaces = &entry.namespaces;
+                log::debug!("Returning {} cached namespaces ({}s old)", namespaces.len(), entry.fetched_at.elapsed().as_secs());
+                // Replay events so incremental listeners behave the same as on a fresh fetch
+                for namespace in namespaces {
+                    emit_namespace_event(&app, NAMESPACE_RESOLVED_EVENT, namespace.clone());
+                }
+                emit_namespace_event(&app, NAMESPACE_PROGRESS_EVENT, NamespaceProgress { processed: namespaces.len(), total: namespaces.len() });
+                return Ok(NamespaceListing::new(namespaces.clone(), entry.rejected.clone(), verbose));
+            }
+        }
+    }
+
+    let (namespaces, rejected) = fetch_available_namespaces(&app, &creds, &filter, batch_size, delay_ms).await?;
+
+    *NAMESPACE_CACHE.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(NamespaceCacheEntry {
+        fetched_at: Instant::now(),
+        endpoint,
+        filter,
+        namespaces: namespaces.clone(),
+        rejected: rejected.clone(),
+    });
+
+    Ok(NamespaceListing::new(namespaces, rejected, verbose))
+}
+
+// listcurrencies + filtering + batched fee currency resolution (uncached)
+async fn fetch_available_namespaces(
+    app: &tauri::AppHandle,
+    creds: &crate::credentials::Credentials,
+    filter: &NamespaceFilter,
+    batch_size: usize,
+    delay_ms: u64,
+) -> Result<(Vec<NamespaceOption>, Vec<NamespaceRejection>), CommandError> {
+    log::info!("Starting namespace fetch with filter {:?}...", filter);
+    let mut rejected = Vec::new();
     
     // Get current block height for startblock filtering
     let current_block_height = connect_and_get_block_height(
         creds.rpc_user.clone(),
         creds.rpc_pass.clone(),
+        creds.rpc_host.clone(),
         creds.rpc_port,
     ).await
-        .map_err(|e| format!("Failed to get current block height: {}", e))?;
+        .map_err(|e| CommandError::with_context("Failed to get current block height", e))?;
     
-    println!("Current block height: {}", current_block_height);
-    println!("Credentials loaded, calling listcurrencies...");
+    log::debug!("Current block height: {}", current_block_height);
+    log::debug!("Credentials loaded, calling listcurrencies...");
     
     // Call listcurrencies RPC method
-    let response: Value = make_rpc_call(
+    let response: Value = make_rpc_call_retry(
         &creds.rpc_user,
         &creds.rpc_pass,
+        &creds.rpc_host,
         creds.rpc_port,
         "listcurrencies",
         vec![],
+        DEFAULT_RETRY_POLICY,
     ).await
-        .map_err(|e| format!("Failed to call listcurrencies: {}", e))?;
+        .map_err(|e| CommandError::with_context("Failed to call listcurrencies", e))?;
     
-    println!("Got response, parsing currencies...");
-    println!("Response type: {:?}", response.as_array().map(|arr| arr.len()).unwrap_or(0));
+    log::debug!("Got response, parsing currencies...");
+    log::debug!("Response type: {:?}", response.as_array().map(|arr| arr.len()).unwrap_or(0));
     
     // Parse the response as an array of currency info
     let currencies_array: Vec<CurrencyInfo> = match serde_json::from_value::<Vec<CurrencyInfo>>(response.clone()) {
         Ok(currencies) => {
-            println!("Successfully parsed {} currencies", currencies.len());
+            log::debug!("Successfully parsed {} currencies", currencies.len());
             currencies
         }
         Err(e) => {
-            println!("Failed to parse currencies response: {}", e);
-            println!("Response sample: {}", serde_json::to_string_pretty(&response).unwrap_or_else(|_| "Unable to serialize".to_string()));
-            return Err(format!("Failed to parse currencies response: {}", e));
+            log::error!("Failed to parse currencies response: {}", e);
+            log::debug!("Response sample: {}", serde_json::to_string_pretty(&response).unwrap_or_else(|_| "Unable to serialize".to_string()));
+            return Err(CommandError::from(VerusRpcError::ParseError(format!("Failed to parse currencies response: {}", e))));
         }
     };
     
-    println!("Parsed {} currencies, filtering...", currencies_array.len());
+    log::debug!("Parsed {} currencies, filtering...", currencies_array.len());
     
-    // First pass: filter by existing criteria + ALL reserves > 0
+    // First pass: filter by startblock + the caller's NamespaceFilter (default: options 33/41, proofprotocol 1, ALL reserves > 0)
     let mut valid_currency_infos = Vec::new();
     
     for (index, currency_info) in currencies_array.iter().enumerate() {
         let def = &currency_info.currencydefinition;
         
-        println!(
+        log::debug!(
             "Processing currency {}/{}: {} - options: {}, proofprotocol: {}",
             index + 1, currencies_array.len(), def.name, def.options, def.proofprotocol
         );
         
-        // Filter criteria: options must be 33 or 41, proofprotocol must be 1, and startblock must be <= current height
-        if (def.options == 33 || def.options == 41) && def.proofprotocol == 1 && def.startblock <= current_block_height {
-            println!("  ✓ Currency {} passed options/proofprotocol/startblock check (startblock: {} <= current: {})", 
-                def.name, def.startblock, current_block_height);
-            
-            // NEW: Check that currency has reserves and ALL reserves > 0
-            if let Some(reserves) = &currency_info.bestcurrencystate.reservecurrencies {
-                if reserves.is_empty() {
-                    println!("  ✗ Currency {} has empty reserves array", def.name);
-                } else {
-                    let reserve_count = reserves.len();
-                    println!("  Checking {} reserves for currency {}", reserve_count, def.name);
-                    
-                    let mut all_reserves_positive = true;
-                    for (i, reserve) in reserves.iter().enumerate() {
-                        println!("    Reserve {}: {} (reserves: {})", i, reserve.currencyid, reserve.reserves);
-                        if reserve.reserves <= 0.0 {
-                            all_reserves_positive = false;
-                            println!("    ✗ Reserve {} has zero reserves: {}", i, reserve.reserves);
-                        }
-                    }
-                    
-                    if all_reserves_positive {
-                        println!("  ✓ Currency {} passed all filters (all {} reserves > 0)", def.name, reserve_count);
-                        valid_currency_infos.push(currency_info.clone());
-                    } else {
-                        println!("  ✗ Currency {} failed reserve check (some reserves are 0)", def.name);
-                    }
-                }
-            } else {
-                println!("  ✗ Currency {} has no reserves field", def.name);
+        let reserves = currency_info.bestcurrencystate.reservecurrencies.as_deref();
+        match namespace_rejection_reason(def, reserves, filter, current_block_height) {
+            None => {
+                log::debug!("  ✓ Currency {} passed all filters", def.name);
+                valid_currency_infos.push(currency_info.clone());
+            }
+            Some(reason) => {
+                log::debug!("  ✗ Currency {} rejected: {}", def.name, reason);
+                rejected.push(NamespaceRejection {
+                    name: def.name.clone(),
+                    currency_id: def.currencyid.clone(),
+                    reason,
+                });
             }
-        } else {
-            let startblock_check = if def.startblock <= current_block_height { "✓" } else { "✗" };
-            println!("  ✗ Currency {} failed initial checks (options: {}, proofprotocol: {}, startblock: {} {} current: {})", 
-                def.name, def.options, def.proofprotocol, def.startblock, startblock_check, current_block_height);
         }
     }
     
-    println!("Found {} currencies passing initial filters", valid_currency_infos.len());
+    log::info!("Found {} currencies passing initial filters", valid_currency_infos.len());
     
-    // Second pass: make batched getcurrency calls to resolve fee currencies (5 at a time)
-    println!("Processing {} namespaces in batches of 5...", valid_currency_infos.len());
+    // Second pass: make batched getcurrency calls to resolve fee currencies (batch_size at a time)
+    log::debug!("Processing {} namespaces in batches of {}...", valid_currency_infos.len(), batch_size);
     
     if valid_currency_infos.is_empty() {
-        println!("No namespaces to process - returning empty list");
-        return Ok(Vec::new());
+        log::debug!("No namespaces to process - returning empty list");
+        emit_namespace_event(app, NAMESPACE_PROGRESS_EVENT, NamespaceProgress { processed: 0, total: 0 });
+        return Ok((Vec::new(), rejected));
     }
     
     let mut valid_namespaces = Vec::new();
-    let batch_size = 5;
     let total_batches = (valid_currency_infos.len() + batch_size - 1) / batch_size;
     
     // Process in batches
     for (batch_index, batch) in valid_currency_infos.chunks(batch_size).enumerate() {
-        println!("Processing batch {}/{} ({} items)...", batch_index + 1, total_batches, batch.len());
+        log::debug!("Processing batch {}/{} ({} items)...", batch_index + 1, total_batches, batch.len());
         
         // Create futures for this batch
         let mut batch_futures = Vec::new();
@@ -257,14 +504,16 @@ pub async fn get_available_namespaces(
         for currency_info in batch {
             let rpc_user = creds.rpc_user.clone();
             let rpc_pass = creds.rpc_pass.clone();
+            let rpc_host = creds.rpc_host.clone();
             let rpc_port = creds.rpc_port;
-            let currency_info_clone = currency_info.clone();
+            let definition = currency_info.currencydefinition.clone();
             
             let future = async move {
                 resolve_namespace_fee_currency(
-                    currency_info_clone,
+                    definition,
                     &rpc_user,
                     &rpc_pass,
+                    &rpc_host,
                     rpc_port,
                 ).await
             };
@@ -276,37 +525,36 @@ pub async fn get_available_namespaces(
         let batch_results = futures::future::join_all(batch_futures).await;
         
         // Process batch results
-        for (local_index, result) in batch_results.into_iter().enumerate() {
+        for (local_index, namespace) in batch_results.into_iter().enumerate() {
             let global_index = batch_index * batch_size + local_index + 1;
-            match result {
-                Ok(namespace) => {
-                    println!("✓ Result {}: Successfully resolved namespace: {} (fee: {} {})", 
-                        global_index, namespace.name, namespace.registration_fee, namespace.fee_currency_name);
-                    valid_namespaces.push(namespace);
-                }
-                Err(e) => {
-                    println!("✗ Result {}: Failed to resolve namespace: {}", global_index, e);
-                    // Skip this namespace as requested
-                }
-            }
+            // Namespaces are never dropped here: an unresolved fee currency falls back to the namespace's own
+            log::debug!("✓ Result {}: namespace: {} (fee: {} {}{})", 
+                global_index, namespace.name, namespace.registration_fee, namespace.fee_currency_name,
+                if namespace.fee_currency_resolved { "" } else { ", unverified" });
+            emit_namespace_event(app, NAMESPACE_RESOLVED_EVENT, namespace.clone());
+            valid_namespaces.push(namespace);
         }
         
+        // Progress counts candidates processed (resolved or skipped), not just successes
+        let processed = (batch_index * batch_size + batch.len()).min(valid_currency_infos.len());
+        emit_namespace_event(app, NAMESPACE_PROGRESS_EVENT, NamespaceProgress { processed, total: valid_currency_infos.len() });
+        
         // Small delay between batches to be nice to the RPC server
-        if batch_index < total_batches - 1 {
-            println!("Waiting 100ms before next batch...");
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        if batch_index < total_batches - 1 && delay_ms > 0 {
+            log::debug!("Waiting {}ms before next batch...", delay_ms);
+            tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
         }
     }
     
-    println!("Final result: {} valid namespaces", valid_namespaces.len());
+    log::info!("Final result: {} valid namespaces", valid_namespaces.len());
     
     // Sort by name for better UX
     valid_namespaces.sort_by(|a, b| a.name.cmp(&b.name));
     
-    Ok(valid_namespaces)
+    Ok((valid_namespaces, rejected))
 }
 
-// Map blockchain ID to currency name for getcurrency calls
+// Fallback map from blockchain ID to currency name, used only when the daemon can't be reached
 fn get_currency_name_for_blockchain(blockchain_id: &str) -> Option<String> {
     match blockchain_id {
         "verus-testnet" => Some("vrsctest".to_string()),
@@ -318,66 +566,172 @@ fn get_currency_name_for_blockchain(blockchain_id: &str) -> Option<String> {
     }
 }
 
+// Ask the daemon for its native currency name (ChainInfo.name, e.g. "VRSCTEST"), cached per endpoint
+async fn fetch_native_currency_name(creds: &crate::credentials::Credentials) -> Option<String> {
+    match super::wallet_rpc::fetch_native_currency_name(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port).await {
+        Ok(name) => Some(name),
+        Err(e) => {
+            log::warn!("Chain info unavailable, falling back to static currency map: {}", e);
+            None
+        }
+    }
+}
+
+// Look up a single namespace by name (e.g. from a registration deep link) without the full
+// listcurrencies scan. Applies the default NamespaceFilter; Ok(None) when it doesn't qualify.
+#[tauri::command]
+pub async fn get_namespace(
+    app: tauri::AppHandle,
+    namespace_name: String,
+) -> Result<Option<NamespaceOption>, CommandError> {
+    log::info!("Looking up namespace: {}", namespace_name);
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    let current_block_height = connect_and_get_block_height(
+        creds.rpc_user.clone(),
+        creds.rpc_pass.clone(),
+        creds.rpc_host.clone(),
+        creds.rpc_port,
+    ).await
+        .map_err(CommandError::from)?;
+
+    let response: Value = match make_rpc_call_retry(
+        &creds.rpc_user,
+        &creds.rpc_pass,
+        &creds.rpc_host,
+        creds.rpc_port,
+        "getcurrency",
+        vec![json!(namespace_name)],
+        DEFAULT_RETRY_POLICY,
+    ).await {
+        Ok(response) => response,
+        // -5 is the daemon's "invalid currency" answer: there is no such currency
+        Err(VerusRpcError::Rpc { code: -5, message }) => {
+            log::debug!("✗ Namespace {} not found: {}", namespace_name, message);
+            return Ok(None);
+        }
+        Err(e) => return Err(CommandError::with_context(format!("Failed to call getcurrency for {}", namespace_name), e)),
+    };
+
+    // getcurrency returns the definition fields at the top level, plus the current state
+    let def: CurrencyDefinition = match serde_json::from_value(response.clone()) {
+        Ok(def) => def,
+        Err(e) => {
+            log::debug!("✗ Namespace {} rejected: not a full currency definition ({})", namespace_name, e);
+            return Ok(None);
+        }
+    };
+    let reserves: Option<Vec<ReserveCurrency>> = response
+        .get("bestcurrencystate")
+        .and_then(|state| state.get("reservecurrencies"))
+        .and_then(|reserves| serde_json::from_value(reserves.clone()).ok());
+
+    if let Some(reason) = namespace_rejection_reason(&def, reserves.as_deref(), &NamespaceFilter::default(), current_block_height) {
+        log::debug!("✗ Namespace {} rejected: {}", namespace_name, reason);
+        return Ok(None);
+    }
+
+    let namespace = resolve_namespace_fee_currency(def, &creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port).await;
+    log::info!("✓ Namespace {} qualifies (fee: {} {})", namespace.name, namespace.registration_fee, namespace.fee_currency_name);
+    Ok(Some(namespace))
+}
+
+/// Root currency and available namespaces in one call, fetched concurrently (cached namespaces and
+/// namespace events behave as in get_available_namespaces)
+#[tauri::command]
+pub async fn get_registration_options(
+    app: tauri::AppHandle,
+    blockchain_id: String,
+) -> Result<RegistrationOptions, CommandError> {
+    let (root, namespaces) = tokio::join!(
+        get_root_currency(app.clone(), blockchain_id),
+        get_available_namespaces(app.clone(), None, None, None, None, None),
+    );
+
+    let (root, root_error) = match root {
+        Ok(root) => (Some(root), None),
+        Err(e) => {
+            log::warn!("Registration options: root currency failed: {}", e);
+            (None, Some(e.message))
+        }
+    };
+    let (namespaces, namespaces_error) = match namespaces {
+        Ok(NamespaceListing::Plain(namespaces)) | Ok(NamespaceListing::Verbose { namespaces, .. }) => (namespaces, None),
+        Err(e) => {
+            log::warn!("Registration options: namespace listing failed: {}", e);
+            (Vec::new(), Some(e.message))
+        }
+    };
+
+    Ok(RegistrationOptions { root, root_error, namespaces, namespaces_error })
+}
+
 // Get root currency information for a blockchain
 #[tauri::command]
 pub async fn get_root_currency(
     app: tauri::AppHandle,
     blockchain_id: String,
-) -> Result<NamespaceOption, String> {
-    println!("Getting root currency for blockchain: {}", blockchain_id);
+) -> Result<NamespaceOption, CommandError> {
+    log::info!("Getting root currency for blockchain: {}", blockchain_id);
     
     // Load credentials
-    let creds = crate::credentials::load_credentials(app).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+    let creds = crate::credentials::load_credentials(app).await?;
     
     // Get current block height for startblock filtering
     let current_block_height = connect_and_get_block_height(
         creds.rpc_user.clone(),
         creds.rpc_pass.clone(),
+        creds.rpc_host.clone(),
         creds.rpc_port,
     ).await
-        .map_err(|e| format!("Failed to get current block height: {}", e))?;
+        .map_err(|e| CommandError::with_context("Failed to get current block height", e))?;
     
-    println!("Current block height: {}", current_block_height);
+    log::debug!("Current block height: {}", current_block_height);
     
-    // Get the currency name for this blockchain
-    let currency_name = get_currency_name_for_blockchain(&blockchain_id)
-        .ok_or_else(|| format!("Unsupported blockchain: {}", blockchain_id))?;
+    // Get the currency name for this blockchain: ask the daemon first, then the static map
+    let currency_name = match fetch_native_currency_name(&creds).await {
+        Some(name) => name,
+        None => get_currency_name_for_blockchain(&blockchain_id)
+            .ok_or_else(|| CommandError::invalid_argument(format!("Unsupported blockchain: {}", blockchain_id)))?,
+    };
     
-    println!("Calling getcurrency for: {}", currency_name);
+    log::debug!("Calling getcurrency for: {}", currency_name);
     
     // Call getcurrency RPC method
-    let response: Value = make_rpc_call(
+    let response: Value = make_rpc_call_retry(
         &creds.rpc_user,
         &creds.rpc_pass,
+        &creds.rpc_host,
         creds.rpc_port,
         "getcurrency",
         vec![json!(currency_name)],
+        DEFAULT_RETRY_POLICY,
     ).await
-        .map_err(|e| format!("Failed to call getcurrency: {}", e))?;
+        .map_err(|e| CommandError::with_context(format!("Failed to call getcurrency for {}", currency_name), e))?;
     
-    println!("Got getcurrency response for {}", currency_name);
+    log::debug!("Got getcurrency response for {}", currency_name);
     
     // Parse the response as full GetCurrencyResponse to access startblock
     let currency_details: GetCurrencyResponse = serde_json::from_value::<GetCurrencyResponse>(response.clone())
         .map_err(|e| {
-            println!("Failed to parse getcurrency response: {}", e);
-            println!("Response: {}", serde_json::to_string_pretty(&response).unwrap_or_else(|_| "Unable to serialize".to_string()));
-            format!("Failed to parse getcurrency response: {}", e)
+            log::error!("Failed to parse getcurrency response: {}", e);
+            log::debug!("Response: {}", serde_json::to_string_pretty(&response).unwrap_or_else(|_| "Unable to serialize".to_string()));
+            VerusRpcError::ParseError(format!("Failed to parse getcurrency response: {}", e))
         })?;
+    remember_currency_names(&currency_details, &creds.rpc_host, creds.rpc_port);
     
     // Check startblock if available
     if let Some(startblock) = currency_details.startblock {
         if startblock > current_block_height {
-            return Err(format!(
+            return Err(CommandError::new("CURRENCY_NOT_STARTED", format!(
                 "Root currency {} has not started yet (startblock: {} > current: {})", 
                 currency_name, startblock, current_block_height
-            ));
+            )));
         }
-        println!("Root currency {} startblock check passed (startblock: {} <= current: {})", 
+        log::debug!("Root currency {} startblock check passed (startblock: {} <= current: {})", 
             currency_name, startblock, current_block_height);
     } else {
-        println!("Root currency {} has no startblock field - assuming active", currency_name);
+        log::debug!("Root currency {} has no startblock field - assuming active", currency_name);
     }
     
     // Convert to NamespaceOption format
@@ -387,11 +741,12 @@ pub async fn get_root_currency(
         registration_fee: currency_details.idregistrationfees,
         fully_qualified_name: currency_details.name.clone(),
         fee_currency_name: currency_details.name.clone(), // Root currency fees are paid in itself
+        fee_currency_resolved: true,
         options: 41, // Assume root currencies support referral system
         id_referral_levels: currency_details.idreferrallevels.unwrap_or(0),
     };
     
-    println!("Root currency created: {} (fee: {} {})", 
+    log::info!("Root currency created: {} (fee: {} {})", 
         namespace_option.name, 
         namespace_option.registration_fee, 
         namespace_option.fee_currency_name);
@@ -400,138 +755,467 @@ pub async fn get_root_currency(
 }
 
 async fn resolve_namespace_fee_currency(
-    currency_info: CurrencyInfo,
+    definition: CurrencyDefinition,
     rpc_user: &str,
     rpc_pass: &str,
+    rpc_host: &str,
     rpc_port: u16,
-) -> Result<NamespaceOption, String> {
-    let def = &currency_info.currencydefinition;
+) -> NamespaceOption {
+    let def = &definition;
     
-    println!("  Resolving fee currency for namespace: {}", def.name);
+    log::debug!("  Resolving fee currency for namespace: {}", def.name);
+    
+    // listcurrencies already gave us everything but the fee currency, so a flaky getcurrency
+    // shouldn't hide the namespace; assume it charges in its own currency and flag it
+    let (fee_currency_name, fee_currency_resolved) =
+        match fetch_fee_currency_name(def, rpc_user, rpc_pass, rpc_host, rpc_port).await {
+            Ok(name) => (name, true),
+            Err(e) => {
+                log::warn!("  ⚠ {} - falling back to '{}' as fee currency (unverified)", e, def.name);
+                (def.name.clone(), false)
+            }
+        };
     
+    NamespaceOption {
+        name: def.name.clone(),
+        currency_id: def.currencyid.clone(),
+        registration_fee: def.idregistrationfees,
+        fully_qualified_name: def.fullyqualifiedname.clone(),
+        fee_currency_name,
+        fee_currency_resolved,
+        options: def.options,
+        id_referral_levels: def.idreferrallevels,
+    }
+}
+
+// Look up the currency a namespace charges its registration fee in (getcurrency + idimportfees)
+async fn fetch_fee_currency_name(
+    def: &CurrencyDefinition,
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+) -> Result<String, String> {    
     // Call getcurrency to get currency names mapping
-    let response: Value = make_rpc_call(
+    let response: Value = make_rpc_call_retry(
         rpc_user,
         rpc_pass,
+        rpc_host,
         rpc_port,
         "getcurrency",
         vec![json!(def.currencyid)],
+        DEFAULT_RETRY_POLICY,
     ).await
         .map_err(|e| {
-            println!("  ✗ RPC call failed for {}: {}", def.name, e);
+            log::warn!("  ✗ RPC call failed for {}: {}", def.name, e);
             format!("Failed to call getcurrency for {}: {}", def.name, e)
         })?;
     
-    println!("  ✓ Got getcurrency response for {}", def.name);
+    log::debug!("  ✓ Got getcurrency response for {}", def.name);
     
     let currency_details: GetCurrencyResponse = serde_json::from_value::<GetCurrencyResponse>(response.clone())
         .map_err(|e| {
-            println!("  ✗ Failed to parse getcurrency response for {}: {}", def.name, e);
-            println!("  Response sample: {}", serde_json::to_string_pretty(&response).unwrap_or_else(|_| "Unable to serialize".to_string()));
+            log::error!("  ✗ Failed to parse getcurrency response for {}: {}", def.name, e);
+            log::debug!("  Response sample: {}", serde_json::to_string_pretty(&response).unwrap_or_else(|_| "Unable to serialize".to_string()));
             format!("Failed to parse getcurrency response for {}: {}", def.name, e)
         })?;
+    remember_currency_names(&currency_details, rpc_host, rpc_port);
     
     // Determine fee currency based on idimportfees
-    println!("  Determining fee currency for {} (idimportfees: {})", def.name, def.idimportfees);
+    log::debug!("  Determining fee currency for {} (idimportfees: {})", def.name, def.idimportfees);
     
     // Check if idimportfees is one of the special reserve index values (0.00000000 - 0.00000009)
-    let reserve_index_opt = match def.idimportfees {
-        x if (x * 100000000.0).round() as i32 >= 0 && (x * 100000000.0).round() as i32 <= 9 => {
-            let index = (x * 100000000.0).round() as usize;
-            if index <= 9 { Some(index) } else { None }
-        }
-        _ => None
-    };
+    let reserve_index_opt = reserve_index_from_import_fee(def.idimportfees);
     
     let fee_currency_name = if let Some(reserve_index) = reserve_index_opt {
         // Special case: fee is in one of the reserve currencies (index 0-9)
-        println!("  Reserve fee case: idimportfees {} -> reserve index {}", def.idimportfees, reserve_index);
+        log::debug!("  Reserve fee case: idimportfees {} -> reserve index {}", def.idimportfees, reserve_index);
         
         if let Some(currency_names) = &currency_details.currencynames {
-            println!("  Found currency names mapping with {} entries", currency_names.len());
+            log::debug!("  Found currency names mapping with {} entries", currency_names.len());
             
             if let Some(reserve_currencies) = &currency_details.bestcurrencystate {
                 if let Some(reserves) = &reserve_currencies.reservecurrencies {
                     let reserve_count = reserves.len();
-                    println!("  Found {} reserve currencies", reserve_count);
+                    log::debug!("  Found {} reserve currencies", reserve_count);
                     
                     if reserve_index < reserve_count {
                         let reserve_currency_id = &reserves[reserve_index].currencyid;
-                        println!("  Looking up reserve currency ID: {}", reserve_currency_id);
+                        log::debug!("  Looking up reserve currency ID: {}", reserve_currency_id);
                         
                         let currency_name = currency_names.get(reserve_currency_id)
                             .unwrap_or(&format!("Unknown_{}", reserve_index))
                             .clone();
                         
-                        println!("  ✓ Resolved to currency: {}", currency_name);
+                        log::debug!("  ✓ Resolved to currency: {}", currency_name);
                         currency_name
                     } else {
-                        println!("  ✗ Invalid reserve index {} (only {} reserves available)", reserve_index, reserve_count);
+                        log::warn!("  ✗ Invalid reserve index {} (only {} reserves available)", reserve_index, reserve_count);
                         format!("InvalidIndex_{}", reserve_index)
                     }
                 } else {
-                    println!("  ✗ No reservecurrencies found in bestcurrencystate");
+                    log::warn!("  ✗ No reservecurrencies found in bestcurrencystate");
                     "NoReserves".to_string()
                 }
             } else {
-                println!("  ✗ No bestcurrencystate found in getcurrency response");
+                log::warn!("  ✗ No bestcurrencystate found in getcurrency response");
                 "UnknownReserve".to_string()
             }
         } else {
-            println!("  ✗ No currencynames found in getcurrency response");
+            log::warn!("  ✗ No currencynames found in getcurrency response");
             "UnknownCurrency".to_string()
         }
     } else {
         // Default case: fee is in the namespace's own currency
-        println!("  ✓ Default fee case: using namespace currency '{}' (idimportfees: {})", def.name, def.idimportfees);
+        log::debug!("  ✓ Default fee case: using namespace currency '{}' (idimportfees: {})", def.name, def.idimportfees);
         def.name.clone()
     };
     
-    println!("  ✓ Final fee currency name: {}", fee_currency_name);
+    log::debug!("  ✓ Final fee currency name: {}", fee_currency_name);
     
-    Ok(NamespaceOption {
-        name: def.name.clone(),
-        currency_id: def.currencyid.clone(),
-        registration_fee: def.idregistrationfees,
-        fully_qualified_name: def.fullyqualifiedname.clone(),
-        fee_currency_name,
-        options: def.options,
-        id_referral_levels: def.idreferrallevels,
-    })
+    Ok(fee_currency_name)
 } 
 
-// Tauri command to get currency details including reserves
-#[tauri::command]
-pub async fn get_currency(
-    app: tauri::AppHandle,
-    currencyname: String,
-) -> Result<GetCurrencyResponse, String> {
-    println!("Getting currency details for: {}", currencyname);
-    
-    // Load credentials
-    let creds = crate::credentials::load_credentials(app).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
-    
+// Fetch and parse getcurrency for a currency name or ID
+async fn fetch_currency(
+    creds: &crate::credentials::Credentials,
+    currencyname: &str,
+) -> Result<GetCurrencyResponse, CommandError> {
     // Call getcurrency RPC method
     let response: Value = make_rpc_call(
         &creds.rpc_user,
         &creds.rpc_pass,
+        &creds.rpc_host,
         creds.rpc_port,
         "getcurrency",
         vec![json!(currencyname)],
     ).await
-        .map_err(|e| format!("Failed to call getcurrency: {}", e))?;
+        .map_err(|e| CommandError::with_context("Failed to call getcurrency", e))?;
     
-    println!("Got getcurrency response for {}", currencyname);
+    log::debug!("Got getcurrency response for {}", currencyname);
     
     // Parse the response
-    let currency_details: GetCurrencyResponse = serde_json::from_value::<GetCurrencyResponse>(response.clone())
+    let currency_details = serde_json::from_value::<GetCurrencyResponse>(response.clone())
         .map_err(|e| {
-            println!("Failed to parse getcurrency response: {}", e);
-            println!("Response: {}", serde_json::to_string_pretty(&response).unwrap_or_else(|_| "Unable to serialize".to_string()));
-            format!("Failed to parse getcurrency response: {}", e)
+            log::error!("Failed to parse getcurrency response for {}: {}", currencyname, e);
+            log::debug!("Response: {}", serde_json::to_string_pretty(&response).unwrap_or_else(|_| "Unable to serialize".to_string()));
+            VerusRpcError::ParseError(format!("Failed to parse getcurrency response: {}", e))
         })?;
+    remember_currency_names(&currency_details, &creds.rpc_host, creds.rpc_port);
+    Ok(currency_details)
+}
+
+// Share a response's currencynames map with the process-wide name cache
+fn remember_currency_names(currency: &GetCurrencyResponse, rpc_host: &str, rpc_port: u16) {
+    if let Some(names) = &currency.currencynames {
+        crate::currency_names::remember_currency_names(rpc_host, rpc_port, names);
+    }
+}
+
+// Tauri command to get currency details including reserves
+#[tauri::command]
+pub async fn get_currency(
+    app: tauri::AppHandle,
+    currencyname: String,
+) -> Result<GetCurrencyResponse, CommandError> {
+    log::info!("Getting currency details for: {}", currencyname);
     
-    println!("Successfully parsed currency details for {}", currencyname);
+    // Load credentials
+    let creds = crate::credentials::load_credentials(app).await?;
+    
+    let currency_details = fetch_currency(&creds, &currencyname).await?;
+    
+    log::debug!("Successfully parsed currency details for {}", currencyname);
     Ok(currency_details)
-} 
\ No newline at end of file
+}
+
+// --- Conversion pathfinding ---
+
+// Intermediate currencies allowed in a conversion path
+const MAX_CONVERSION_HOPS: usize = 2;
+// First-hop candidates explored (by liquidity) when looking for a two-hop path
+const MAX_PATH_CANDIDATES: usize = 10;
+
+// A currency directly convertible to/from another: (name, liquidity). Liquidity is the reserve amount
+// on the connecting basket - a rough ranking only, since amounts are in different currencies.
+type ConversionNeighbors = HashMap<String, (String, f64)>;
+
+// Currencies reachable in one direct conversion: the currency's own reserves (if it's a basket)
+// plus every basket holding it as a reserve
+async fn conversion_neighbors(
+    creds: &crate::credentials::Credentials,
+    currency: &GetCurrencyResponse,
+) -> ConversionNeighbors {
+    let mut neighbors = ConversionNeighbors::new();
+
+    let names = currency.currencynames.clone().unwrap_or_default();
+    let reserves = currency
+        .bestcurrencystate
+        .as_ref()
+        .and_then(|state| state.reservecurrencies.as_ref());
+    for reserve in reserves.into_iter().flatten() {
+        let name = names.get(&reserve.currencyid).cloned().unwrap_or_else(|| reserve.currencyid.clone());
+        neighbors.insert(reserve.currencyid.clone(), (name, reserve.reserves));
+    }
+
+    // Each entry is {"<basket name>": {currency definition}, "lastnotarization": {...}, ...}
+    match make_rpc_call::<Vec<Value>>(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "getcurrencyconverters", vec![json!(currency.currencyid)]).await {
+        Ok(converters) => {
+            for entry in &converters {
+                let Some(object) = entry.as_object() else { continue };
+                let Some((name, basket_id)) = object.iter().find_map(|(key, value)| {
+                    value.get("currencyid").and_then(|id| id.as_str()).map(|id| (key.clone(), id.to_string()))
+                }) else {
+                    continue;
+                };
+                let liquidity = entry["lastnotarization"]["currencystate"]["reservecurrencies"]
+                    .as_array()
+                    .and_then(|reserves| reserves.iter().find(|r| r["currencyid"].as_str() == Some(currency.currencyid.as_str())))
+                    .and_then(|r| r["reserves"].as_f64())
+                    .unwrap_or(0.0);
+                neighbors.entry(basket_id).or_insert((name, liquidity));
+            }
+        }
+        Err(e) => log::warn!("find_conversion_path: getcurrencyconverters failed for {}: {}", currency.name, e),
+    }
+
+    neighbors
+}
+
+// Best shared neighbor of two neighbor sets (highest liquidity on the weaker side)
+fn best_common_neighbor(a: &ConversionNeighbors, b: &ConversionNeighbors, exclude: &[&str]) -> Option<(String, f64)> {
+    a.iter()
+        .filter(|(id, _)| !exclude.contains(&id.as_str()))
+        .filter_map(|(id, (name, liquidity_a))| b.get(id).map(|(_, liquidity_b)| (name.clone(), liquidity_a.min(*liquidity_b))))
+        .max_by(|x, y| x.1.partial_cmp(&y.1).unwrap_or(std::cmp::Ordering::Equal))
+}
+
+/// Intermediate currencies to convert through, in order (empty = direct conversion). A single
+/// intermediate basket can be passed as `via` to estimate_conversion / send_currency_conversion.
+#[tauri::command]
+pub async fn find_conversion_path(
+    app: tauri::AppHandle,
+    from_currency: String,
+    to_currency: String,
+) -> Result<Vec<String>, CommandError> {
+    log::info!("find_conversion_path: {} -> {}", from_currency, to_currency);
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    let from = fetch_currency(&creds, &from_currency).await?;
+    let to = fetch_currency(&creds, &to_currency).await?;
+    if from.currencyid == to.currencyid {
+        return Err(CommandError::invalid_argument(format!("{} and {} are the same currency", from_currency, to_currency)));
+    }
+
+    let from_neighbors = conversion_neighbors(&creds, &from).await;
+    if from_neighbors.contains_key(&to.currencyid) {
+        log::info!("find_conversion_path: {} -> {} converts directly", from_currency, to_currency);
+        return Ok(Vec::new());
+    }
+    let to_neighbors = conversion_neighbors(&creds, &to).await;
+    if to_neighbors.contains_key(&from.currencyid) {
+        log::info!("find_conversion_path: {} -> {} converts directly", from_currency, to_currency);
+        return Ok(Vec::new());
+    }
+
+    let endpoints = [from.currencyid.as_str(), to.currencyid.as_str()];
+    if let Some((via, _)) = best_common_neighbor(&from_neighbors, &to_neighbors, &endpoints) {
+        log::info!("find_conversion_path: {} -> {} via {}", from_currency, to_currency, via);
+        return Ok(vec![via]);
+    }
+
+    // Two intermediates: explore the most liquid first hops
+    let mut candidates: Vec<(&String, &(String, f64))> = from_neighbors
+        .iter()
+        .filter(|(id, _)| !endpoints.contains(&id.as_str()))
+        .collect();
+    candidates.sort_by(|a, b| b.1 .1.partial_cmp(&a.1 .1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut best: Option<(Vec<String>, f64)> = None;
+    for (first_id, (first_name, first_liquidity)) in candidates.into_iter().take(MAX_PATH_CANDIDATES) {
+        let first = match fetch_currency(&creds, first_id).await {
+            Ok(currency) => currency,
+            Err(e) => {
+                log::warn!("find_conversion_path: skipping {}: {}", first_name, e);
+                continue;
+            }
+        };
+        let first_neighbors = conversion_neighbors(&creds, &first).await;
+        let exclude = [endpoints[0], endpoints[1], first_id.as_str()];
+        if let Some((second_name, liquidity)) = best_common_neighbor(&first_neighbors, &to_neighbors, &exclude) {
+            let score = liquidity.min(*first_liquidity);
+            let improves = match &best {
+                Some((_, best_score)) => score > *best_score,
+                None => true,
+            };
+            if improves {
+                best = Some((vec![first_name.clone(), second_name], score));
+            }
+        }
+    }
+
+    match best {
+        Some((path, _)) => {
+            log::info!("find_conversion_path: {} -> {} via {:?}", from_currency, to_currency, path);
+            Ok(path)
+        }
+        None => Err(CommandError::new(VerusRpcError::NotFound.code(), format!(
+            "No conversion path from {} to {} within {} intermediate currencies",
+            from_currency, to_currency, MAX_CONVERSION_HOPS
+        ))),
+    }
+}
+
+// Tauri command to get the reserve basket of a currency with human-readable names
+#[tauri::command]
+pub async fn get_currency_reserves(
+    app: tauri::AppHandle,
+    currencyname: String,
+) -> Result<Vec<ReserveBalance>, CommandError> {
+    log::info!("Getting reserve composition for: {}", currencyname);
+    
+    // Load credentials
+    let creds = crate::credentials::load_credentials(app).await?;
+    
+    let currency_details = fetch_currency(&creds, &currencyname).await?;
+    
+    // Non-basket currencies simply have no reserves
+    let reserves = currency_details
+        .bestcurrencystate
+        .and_then(|state| state.reservecurrencies)
+        .unwrap_or_default();
+    let currency_names = currency_details.currencynames.unwrap_or_default();
+    
+    let balances: Vec<ReserveBalance> = reserves
+        .into_iter()
+        .map(|reserve| ReserveBalance {
+            // Same resolution as resolve_namespace_fee_currency: currencynames map, else the raw ID
+            name: currency_names
+                .get(&reserve.currencyid)
+                .cloned()
+                .unwrap_or_else(|| reserve.currencyid.clone()),
+            currency_id: reserve.currencyid,
+            weight: reserve.weight,
+            reserves: reserve.reserves,
+            price_in_reserve: reserve.priceinreserve,
+        })
+        .collect();
+    
+    log::debug!("Currency {} has {} reserve currencies", currencyname, balances.len());
+    Ok(balances)
+}
+
+// Tauri command to estimate the full cost of registering a VerusID under a namespace
+#[tauri::command]
+pub async fn estimate_registration_cost(
+    app: tauri::AppHandle,
+    namespace: String,
+    referral: Option<String>,
+    pay_currency: String,
+) -> Result<RegistrationCostEstimate, CommandError> {
+    log::info!("Estimating registration cost under {} (referral: {:?}, paying in {})", namespace, referral, pay_currency);
+    
+    // Load credentials
+    let creds = crate::credentials::load_credentials(app).await?;
+    
+    let currency_details = fetch_currency(&creds, &namespace).await?;
+    
+    // Fee currency: a reserve of the basket when idimportfees encodes an index, else the namespace itself
+    let fee_currency = reserve_index_from_import_fee(currency_details.idimportfees)
+        .and_then(|index| {
+            let reserves = currency_details.bestcurrencystate.as_ref()?.reservecurrencies.as_ref()?;
+            let reserve_id = &reserves.get(index)?.currencyid;
+            currency_details.currencynames.as_ref()?.get(reserve_id).cloned()
+        })
+        .unwrap_or_else(|| currency_details.name.clone());
+    
+    let base_fee = currency_details.idregistrationfees;
+    let has_referral = referral.as_deref().is_some_and(|r| !r.trim().is_empty());
+    let discount = if has_referral {
+        referral_discount(base_fee, currency_details.idreferrallevels.unwrap_or(0))
+    } else {
+        0.0
+    };
+    let net_fee = base_fee - discount;
+    
+    // Convert only when paying in a different currency than the fee is charged in
+    let converted_amount = if pay_currency.eq_ignore_ascii_case(&fee_currency) {
+        net_fee
+    } else {
+        let via = if !namespace.eq_ignore_ascii_case(&pay_currency) && !namespace.eq_ignore_ascii_case(&fee_currency) {
+            Some(namespace.clone())
+        } else {
+            None
+        };
+        let request = EstimateConversionRequest {
+            currency: pay_currency.clone(),
+            convertto: fee_currency.clone(),
+            via,
+            amount: net_fee,
+        };
+        estimate_required_input(creds.rpc_user.clone(), creds.rpc_pass.clone(), creds.rpc_host.clone(), creds.rpc_port, request)
+            .await
+            .map_err(|e| CommandError::with_context(format!("Failed to estimate conversion from {} to {}", pay_currency, fee_currency), e))?
+            .required_input
+    };
+    
+    log::info!(
+        "Registration cost: base {} - discount {} = {} {} (~{} {})",
+        base_fee, discount, net_fee, fee_currency, converted_amount, pay_currency
+    );
+    
+    Ok(RegistrationCostEstimate {
+        base_fee,
+        referral_discount: discount,
+        net_fee,
+        converted_amount,
+        fee_currency,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve_index_accepts_exact_indices() {
+        for index in 0..=9usize {
+            let idimportfees = index as f64 / SATS_PER_COIN;
+            assert_eq!(reserve_index_from_import_fee(idimportfees), Some(index), "{}", idimportfees);
+        }
+        assert_eq!(reserve_index_from_import_fee(0.00000003), Some(3));
+        assert_eq!(reserve_index_from_import_fee(0.00000009), Some(9));
+    }
+
+    #[test]
+    fn reserve_index_tolerates_float_drift() {
+        assert_eq!(reserve_index_from_import_fee(2.9999999e-8), Some(3));
+        assert_eq!(reserve_index_from_import_fee(3.0000001e-8), Some(3));
+        assert_eq!(reserve_index_from_import_fee(8.9999999e-8), Some(9));
+        assert_eq!(reserve_index_from_import_fee(1e-16), Some(0));
+    }
+
+    #[test]
+    fn reserve_index_rejects_ten_satoshis_and_up() {
+        assert_eq!(reserve_index_from_import_fee(0.0000001), None);
+        assert_eq!(reserve_index_from_import_fee(9.9999999e-8), None);
+        assert_eq!(reserve_index_from_import_fee(0.01), None);
+        assert_eq!(reserve_index_from_import_fee(100.0), None);
+    }
+
+    #[test]
+    fn reserve_index_rejects_fractional_satoshis() {
+        assert_eq!(reserve_index_from_import_fee(1.5e-8), None);
+        assert_eq!(reserve_index_from_import_fee(2.5e-9), None);
+        assert_eq!(reserve_index_from_import_fee(3.01e-8), None);
+    }
+
+    #[test]
+    fn reserve_index_rejects_negative_and_non_finite() {
+        assert_eq!(reserve_index_from_import_fee(-0.00000001), None);
+        assert_eq!(reserve_index_from_import_fee(-3.0), None);
+        assert_eq!(reserve_index_from_import_fee(f64::NAN), None);
+        assert_eq!(reserve_index_from_import_fee(f64::INFINITY), None);
+        assert_eq!(reserve_index_from_import_fee(f64::NEG_INFINITY), None);
+    }
+}
@@ -0,0 +1,187 @@
+// File: src-tauri/src/price_history.rs
+// Description: Local price history for currency pairs, sampled from estimateconversion.
+// Changes:
+// - Created file with sample_price and get_price_history commands backed by price_history.json in the
+//   app data dir. Each pair keeps at most MAX_POINTS_PER_PAIR points (oldest dropped first) and at most
+//   MAX_PAIRS pairs are tracked (the least recently sampled pair is dropped).
+// - Commands return CommandError (RPC failures keep their VerusRpcError code); history file work runs on
+//   the blocking pool
+
+use crate::wallet_rpc::{estimate_conversion, EstimateConversionRequest};
+use crate::CommandError;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, Runtime};
+
+// File name inside the app data dir
+const HISTORY_FILE: &str = "price_history.json";
+
+// Ring buffer size per pair (e.g. ~3.5 days of 5-minute samples)
+const MAX_POINTS_PER_PAIR: usize = 1000;
+
+// Number of pairs kept
+const MAX_PAIRS: usize = 50;
+
+// Serializes read-modify-write cycles so concurrent samples don't drop points
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PricePoint {
+    pub timestamp: u64, // Unix seconds
+    pub price: f64,     // Amount of convertto received for 1 unit of currency
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct PriceHistory {
+    #[serde(default)]
+    pairs: HashMap<String, VecDeque<PricePoint>>,
+}
+
+#[derive(Debug, thiserror::Error, Serialize)]
+pub enum PriceHistoryError {
+    #[error("Could not resolve app data directory: {0}")]
+    Path(String),
+    #[error("I/O error: {0}")]
+    Io(String),
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+}
+
+impl From<std::io::Error> for PriceHistoryError {
+    fn from(error: std::io::Error) -> Self {
+        PriceHistoryError::Io(error.to_string())
+    }
+}
+
+// --- Helper Functions ---
+
+fn pair_key(currency: &str, convertto: &str) -> String {
+    format!("{}->{}", currency.to_lowercase(), convertto.to_lowercase())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn history_file_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, PriceHistoryError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| PriceHistoryError::Path(e.to_string()))?;
+    Ok(dir.join(HISTORY_FILE))
+}
+
+fn read_history(path: &Path) -> Result<PriceHistory, PriceHistoryError> {
+    if !path.exists() {
+        return Ok(PriceHistory::default());
+    }
+    let contents = fs::read_to_string(path)?;
+    serde_json::from_str(&contents).map_err(|e| PriceHistoryError::Serialization(e.to_string()))
+}
+
+// Write via temp file + rename (atomic on the same filesystem)
+fn write_history(path: &Path, history: &PriceHistory) -> Result<(), PriceHistoryError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents = serde_json::to_string(history).map_err(|e| PriceHistoryError::Serialization(e.to_string()))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn record_point(history: &mut PriceHistory, key: String, point: PricePoint) {
+    let points = history.pairs.entry(key.clone()).or_default();
+    points.push_back(point);
+    while points.len() > MAX_POINTS_PER_PAIR {
+        points.pop_front();
+    }
+
+    while history.pairs.len() > MAX_PAIRS {
+        let stalest = history
+            .pairs
+            .iter()
+            .filter(|(k, _)| **k != key)
+            .min_by_key(|(_, points)| points.back().map(|p| p.timestamp).unwrap_or(0))
+            .map(|(k, _)| k.clone());
+        match stalest {
+            Some(stalest) => {
+                log::debug!("Dropping price history for {}", stalest);
+                history.pairs.remove(&stalest);
+            }
+            None => break,
+        }
+    }
+}
+
+// History file work runs on the blocking pool: HISTORY_LOCK is a std Mutex held across the read and write
+async fn run_blocking<T: Send + 'static>(
+    work: impl FnOnce() -> Result<T, PriceHistoryError> + Send + 'static,
+) -> Result<T, PriceHistoryError> {
+    tauri::async_runtime::spawn_blocking(work)
+        .await
+        .map_err(|e| PriceHistoryError::Io(format!("price history task failed: {}", e)))?
+}
+
+// --- Tauri Commands ---
+
+/// Record the current price of 1 currency in convertto (estimateconversion)
+#[tauri::command]
+pub async fn sample_price<R: Runtime>(
+    app: AppHandle<R>,
+    currency: String,
+    convertto: String,
+) -> Result<PricePoint, CommandError> {
+    let creds = crate::credentials::load_credentials(app.clone()).await?;
+
+    let request = EstimateConversionRequest {
+        currency: currency.clone(),
+        convertto: convertto.clone(),
+        via: None,
+        amount: 1.0,
+    };
+    let price = estimate_conversion(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, request)
+        .await
+        .map_err(CommandError::from)?;
+
+    let point = PricePoint { timestamp: now_secs(), price };
+    log::debug!("Price sample {} -> {}: {}", currency, convertto, price);
+
+    let key = pair_key(&currency, &convertto);
+    let recorded = point.clone();
+    run_blocking(move || {
+        let _guard = HISTORY_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let path = history_file_path(&app)?;
+        let mut history = read_history(&path)?;
+        record_point(&mut history, key, recorded);
+        write_history(&path, &history)
+    })
+    .await?;
+
+    Ok(point)
+}
+
+/// Recorded prices for a pair, oldest first; since (Unix seconds) filters out older points
+#[tauri::command]
+pub async fn get_price_history<R: Runtime>(
+    app: AppHandle<R>,
+    currency: String,
+    convertto: String,
+    since: Option<u64>,
+) -> Result<Vec<PricePoint>, CommandError> {
+    let key = pair_key(&currency, &convertto);
+    let since = since.unwrap_or(0);
+    let points = run_blocking(move || {
+        let _guard = HISTORY_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let path = history_file_path(&app)?;
+        let mut history = read_history(&path)?;
+        Ok(history.pairs.remove(&key).unwrap_or_default())
+    })
+    .await?;
+    Ok(points.into_iter().filter(|point| point.timestamp >= since).collect())
+}
@@ -4,10 +4,106 @@
 // - Moved RpcResponse, RpcError, VerusRpcError, and make_rpc_call from verus_rpc.rs.
 // - Added SignatureResponse struct for signmessage API response
 // - Added signature verification specific error handling
+// - Added a shared, lazily-initialized reqwest::Client (connection pooling + keep-alive) used by all RPC calls
+// - Added rpc_host parameter to make_rpc_call so the daemon no longer has to run on localhost
+// - Added opt-in HTTPS/TLS endpoints (per host:port transport options) and a Tls error variant
+// - Cookie-authenticated endpoints re-read the .cookie file and retry once on auth failure (cookie may have rotated)
+// - Added make_rpc_call_retry: exponential backoff with jitter for transient failures (transport errors, -28 loading)
+// - Added make_rpc_call_with_timeout for per-call request timeouts (make_rpc_call defaults to 30s)
+// - Added make_rpc_batch: several JSON-RPC calls in one HTTP request, results demultiplexed by id
+// - Added ConnectionRefused / Unauthorized error variants (daemon not running vs wrong credentials)
+// - Added SlippageExceeded error variant (fresh conversion estimate below the caller's minimum)
+// - Added FeeTooLow error variant (custom send fee below the wallet's paytxfee)
+// - Added ReferralNotFound / ReferralNamespaceMismatch error variants (registration referral pre-check)
+// - Added SignerNotInWallet error variant; sign_message maps missing-key RPC errors to it
+// - Added WalletLocked error variant (RPC code -13); JSON-RPC errors in HTTP 500 bodies are now decoded
+// - A null result decodes into T when T allows it (walletpassphrase/walletlock return null)
+// - Params of secret-carrying methods (walletpassphrase, key imports) are never logged
+// - Rotated cookie credentials are registered with log_redaction before use
+// - Added OperationFailed error variant (z_sendmany operation ended as failed/cancelled)
+// - Added SweepAmountTooLow error variant (sweep_to_address balance doesn't exceed the fee)
+// - Added VerusRpcError::code (machine-readable codes for CommandError)
+// - Added InsufficientFunds error variant (pre-flight balance check before a send or conversion)
+// - Added InvalidArgument error variant (request rejected before any RPC, e.g. a bad split_utxos count)
+// - Added an optional per-endpoint token-bucket rate limit (EndpointOptions::max_rpc_per_sec), applied to
+//   make_rpc_call / make_rpc_batch before each request
+// - JSON-RPC code -32601 (method not found) maps to MethodUnsupported { method } (daemon too old for the feature)
+// - Added NotFound / IneligibleNoPrivateAddress so identity checks can tell "no such identity" from
+//   "can't receive private messages"; NotFoundOrIneligible stays as the catch-all
+// - Added AddressIndexDisabled error variant (getaddress* RPCs on a daemon started without -addressindex)
+// - Added WrongPassphrase / WalletNotEncrypted (walletpassphrase / walletlock), passed through unprefixed
+// - is_tls_error matches the native-tls error type in the source chain instead of message substrings
+// - A null JSON-RPC result is decoded as the expected type (single calls and batch entries) instead of failing with Format
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+// Default RPC host used when none is configured (e.g. credentials saved by older versions)
+pub const DEFAULT_RPC_HOST: &str = "127.0.0.1";
+
+// Default request timeout for make_rpc_call
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Short per-attempt timeout for polling helpers (wait_for_*) so one slow poll can't eat the whole budget
+pub const POLL_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Connect timeout for the shared client so a dead daemon fails fast
+const CONNECT_TIMEOUT_SECS: u64 = 5;
+
+// How long an idle pooled connection is kept alive before being dropped
+const POOL_IDLE_TIMEOUT_SECS: u64 = 90;
+
+// Shared HTTP client - reused across all RPC calls so connections are pooled
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+// Separate shared client that accepts invalid/self-signed TLS certificates (opt-in only)
+static INSECURE_HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+// Transport options registered per "host:port" endpoint
+static ENDPOINT_OPTIONS: OnceLock<RwLock<HashMap<String, EndpointOptions>>> = OnceLock::new();
+
+// Token buckets for rate-limited endpoints, keyed by "host:port"
+static RATE_LIMITERS: OnceLock<Mutex<HashMap<String, TokenBucket>>> = OnceLock::new();
+
+// Transport options for a single RPC endpoint
+#[derive(Debug, Clone, Default)]
+pub struct EndpointOptions {
+    pub use_tls: bool,              // Use https:// instead of http://
+    pub accept_invalid_certs: bool, // Accept self-signed/invalid certificates (only with use_tls)
+    pub cookie_path: Option<PathBuf>, // .cookie file to re-read on auth failure (cookie auth only)
+    pub max_rpc_per_sec: Option<u32>, // Client-side request rate limit (None = unlimited)
+}
+
+// Refilled lazily on each acquire; holds at most one second's worth of tokens, so bursts are capped at the rate
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32) -> Self {
+        TokenBucket { rate: rate as f64, tokens: rate as f64, refilled_at: Instant::now() }
+    }
+
+    // Take `cost` tokens, or return how long to wait until they'd be available
+    fn try_take(&mut self, cost: f64) -> Result<(), Duration> {
+        let now = Instant::now();
+        self.tokens = (self.tokens + now.duration_since(self.refilled_at).as_secs_f64() * self.rate).min(self.rate);
+        self.refilled_at = now;
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((cost - self.tokens) / self.rate))
+        }
+    }
+}
 
 // Define structs for the JSON-RPC request and response
 #[derive(Deserialize, Debug)]
@@ -45,12 +141,133 @@ pub enum VerusRpcError {
     Format,
     #[error("Identity not found or cannot receive private messages")]
     NotFoundOrIneligible, // Keep this here as it's a general RPC outcome
+    #[error("Identity not found")]
+    NotFound,
+    #[error("Identity has no private address and cannot receive private messages")]
+    IneligibleNoPrivateAddress,
     #[error("Invalid VerusID format")]
     InvalidFormat, // Keep this here as it's a general RPC outcome
     #[error("Message signing failed")]
     SigningFailed,
     #[error("Message verification failed")]
     VerificationFailed,
+    #[error("TLS error: {0}")]
+    Tls(String),
+    #[error("Connection refused - is the daemon running?")]
+    ConnectionRefused,
+    #[error("Authentication failed - check RPC username and password")]
+    Unauthorized,
+    #[error("Slippage exceeded: estimated output {estimated} is below the minimum of {min_output}")]
+    SlippageExceeded { estimated: f64, min_output: f64 },
+    #[error("Fee {fee} is below the wallet minimum of {minimum}")]
+    FeeTooLow { fee: f64, minimum: f64 },
+    #[error("Referral identity {0} does not exist")]
+    ReferralNotFound(String),
+    #[error("Referral identity {referral} is not in the {namespace} namespace")]
+    ReferralNamespaceMismatch { referral: String, namespace: String },
+    #[error("{0} is not controlled by this wallet")]
+    SignerNotInWallet(String),
+    #[error("Wallet is locked - unlock it with the wallet passphrase")]
+    WalletLocked,
+    #[error("Operation failed ({code}): {message}")]
+    OperationFailed { code: i32, message: String },
+    #[error("Nothing to sweep: available balance {available} does not cover the fee {fee}")]
+    SweepAmountTooLow { available: f64, fee: f64 },
+    #[error("Insufficient funds: {available} available, {required} required (including fee)")]
+    InsufficientFunds { available: f64, required: f64 },
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("Your Verus daemon is too old for this feature ({method} is not supported) - please update it")]
+    MethodUnsupported { method: String },
+    #[error("The daemon's address index is disabled - add addressindex=1 to its config and restart it with -reindex")]
+    AddressIndexDisabled,
+    #[error("Incorrect wallet passphrase")]
+    WrongPassphrase,
+    #[error("Wallet is not encrypted")]
+    WalletNotEncrypted,
+}
+
+impl VerusRpcError {
+    /// Stable, machine-readable code for the frontend to branch on (see CommandError)
+    pub fn code(&self) -> &'static str {
+        match self {
+            VerusRpcError::NetworkError(_) => "NETWORK",
+            VerusRpcError::Rpc { .. } => "RPC",
+            VerusRpcError::ParseError(_) => "PARSE_ERROR",
+            VerusRpcError::Timeout => "TIMEOUT",
+            VerusRpcError::Format => "BAD_RESPONSE",
+            VerusRpcError::NotFoundOrIneligible => "NOT_FOUND",
+            VerusRpcError::NotFound => "IDENTITY_NOT_FOUND",
+            VerusRpcError::IneligibleNoPrivateAddress => "NO_PRIVATE_ADDRESS",
+            VerusRpcError::InvalidFormat => "INVALID_FORMAT",
+            VerusRpcError::SigningFailed => "SIGNING_FAILED",
+            VerusRpcError::VerificationFailed => "VERIFICATION_FAILED",
+            VerusRpcError::Tls(_) => "TLS",
+            VerusRpcError::ConnectionRefused => "CONNECTION_REFUSED",
+            VerusRpcError::Unauthorized => "UNAUTHORIZED",
+            VerusRpcError::SlippageExceeded { .. } => "SLIPPAGE_EXCEEDED",
+            VerusRpcError::FeeTooLow { .. } => "FEE_TOO_LOW",
+            VerusRpcError::ReferralNotFound(_) => "REFERRAL_NOT_FOUND",
+            VerusRpcError::ReferralNamespaceMismatch { .. } => "REFERRAL_NAMESPACE_MISMATCH",
+            VerusRpcError::SignerNotInWallet(_) => "SIGNER_NOT_IN_WALLET",
+            VerusRpcError::WalletLocked => "WALLET_LOCKED",
+            VerusRpcError::OperationFailed { .. } => "OPERATION_FAILED",
+            VerusRpcError::SweepAmountTooLow { .. } => "AMOUNT_TOO_LOW",
+            VerusRpcError::InsufficientFunds { .. } => "INSUFFICIENT_FUNDS",
+            VerusRpcError::InvalidArgument(_) => "INVALID_ARGUMENT",
+            VerusRpcError::MethodUnsupported { .. } => "METHOD_UNSUPPORTED",
+            VerusRpcError::AddressIndexDisabled => "ADDRESS_INDEX_DISABLED",
+            VerusRpcError::WrongPassphrase => "WRONG_PASSPHRASE",
+            VerusRpcError::WalletNotEncrypted => "WALLET_NOT_ENCRYPTED",
+        }
+    }
+}
+
+// RPC_WALLET_UNLOCK_NEEDED: the wallet is encrypted and currently locked
+pub const RPC_WALLET_UNLOCK_NEEDED: i32 = -13;
+
+// RPC_METHOD_NOT_FOUND: returned by daemons that predate an RPC
+pub const RPC_METHOD_NOT_FOUND: i32 = -32601;
+
+// Methods whose params carry secrets (passphrases, private keys) and must never be logged
+const SECRET_PARAM_METHODS: &[&str] = &["walletpassphrase", "walletpassphrasechange", "encryptwallet", "importprivkey", "z_importkey"];
+
+// Error string for String-returning commands. A locked wallet is reported bare (the WalletLocked
+// message) so the frontend can recognise it and prompt for the passphrase; so is an unsupported
+// method, whose message already names it.
+pub fn rpc_failure_message(method: &str, error: &VerusRpcError) -> String {
+    match error {
+        VerusRpcError::WalletLocked
+        | VerusRpcError::MethodUnsupported { .. }
+        | VerusRpcError::WrongPassphrase
+        | VerusRpcError::WalletNotEncrypted => error.to_string(),
+        other => format!("{} failed: {}", method, other),
+    }
+}
+
+// Build the error for a JSON-RPC error object, mapping codes the UI handles specially
+fn rpc_error(method: &str, code: i32, message: String) -> VerusRpcError {
+    match code {
+        RPC_WALLET_UNLOCK_NEEDED => VerusRpcError::WalletLocked,
+        RPC_METHOD_NOT_FOUND => {
+            log::warn!("Daemon does not support {} ({})", method, message);
+            VerusRpcError::MethodUnsupported { method: method.to_string() }
+        }
+        _ => VerusRpcError::Rpc { code, message },
+    }
+}
+
+// Check whether a reqwest error was caused by a TLS handshake/certificate problem.
+// reqwest doesn't expose this directly, so look for the TLS backend's (native-tls) error in the source chain.
+fn is_tls_error(err: &reqwest::Error) -> bool {
+    let mut source: Option<&dyn std::error::Error> = Some(err);
+    while let Some(e) = source {
+        if e.is::<native_tls::Error>() {
+            return true;
+        }
+        source = e.source();
+    }
+    false
 }
 
 // Convert reqwest::Error to String for serialization
@@ -58,7 +275,13 @@ impl From<reqwest::Error> for VerusRpcError {
     fn from(err: reqwest::Error) -> Self {
         if err.is_timeout() {
             VerusRpcError::Timeout
-        } else if err.is_connect() || err.is_request() {
+        } else if is_tls_error(&err) {
+            VerusRpcError::Tls(err.to_string())
+        } else if err.is_connect() {
+            VerusRpcError::ConnectionRefused
+        } else if err.status() == Some(reqwest::StatusCode::UNAUTHORIZED) {
+            VerusRpcError::Unauthorized
+        } else if err.is_request() {
             VerusRpcError::NetworkError(err.to_string())
         } else {
             VerusRpcError::ParseError(err.to_string())
@@ -66,16 +289,177 @@ impl From<reqwest::Error> for VerusRpcError {
     }
 }
 
-// Helper function for generic RPC calls
+fn build_http_client(accept_invalid_certs: bool) -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+        .pool_idle_timeout(Duration::from_secs(POOL_IDLE_TIMEOUT_SECS))
+        .pool_max_idle_per_host(16)
+        .tcp_keepalive(Duration::from_secs(60))
+        .danger_accept_invalid_certs(accept_invalid_certs)
+        .build()
+        .unwrap_or_else(|e| {
+            log::error!("Failed to build pooled HTTP client, falling back to default: {}", e);
+            reqwest::Client::new()
+        })
+}
+
+// Get the shared HTTP client, building it on first use
+pub fn http_client() -> &'static reqwest::Client {
+    HTTP_CLIENT.get_or_init(|| {
+        log::debug!("Initializing shared RPC HTTP client");
+        build_http_client(false)
+    })
+}
+
+// Get the shared client matching the endpoint options.
+// SECURITY: accepting invalid certificates disables server authentication entirely, so anyone
+// able to intercept the connection could read the RPC credentials. It is only used when the
+// user explicitly opted in for a TLS endpoint (e.g. a self-signed nginx proxy on their LAN).
+pub fn http_client_for(options: &EndpointOptions) -> &'static reqwest::Client {
+    if options.use_tls && options.accept_invalid_certs {
+        INSECURE_HTTP_CLIENT.get_or_init(|| {
+            log::warn!("Initializing RPC HTTP client that accepts invalid TLS certificates");
+            build_http_client(true)
+        })
+    } else {
+        http_client()
+    }
+}
+
+fn endpoint_key(rpc_host: &str, rpc_port: u16) -> String {
+    format!("{}:{}", rpc_host, rpc_port)
+}
+
+// Register transport options for an endpoint (called whenever credentials are loaded or saved)
+pub fn set_endpoint_options(rpc_host: &str, rpc_port: u16, options: EndpointOptions) {
+    let key = endpoint_key(rpc_host, rpc_port);
+    {
+        let mut limiters = RATE_LIMITERS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match options.max_rpc_per_sec.filter(|&rate| rate > 0) {
+            // Keep the existing bucket (and its remaining tokens) if the rate didn't change
+            Some(rate) if limiters.get(&key).is_some_and(|bucket| bucket.rate == rate as f64) => {}
+            Some(rate) => {
+                log::info!("Limiting RPC to {} calls/s for {}", rate, key);
+                limiters.insert(key.clone(), TokenBucket::new(rate));
+            }
+            None => {
+                limiters.remove(&key);
+            }
+        }
+    }
+    let registry = ENDPOINT_OPTIONS.get_or_init(|| RwLock::new(HashMap::new()));
+    if let Ok(mut map) = registry.write() {
+        map.insert(key, options);
+    }
+}
+
+// Wait until the endpoint's rate limit allows `calls` more requests (returns immediately when unlimited).
+// A batch costs one token per call, capped at the bucket size so a large batch can't wait forever.
+async fn acquire_rate_limit(rpc_host: &str, rpc_port: u16, calls: usize) {
+    let Some(limiters) = RATE_LIMITERS.get() else {
+        return;
+    };
+    let key = endpoint_key(rpc_host, rpc_port);
+    loop {
+        let wait = {
+            let mut limiters = limiters.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let Some(bucket) = limiters.get_mut(&key) else {
+                return;
+            };
+            match bucket.try_take((calls as f64).min(bucket.rate)) {
+                Ok(()) => return,
+                Err(wait) => wait,
+            }
+        };
+        log::debug!("RPC rate limit reached for {}, waiting {:?}", key, wait);
+        tokio::time::sleep(wait).await;
+    }
+}
+
+// Look up transport options for an endpoint (plain HTTP if nothing was registered)
+pub fn endpoint_options(rpc_host: &str, rpc_port: u16) -> EndpointOptions {
+    ENDPOINT_OPTIONS
+        .get()
+        .and_then(|registry| registry.read().ok())
+        .and_then(|map| map.get(&endpoint_key(rpc_host, rpc_port)).cloned())
+        .unwrap_or_default()
+}
+
+// Build the RPC URL for an endpoint, using https:// only when TLS was opted in
+pub fn build_rpc_url(rpc_host: &str, rpc_port: u16, use_tls: bool) -> String {
+    let scheme = if use_tls { "https" } else { "http" };
+    format!("{}://{}:{}", scheme, rpc_host, rpc_port)
+}
+
+// Errors that may mean the .cookie credentials rotated underneath us
+fn is_possible_cookie_rotation(error: &VerusRpcError) -> bool {
+    matches!(error, VerusRpcError::Unauthorized | VerusRpcError::Rpc { code: -1, .. })
+}
+
+// Helper function for generic RPC calls (uses DEFAULT_RPC_TIMEOUT)
 pub async fn make_rpc_call<T: for<'de> Deserialize<'de>>(
     rpc_user: &str,
     rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    method: &str,
+    params: Vec<Value>,
+) -> Result<T, VerusRpcError> {
+    make_rpc_call_with_timeout(rpc_user, rpc_pass, rpc_host, rpc_port, method, params, DEFAULT_RPC_TIMEOUT).await
+}
+
+// Generic RPC call with an explicit request timeout; an elapsed timeout maps to VerusRpcError::Timeout
+pub async fn make_rpc_call_with_timeout<T: for<'de> Deserialize<'de>>(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
     rpc_port: u16,
     method: &str,
     params: Vec<Value>,
+    timeout: Duration,
 ) -> Result<T, VerusRpcError> {
-    let client = reqwest::Client::new();
-    let rpc_url = format!("http://localhost:{}", rpc_port);
+    let options = endpoint_options(rpc_host, rpc_port);
+    let rpc_url = build_rpc_url(rpc_host, rpc_port, options.use_tls);
+    acquire_rate_limit(rpc_host, rpc_port, 1).await;
+
+    match send_rpc_request(&options, &rpc_url, rpc_user, rpc_pass, method, &params, timeout).await {
+        Err(e) if is_possible_cookie_rotation(&e) => {
+            // The daemon rewrites .cookie on every restart - re-read it and retry once,
+            // but only if the cookie actually changed (never blindly re-send a request).
+            let Some(cookie_path) = options.cookie_path.as_ref() else {
+                return Err(e);
+            };
+            match crate::credentials::read_cookie_file(cookie_path) {
+                Ok((user, pass)) if user != rpc_user || pass != rpc_pass => {
+                    crate::log_redaction::register_credentials(&user, &pass);
+                    log::info!("RPC cookie rotated, retrying {} with refreshed cookie credentials", method);
+                    send_rpc_request(&options, &rpc_url, &user, &pass, method, &params, timeout).await
+                }
+                Ok(_) => Err(e),
+                Err(read_err) => {
+                    log::warn!("Failed to re-read RPC cookie file {:?}: {}", cookie_path, read_err);
+                    Err(e)
+                }
+            }
+        }
+        result => result,
+    }
+}
+
+// Send a single JSON-RPC request and decode the result
+async fn send_rpc_request<T: for<'de> Deserialize<'de>>(
+    options: &EndpointOptions,
+    rpc_url: &str,
+    rpc_user: &str,
+    rpc_pass: &str,
+    method: &str,
+    params: &[Value],
+    timeout: Duration,
+) -> Result<T, VerusRpcError> {
+    let client = http_client_for(options);
 
     let request_body = json!({
         "jsonrpc": "1.0",
@@ -84,19 +468,31 @@ pub async fn make_rpc_call<T: for<'de> Deserialize<'de>>(
         "params": params
     });
 
-    log::debug!("Making RPC call: method={}, params={:?}", method, params);
+    if SECRET_PARAM_METHODS.contains(&method) {
+        log::debug!("Making RPC call: method={}, params=<redacted>", method);
+    } else {
+        log::debug!("Making RPC call: method={}, params={:?}", method, params);
+    }
 
     let request = client
         .post(rpc_url)
         .basic_auth(rpc_user, Some(rpc_pass))
         .header("Content-Type", "application/json")
         .json(&request_body)
-        .timeout(Duration::from_secs(10));
+        .timeout(timeout);
 
     match request.send().await {
         Ok(response) => {
             if response.status() == reqwest::StatusCode::UNAUTHORIZED {
-                return Err(VerusRpcError::Rpc { code: 401, message: "Authentication failed.".to_string() });
+                return Err(VerusRpcError::Unauthorized);
+            }
+            // The daemon reports RPC errors as HTTP 500 with the JSON-RPC error in the body
+            if response.status() == reqwest::StatusCode::INTERNAL_SERVER_ERROR {
+                let status = response.status();
+                return match response.json::<RpcResponse<Value>>().await {
+                    Ok(RpcResponse { error: Some(err), .. }) => Err(rpc_error(method, err.code, err.message)),
+                    _ => Err(VerusRpcError::ParseError(format!("HTTP status server error ({}) for method {}", status, method))),
+                };
             }
             match response.error_for_status() {
                 Ok(successful_response) => {
@@ -105,9 +501,10 @@ pub async fn make_rpc_call<T: for<'de> Deserialize<'de>>(
                             if let Some(result) = rpc_response.result {
                                 Ok(result)
                             } else if let Some(err) = rpc_response.error {
-                                Err(VerusRpcError::Rpc { code: err.code, message: err.message })
+                                Err(rpc_error(method, err.code, err.message))
                             } else {
-                                Err(VerusRpcError::Format)
+                                // A null result is a valid answer for methods like walletlock
+                                decode_null_result(method)
                             }
                         }
                         Err(e) => {
@@ -126,10 +523,208 @@ pub async fn make_rpc_call<T: for<'de> Deserialize<'de>>(
     }
 }
 
+// Decode a null `result` as T: fine for T = () or Option<_>, a ParseError for types that need a value
+fn decode_null_result<T: for<'de> Deserialize<'de>>(method: &str) -> Result<T, VerusRpcError> {
+    serde_json::from_value(Value::Null)
+        .map_err(|e| VerusRpcError::ParseError(format!("{} returned a null result: {}", method, e)))
+}
+
+// Decode a single entry of a JSON-RPC batch response (a null result is passed through like in send_rpc_request)
+fn parse_batch_entry(method: &str, entry: &Value) -> Result<Value, VerusRpcError> {
+    if let Some(err) = entry.get("error").filter(|e| !e.is_null()) {
+        let code = err.get("code").and_then(|c| c.as_i64()).unwrap_or(-1) as i32;
+        let message = err.get("message").and_then(|m| m.as_str()).unwrap_or("Unknown error").to_string();
+        return Err(rpc_error(method, code, message));
+    }
+    match entry.get("result") {
+        Some(result) if !result.is_null() => Ok(result.clone()),
+        Some(_) => decode_null_result(method),
+        None => Err(VerusRpcError::Format),
+    }
+}
+
+// Post a JSON-RPC batch payload and return the raw response entries
+async fn send_rpc_batch_request(
+    options: &EndpointOptions,
+    rpc_url: &str,
+    rpc_user: &str,
+    rpc_pass: &str,
+    payload: &Value,
+) -> Result<Vec<Value>, VerusRpcError> {
+    let response = http_client_for(options)
+        .post(rpc_url)
+        .basic_auth(rpc_user, Some(rpc_pass))
+        .header("Content-Type", "application/json")
+        .json(payload)
+        .timeout(DEFAULT_RPC_TIMEOUT)
+        .send()
+        .await?;
+
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(VerusRpcError::Unauthorized);
+    }
+    // Batch responses are returned with 200 even when individual calls fail
+    let entries = response.error_for_status()?.json::<Vec<Value>>().await?;
+    Ok(entries)
+}
+
+// Send several RPC calls in a single HTTP request (JSON-RPC batch).
+// Results are returned in the same order as `calls`; each item fails independently.
+// A transport-level failure is reported for every item.
+pub async fn make_rpc_batch(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    calls: Vec<(String, Vec<Value>)>,
+) -> Vec<Result<Value, VerusRpcError>> {
+    if calls.is_empty() {
+        return Vec::new();
+    }
+
+    let options = endpoint_options(rpc_host, rpc_port);
+    let rpc_url = build_rpc_url(rpc_host, rpc_port, options.use_tls);
+    acquire_rate_limit(rpc_host, rpc_port, calls.len()).await;
+
+    // Use the index as the id so responses (which may come back in any order) can be matched up
+    let payload = Value::Array(
+        calls
+            .iter()
+            .enumerate()
+            .map(|(index, (method, params))| json!({
+                "jsonrpc": "1.0",
+                "id": index,
+                "method": method,
+                "params": params
+            }))
+            .collect(),
+    );
+
+    log::debug!("Making RPC batch call with {} requests", calls.len());
+
+    let mut entries = send_rpc_batch_request(&options, &rpc_url, rpc_user, rpc_pass, &payload).await;
+    if let (Err(e), Some(cookie_path)) = (&entries, options.cookie_path.as_ref()) {
+        if is_possible_cookie_rotation(e) {
+            if let Ok((user, pass)) = crate::credentials::read_cookie_file(cookie_path) {
+                if user != rpc_user || pass != rpc_pass {
+                    crate::log_redaction::register_credentials(&user, &pass);
+                    log::info!("RPC cookie rotated, retrying batch with refreshed cookie credentials");
+                    entries = send_rpc_batch_request(&options, &rpc_url, &user, &pass, &payload).await;
+                }
+            }
+        }
+    }
+
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::error!("RPC batch request failed: {:?}", e);
+            return calls.iter().map(|_| Err(e.clone())).collect();
+        }
+    };
+
+    // Demultiplex by id
+    let mut results: Vec<Result<Value, VerusRpcError>> = calls.iter().map(|_| Err(VerusRpcError::Format)).collect();
+    for entry in &entries {
+        match entry.get("id").and_then(|id| id.as_u64()).map(|id| id as usize) {
+            Some(index) if index < results.len() => results[index] = parse_batch_entry(&calls[index].0, entry),
+            _ => log::warn!("Ignoring RPC batch entry with unknown id: {:?}", entry.get("id")),
+        }
+    }
+
+    log::debug!(
+        "RPC batch completed: {}/{} succeeded",
+        results.iter().filter(|r| r.is_ok()).count(),
+        results.len()
+    );
+    results
+}
+
+// Retry policy for make_rpc_call_retry
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,     // Retries after the first attempt
+    pub base_delay: Duration, // Delay before the first retry, doubled on each subsequent one
+}
+
+// Default policy for read-only calls during login/namespace loading
+pub const DEFAULT_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_retries: 3,
+    base_delay: Duration::from_millis(250),
+};
+
+// Upper bound for a single backoff delay
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+// Whether an error is transient and worth retrying.
+// Only transport failures and "daemon loading" (-28) qualify; RPC errors such as
+// -5 (not found) are definitive answers and must pass through immediately.
+pub fn is_retryable_error(error: &VerusRpcError) -> bool {
+    match error {
+        VerusRpcError::NetworkError(_) | VerusRpcError::Timeout | VerusRpcError::ConnectionRefused => true,
+        VerusRpcError::Rpc { code, .. } => *code == -28,
+        _ => false,
+    }
+}
+
+// Exponential backoff delay for a retry attempt (1-based), with up to 50% random jitter
+fn backoff_delay(base_delay: Duration, attempt: u32) -> Duration {
+    use std::hash::{BuildHasher, Hasher};
+
+    let exponential = base_delay.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+    let capped = exponential.min(MAX_RETRY_DELAY);
+
+    // RandomState is randomly seeded per instance, which is plenty for jitter
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u32(attempt);
+    let jitter_fraction = (hasher.finish() % 1000) as f64 / 2000.0; // 0.0 - 0.5
+    capped + capped.mul_f64(jitter_fraction)
+}
+
+// make_rpc_call with retries for transient failures.
+// Only use this for read-only/idempotent methods - a timed out z_sendmany may still have been sent.
+pub async fn make_rpc_call_retry<T: for<'de> Deserialize<'de>>(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    method: &str,
+    params: Vec<Value>,
+    policy: RetryPolicy,
+) -> Result<T, VerusRpcError> {
+    let mut attempt: u32 = 0;
+    loop {
+        match make_rpc_call::<T>(rpc_user, rpc_pass, rpc_host, rpc_port, method, params.clone()).await {
+            Ok(result) => {
+                if attempt > 0 {
+                    log::info!("RPC call {} succeeded after {} retr{}", method, attempt, if attempt == 1 { "y" } else { "ies" });
+                }
+                return Ok(result);
+            }
+            Err(e) if is_retryable_error(&e) && attempt < policy.max_retries => {
+                attempt += 1;
+                let delay = backoff_delay(policy.base_delay, attempt);
+                log::warn!(
+                    "RPC call {} failed with transient error ({}), retry {}/{} in {:?}",
+                    method, e, attempt, policy.max_retries, delay
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                if attempt > 0 {
+                    log::error!("RPC call {} failed after {} retries: {}", method, attempt, e);
+                }
+                return Err(e);
+            }
+        }
+    }
+}
+
 // Sign message using Verus signmessage RPC
 pub async fn sign_message(
     rpc_user: &str,
     rpc_pass: &str,
+    rpc_host: &str,
     rpc_port: u16,
     verusid: &str,
     message: &str,
@@ -139,11 +734,15 @@ pub async fn sign_message(
 
     let params = vec![json!(verusid), json!(message)];
     
-    match make_rpc_call::<SignatureResponse>(rpc_user, rpc_pass, rpc_port, "signmessage", params).await {
+    match make_rpc_call::<SignatureResponse>(rpc_user, rpc_pass, rpc_host, rpc_port, "signmessage", params).await {
         Ok(signature_response) => {
             log::info!("Message signed successfully. Hash: {}", signature_response.hash);
             Ok(signature_response)
         }
+        Err(VerusRpcError::Rpc { code, ref message }) if is_missing_signing_key(code, message) => {
+            log::warn!("Cannot sign with {}: wallet has no key for it ({})", verusid, message);
+            Err(VerusRpcError::SignerNotInWallet(verusid.to_string()))
+        }
         Err(e) => {
             log::error!("Failed to sign message: {:?}", e);
             Err(VerusRpcError::SigningFailed)
@@ -151,10 +750,17 @@ pub async fn sign_message(
     }
 }
 
+// signmessage reports a missing key as a wallet error (-4) or with a "private key" message
+fn is_missing_signing_key(code: i32, message: &str) -> bool {
+    let message = message.to_lowercase();
+    code == -4 || message.contains("private key") || message.contains("cannot sign")
+}
+
 // Verify message using Verus verifymessage RPC
 pub async fn verify_message(
     rpc_user: &str,
     rpc_pass: &str,
+    rpc_host: &str,
     rpc_port: u16,
     verusid: &str,
     signature: &str,
@@ -166,7 +772,7 @@ pub async fn verify_message(
 
     let params = vec![json!(verusid), json!(signature), json!(message)];
     
-    match make_rpc_call::<bool>(rpc_user, rpc_pass, rpc_port, "verifymessage", params).await {
+    match make_rpc_call::<bool>(rpc_user, rpc_pass, rpc_host, rpc_port, "verifymessage", params).await {
         Ok(is_valid) => {
             if is_valid {
                 log::debug!("Message signature verified successfully for {}", verusid);
@@ -182,4 +788,28 @@ pub async fn verify_message(
             Ok(false)
         }
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_entry_passes_null_result_through() {
+        let entry = json!({ "id": 0, "result": null, "error": null });
+        assert_eq!(parse_batch_entry("walletlock", &entry).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn batch_entry_without_result_is_a_format_error() {
+        let entry = json!({ "id": 0, "error": null });
+        assert!(matches!(parse_batch_entry("getinfo", &entry), Err(VerusRpcError::Format)));
+    }
+
+    #[test]
+    fn null_result_decodes_for_unit_and_option_only() {
+        assert!(decode_null_result::<()>("walletlock").is_ok());
+        assert_eq!(decode_null_result::<Option<u64>>("getblockcount").unwrap(), None);
+        assert!(matches!(decode_null_result::<u64>("getblockcount"), Err(VerusRpcError::ParseError(_))));
+    }
+}
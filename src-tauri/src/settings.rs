@@ -7,12 +7,14 @@
 // - Added Tauri commands for saving/loading conversations.
 // - Added Tauri commands for saving/loading messages per conversation.
 // - Added Tauri command for deleting chat data.
+// - Commands return CommandError (SettingsError converts to the SETTINGS code).
 
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Runtime};
 use tauri_plugin_store::{StoreExt, Error as StoreError};
 use std::collections::HashMap; // Needed if using HashMap approach later
 use serde_json::json; // Import serde_json macro for json!() usage
+use crate::CommandError;
 
 // Use the same store path as credentials for simplicity, just different keys
 const STORE_PATH: &str = "store.json";
@@ -83,12 +85,12 @@ pub async fn save_persistence_setting<R: Runtime>(
     app: AppHandle<R>,
     identity_i_address: String,
     save_preference: bool,
-) -> Result<(), SettingsError> {
+) -> Result<(), CommandError> {
     log::info!("Saving persistence setting for {}: {}", identity_i_address, save_preference);
-    let store = app.store(STORE_PATH)?;
+    let store = app.store(STORE_PATH).map_err(SettingsError::from)?;
     let key = get_preference_key(&identity_i_address);
     store.set(key, json!(save_preference)); // Use serde_json::json macro
-    store.save()?;
+    store.save().map_err(SettingsError::from)?;
     log::info!("Persistence setting saved successfully.");
     Ok(())
 }
@@ -97,9 +99,9 @@ pub async fn save_persistence_setting<R: Runtime>(
 pub async fn load_persistence_setting<R: Runtime>(
     app: AppHandle<R>,
     identity_i_address: String,
-) -> Result<Option<bool>, SettingsError> {
+) -> Result<Option<bool>, CommandError> {
     log::info!("Loading persistence setting for {}", identity_i_address);
-    let store = app.store(STORE_PATH)?;
+    let store = app.store(STORE_PATH).map_err(SettingsError::from)?;
     let key = get_preference_key(&identity_i_address);
     match store.get(&key) {
         Some(value) => {
@@ -121,14 +123,14 @@ pub async fn save_conversations<R: Runtime>(
     app: AppHandle<R>,
     identity_i_address: String,
     conversations: Vec<Conversation>,
-) -> Result<(), SettingsError> {
+) -> Result<(), CommandError> {
     log::info!("Saving {} conversations for {}", conversations.len(), identity_i_address);
-    let store = app.store(STORE_PATH)?;
+    let store = app.store(STORE_PATH).map_err(SettingsError::from)?;
     let key = get_conversations_key(&identity_i_address);
     let conversations_json = serde_json::to_value(conversations)
         .map_err(|e| SettingsError::Serialization(e.to_string()))?;
     store.set(key, conversations_json);
-    store.save()?;
+    store.save().map_err(SettingsError::from)?;
     log::info!("Conversations saved successfully.");
     Ok(())
 }
@@ -137,15 +139,15 @@ pub async fn save_conversations<R: Runtime>(
 pub async fn load_conversations<R: Runtime>(
     app: AppHandle<R>,
     identity_i_address: String,
-) -> Result<Vec<Conversation>, SettingsError> {
+) -> Result<Vec<Conversation>, CommandError> {
     log::info!("Loading conversations for {}", identity_i_address);
-    let store = app.store(STORE_PATH)?;
+    let store = app.store(STORE_PATH).map_err(SettingsError::from)?;
     let key = get_conversations_key(&identity_i_address);
     match store.get(&key) {
         Some(value) => {
             log::debug!("Found conversations value for {}", identity_i_address);
              serde_json::from_value::<Vec<Conversation>>(value.clone())
-                 .map_err(|e| SettingsError::Deserialization(format!("Failed to parse conversations Vec: {}", e)))
+                 .map_err(|e| SettingsError::Deserialization(format!("Failed to parse conversations Vec: {}", e)).into())
         }
         None => {
             log::info!("No conversations found in store for {}", identity_i_address);
@@ -160,14 +162,14 @@ pub async fn save_messages_for_conversation<R: Runtime>(
     identity_i_address: String,
     conversation_id: String,
     messages: Vec<ChatMessage>,
-) -> Result<(), SettingsError> {
+) -> Result<(), CommandError> {
     log::info!("Saving {} messages for conversation {} (user {})", messages.len(), conversation_id, identity_i_address);
-    let store = app.store(STORE_PATH)?;
+    let store = app.store(STORE_PATH).map_err(SettingsError::from)?;
     let key = get_messages_key(&identity_i_address, &conversation_id);
      let messages_json = serde_json::to_value(messages)
         .map_err(|e| SettingsError::Serialization(e.to_string()))?;
     store.set(key, messages_json);
-    store.save()?;
+    store.save().map_err(SettingsError::from)?;
     log::info!("Messages for conversation {} saved successfully.", conversation_id);
     Ok(())
 }
@@ -177,15 +179,15 @@ pub async fn load_messages_for_conversation<R: Runtime>(
     app: AppHandle<R>,
     identity_i_address: String,
     conversation_id: String,
-) -> Result<Vec<ChatMessage>, SettingsError> {
+) -> Result<Vec<ChatMessage>, CommandError> {
     log::info!("Loading messages for conversation {} (user {})", conversation_id, identity_i_address);
-     let store = app.store(STORE_PATH)?;
+     let store = app.store(STORE_PATH).map_err(SettingsError::from)?;
     let key = get_messages_key(&identity_i_address, &conversation_id);
     match store.get(&key) {
         Some(value) => {
             log::debug!("Found messages value for conversation {}", conversation_id);
              serde_json::from_value::<Vec<ChatMessage>>(value.clone())
-                 .map_err(|e| SettingsError::Deserialization(format!("Failed to parse messages Vec for {}: {}", conversation_id, e)))
+                 .map_err(|e| SettingsError::Deserialization(format!("Failed to parse messages Vec for {}: {}", conversation_id, e)).into())
         }
         None => {
             log::info!("No messages found in store for conversation {}", conversation_id);
@@ -198,9 +200,9 @@ pub async fn load_messages_for_conversation<R: Runtime>(
 pub async fn delete_chat_data<R: Runtime>(
     app: AppHandle<R>,
     identity_i_address: String,
-) -> Result<(), SettingsError> {
+) -> Result<(), CommandError> {
      log::warn!("Attempting to delete ALL chat data (preference, conversations, messages) for identity: {}", identity_i_address);
-    let store = app.store(STORE_PATH)?;
+    let store = app.store(STORE_PATH).map_err(SettingsError::from)?;
 
     let pref_key = get_preference_key(&identity_i_address);
     let convos_key = get_conversations_key(&identity_i_address);
@@ -245,7 +247,7 @@ pub async fn delete_chat_data<R: Runtime>(
     log::info!("Deleted message data for {} conversations.", messages_deleted);
 
     // 5. Save changes to the store file
-    store.save()?;
+    store.save().map_err(SettingsError::from)?;
     log::warn!("Completed deletion of chat data for identity: {}. Store saved.", identity_i_address);
 
     Ok(())
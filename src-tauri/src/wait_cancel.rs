@@ -0,0 +1,224 @@
+// File: src-tauri/src/wait_cancel.rs
+// Description: Shared polling loop and cancellation for the long-running wait_for_* commands.
+// Changes:
+// - Created file: waits register a flag under a frontend-supplied key; cancel_wait flips it and wakes
+//   the sleeping loop, which then returns Ok(false).
+// - Added poll_until (the start/timeout/sleep loop every wait_for_* command shares) and poll_attempt_timeout.
+// - Added tests for poll_until (ready on a later call, timeout, timeout_secs = 0, check error).
+// - poll_until is generic over the check's error type (wait_for_operation surfaces a typed VerusRpcError).
+// - cancel_wait returns CommandError like the other commands.
+// - Added cancel_all_waits for app shutdown: every wait, keyed or not, is cancelled and woken, and waits
+//   started afterwards return immediately.
+
+use crate::rpc_client::POLL_RPC_TIMEOUT;
+use crate::CommandError;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+use tokio::sync::Notify;
+use tokio::time::Duration;
+
+#[derive(Default)]
+struct CancelFlag {
+    cancelled: AtomicBool,
+    wake: Notify,
+}
+
+// Active waits by key
+static ACTIVE_WAITS: Mutex<Option<HashMap<String, Arc<CancelFlag>>>> = Mutex::new(None);
+
+// Set once by cancel_all_waits; cancels every current and future wait
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+// Wakes sleeping waits (including keyless ones) on shutdown
+static SHUTDOWN_WAKE: OnceLock<Notify> = OnceLock::new();
+
+fn shutdown_wake() -> &'static Notify {
+    SHUTDOWN_WAKE.get_or_init(Notify::new)
+}
+
+fn with_waits<T>(f: impl FnOnce(&mut HashMap<String, Arc<CancelFlag>>) -> T) -> T {
+    let mut guard = ACTIVE_WAITS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(guard.get_or_insert_with(HashMap::new))
+}
+
+/// Cancellation handle for one wait loop; deregisters itself when dropped.
+/// A wait without a key can't be cancelled and behaves exactly as before.
+pub struct WaitCancel {
+    key: Option<String>,
+    flag: Arc<CancelFlag>,
+}
+
+impl WaitCancel {
+    pub fn register(key: Option<String>) -> Self {
+        let flag = Arc::new(CancelFlag::default());
+        if let Some(key) = &key {
+            // A new wait under the same key replaces the old one
+            with_waits(|waits| waits.insert(key.clone(), flag.clone()));
+        }
+        WaitCancel { key, flag }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.cancelled.load(Ordering::SeqCst) || SHUTTING_DOWN.load(Ordering::SeqCst)
+    }
+
+    /// Sleep for the poll interval, waking early on cancellation or shutdown. Returns true if cancelled.
+    pub async fn sleep(&self, duration: Duration) -> bool {
+        // Register for the shutdown wake-up before checking the flag so a shutdown in between isn't missed
+        let shutdown = shutdown_wake().notified();
+        tokio::pin!(shutdown);
+        shutdown.as_mut().enable();
+        if self.is_cancelled() {
+            return true;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => {}
+            _ = self.flag.wake.notified() => {}
+            _ = shutdown => {}
+        }
+        self.is_cancelled()
+    }
+}
+
+impl Drop for WaitCancel {
+    fn drop(&mut self) {
+        if let Some(key) = &self.key {
+            with_waits(|waits| {
+                // Only remove our own entry, not a newer wait registered under the same key
+                if waits.get(key).is_some_and(|flag| Arc::ptr_eq(flag, &self.flag)) {
+                    waits.remove(key);
+                }
+            });
+        }
+    }
+}
+
+// Per-attempt timeout for a poll, never longer than what's left of the overall budget
+pub fn poll_attempt_timeout(start: Instant, timeout_secs: u64) -> Duration {
+    let remaining = Duration::from_secs(timeout_secs).saturating_sub(start.elapsed());
+    POLL_RPC_TIMEOUT.min(remaining).max(Duration::from_secs(1))
+}
+
+/// Run check every interval_secs until it returns Ok(true) (-> Ok(true)), the timeout elapses or the
+/// wait is cancelled (-> Ok(false)), or check fails (-> that error).
+/// check receives the per-attempt RPC timeout, and is always called at least once - a wait whose
+/// condition already holds succeeds even with timeout_secs = 0.
+pub async fn poll_until<F, Fut, E>(
+    label: &str,
+    interval_secs: u64,
+    timeout_secs: u64,
+    cancel: &WaitCancel,
+    mut check: F,
+) -> Result<bool, E>
+where
+    F: FnMut(Duration) -> Fut,
+    Fut: Future<Output = Result<bool, E>>,
+{
+    let start = Instant::now();
+    loop {
+        if cancel.is_cancelled() {
+            log::info!("{}: cancelled", label);
+            return Ok(false);
+        }
+        if check(poll_attempt_timeout(start, timeout_secs)).await? {
+            return Ok(true);
+        }
+        if start.elapsed() >= Duration::from_secs(timeout_secs) {
+            log::warn!("{}: timeout after {}s", label, timeout_secs);
+            return Ok(false);
+        }
+        if cancel.sleep(Duration::from_secs(interval_secs)).await {
+            log::info!("{}: cancelled", label);
+            return Ok(false);
+        }
+    }
+}
+
+/// Cancel every running wait and make new ones return at once (app shutdown). Returns how many keyed
+/// waits were running; keyless waits are stopped too but aren't counted.
+pub fn cancel_all_waits() -> usize {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+    let flags: Vec<Arc<CancelFlag>> = with_waits(|waits| waits.drain().map(|(_, flag)| flag).collect());
+    for flag in &flags {
+        flag.cancelled.store(true, Ordering::SeqCst);
+        flag.wake.notify_one();
+    }
+    shutdown_wake().notify_waiters();
+    log::info!("Cancelled all waits ({} keyed)", flags.len());
+    flags.len()
+}
+
+/// Cancel the wait registered under key; returns false if no such wait is running
+#[tauri::command]
+pub async fn cancel_wait(key: String) -> Result<bool, CommandError> {
+    match with_waits(|waits| waits.remove(&key)) {
+        Some(flag) => {
+            log::info!("Cancelling wait {}", key);
+            flag.cancelled.store(true, Ordering::SeqCst);
+            // notify_one stores a permit, so a loop that hasn't started sleeping yet still wakes
+            flag.wake.notify_one();
+            Ok(true)
+        }
+        None => {
+            log::debug!("cancel_wait: no active wait for {}", key);
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    // A check that counts its calls and reports ready from call ready_at on (never if None)
+    fn counting_check(
+        calls: &AtomicUsize,
+        ready_at: Option<usize>,
+    ) -> impl FnMut(Duration) -> std::future::Ready<Result<bool, String>> + '_ {
+        move |_| {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            std::future::ready(Ok(ready_at.is_some_and(|n| call >= n)))
+        }
+    }
+
+    #[tokio::test]
+    async fn ready_on_a_later_call_returns_true() {
+        let calls = AtomicUsize::new(0);
+        let cancel = WaitCancel::register(None);
+        let result = poll_until("test", 0, 30, &cancel, counting_check(&calls, Some(3))).await;
+        assert_eq!(result, Ok(true));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn never_ready_returns_false_after_timeout() {
+        let calls = AtomicUsize::new(0);
+        let cancel = WaitCancel::register(None);
+        let start = Instant::now();
+        let result = poll_until("test", 1, 1, &cancel, counting_check(&calls, None)).await;
+        assert_eq!(result, Ok(false));
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn ready_on_first_call_with_zero_timeout_returns_true() {
+        let calls = AtomicUsize::new(0);
+        let cancel = WaitCancel::register(None);
+        let result = poll_until("test", 0, 0, &cancel, counting_check(&calls, Some(1))).await;
+        assert_eq!(result, Ok(true));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn check_error_is_returned() {
+        let cancel = WaitCancel::register(None);
+        let result: Result<bool, String> =
+            poll_until("test", 0, 30, &cancel, |_| std::future::ready(Err("boom".to_string()))).await;
+        assert_eq!(result, Err("boom".to_string()));
+    }
+}
@@ -9,25 +9,87 @@
 // - Added get_wallet_info function and command to get wallet balances and reserve balances
 // - Added currency conversion commands: get_wallet_addresses, get_address_currency_balances, send_currency_conversion
 // - Removed fund_private_address_for_messages functionality as it's no longer needed
+// - RPC calls now take an rpc_host argument (configurable daemon host)
+// - Balance lookups retry transient RPC failures with backoff
+// - wait_for_block_increase polls with a short per-attempt RPC timeout
+// - Added consolidate_utxos (z_mergetoaddress self-merge of dust notes) to restore Fast Messages capacity
+// - get_utxo_info takes a min_usable threshold (default 0.0001) and reports it along with dust_utxos
+// - Added get_wallet_utxo_summary (one z_listunspent call for many addresses); classification moved to classify_utxos
+// - Added estimate_required_input (reverse estimate: input needed for a desired output) and the estimate_conversion_input command
+// - initiate_currency_conversion takes an optional min_output and aborts with SlippageExceeded if a fresh
+//   estimate falls below it; send_currency_conversion derives it from expected_output and max_slippage_pct
+// - initiate_currency_conversion / send_currency_conversion accept an optional fee override (>= paytxfee)
+// - Added WalletTransaction and get_transaction_history (listtransactions with pagination and category filter)
+// - Added export_transactions_csv (ISO-8601 timestamps; shielded entries without an address get an empty column)
+// - Added unlock_wallet (walletpassphrase) and lock_wallet (walletlock) for encrypted wallets
+// - Added SyncStatus with get_sync_status (getblockchaininfo) and wait_for_sync polling
+// - wait_for_block_increase / wait_for_sync accept an optional cancel_key (see wait_cancel)
+// - wait_for_block_increase / wait_for_sync run on the shared wait_cancel::poll_until loop
+// - WalletInfo also carries immature_balance, txcount, keypoololdest and unlocked_until (when reported)
+// - Added sweep_to_address (z_mergetoaddress from several addresses, rejected if the fee eats the balance)
+// - consolidate_utxos reads the opid out of the z_mergetoaddress result object (it isn't a bare string)
+// - Added AddressKind (prefix-based) and fetch_address_validation (validateaddress / z_validateaddress)
+// - Added validate_address command and ensure_valid_address guard; get_utxo_info and
+//   initiate_currency_conversion reject malformed addresses with InvalidFormat before doing any work
+// - Added import_z_key (z_importkey with RescanMode; returns once the daemon accepts the key, not after the rescan)
+// - Commands return CommandError (code from VerusRpcError::code) instead of a formatted String
+// - Added ChainInfo / fetch_chain_info and get_chain_info (getinfo + getblockchaininfo + getnetworkinfo in one batch)
+// - Added ensure_sufficient_funds; initiate_currency_conversion checks the from_currency balance at from_address
+//   (plus the fee when it is paid in the same currency) and fails early with InsufficientFunds
+// - fetch_address_currency_balances takes minconf and returns CurrencyBalances (raw i-address map plus an
+//   optional name-keyed map resolved through cached getcurrency lookups); get_address_currency_balances and
+//   get_address_currency_balance accept an optional minconf
+// - Added split_utxos (one self-directed send creating `count` outputs of `per_amount`, for bursts of messages)
+// - Added NetworkHealth / get_network_health (peer count + sync state in one batch; no_peers flags an isolated daemon)
+// - Added get_wildcard_utxo_info (listunspent over the transparent, non-identity UTXOs a "*" source spends)
+// - Added fetch_transaction_fee / get_transaction_fee (gettransaction fee; shielded sends computed from valueBalance)
+// - Added get_transparent_balance (getaddressbalance when the address index is on, else a listunspent sum)
+// - Currency name resolution moved to currency_names (shared cache)
+// - get_private_balance takes an optional min_conf (z_getbalance minconf, default DEFAULT_MIN_CONF)
+// - Added MessageCapacity / estimate_message_capacity (usable UTXOs vs balance / paytxfee, the smaller wins)
+// - send_currency_conversion accepts dry_run: all checks run and the sendcurrency params are returned with a
+//   fresh estimate, but nothing is broadcast (checks + params live in prepare_currency_conversion)
+// - Added AddressDelta / fetch_address_deltas (getaddressdeltas from a start height to the tip, for receive
+//   detection); a daemon without -addressindex yields AddressIndexDisabled
+// - Added ReserveTransfer / fetch_reserve_transfers and get_transaction_reserve_transfers (reserve transfer
+//   outputs of a raw transaction, one entry per currency carried)
+// - EstimateConversionResponse carries net input, input/conversion fees (when reported) and the effective
+//   price; estimate_conversion_quote / estimate_currency_conversion_quote return it (estimate_conversion is unchanged)
+// - consolidate_utxos skips dust that can't pay the fee plus a usable output, and merges only while
+//   at most one usable note exists (z_mergetoaddress can't be limited to dust notes)
+// - unlock_wallet / lock_wallet return CommandError: WRONG_PASSPHRASE / WALLET_NOT_ENCRYPTED for those daemon codes
+// - Custom conversion fees must be at least max(paytxfee, DEFAULT_TX_FEE); the native currency name used by the
+//   balance check comes from fetch_native_currency_name (cached per endpoint) instead of a fetch_chain_info per send
 
 use serde_json::{json, Value};
-use super::rpc_client::{make_rpc_call, VerusRpcError};
+use super::rpc_client::{make_rpc_batch, make_rpc_call, make_rpc_call_retry, make_rpc_call_with_timeout, VerusRpcError, DEFAULT_RETRY_POLICY, DEFAULT_RPC_TIMEOUT};
+use crate::wait_cancel::{poll_until, WaitCancel};
+use crate::CommandError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tokio::time::{sleep, Duration};
+use std::sync::{Mutex, OnceLock};
+use tokio::time::Duration;
 
 // UTXO information structure for Fast Messages feature
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UtxoInfo {
     pub total_utxos: u32,           // Total count including dust
-    pub usable_utxos: u32,          // Count with amount >= 0.0001 (Fast Messages count)
+    pub usable_utxos: u32,          // Count with amount >= min_usable (Fast Messages count)
+    pub dust_utxos: u32,            // Count below min_usable (total - usable)
     pub total_spendable_value: f64, // Sum of usable UTXOs only
     pub largest_utxo: f64,          // Largest single UTXO amount
-    pub smallest_utxo: f64,         // Smallest usable UTXO amount (>= 0.0001)
+    pub smallest_utxo: f64,         // Smallest usable UTXO amount (>= min_usable)
+    pub min_usable: f64,            // Threshold used for the counts above
 }
 
+// Default minimum amount for a UTXO to fund a message
+pub const DEFAULT_MIN_USABLE_UTXO: f64 = 0.0001;
+
+// Fee the daemon applies to sendcurrency / z_sendmany when none is given
+pub const DEFAULT_TX_FEE: f64 = 0.0001;
+
 // Request structure for estimateconversion
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EstimateConversionRequest {
     pub currency: String,
     pub convertto: String,
@@ -36,14 +98,51 @@ pub struct EstimateConversionRequest {
     pub amount: f64,
 }
 
-// Response structure for estimateconversion (simplified to get the key field)
+// Response structure for estimateconversion. Only estimatedcurrencyout is always present; the fee
+// fields are None when the daemon doesn't report them.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EstimateConversionResponse {
     #[serde(rename = "estimatedcurrencyout")]
     pub estimated_currency_out: f64,
-    // We can add other fields later if needed
+    #[serde(rename = "netinputamount", default)]
+    pub net_input_amount: Option<f64>,   // Input left after fees
+    #[serde(rename = "inputcurrencyfees", default)]
+    pub input_currency_fees: Option<f64>,
+    #[serde(rename = "conversionfees", default)]
+    pub conversion_fees: Option<f64>,
+    #[serde(default)]
+    pub effective_price: Option<f64>,    // Output per unit of input (requested amount, fees included)
+}
+
+// Result of a reverse conversion estimate (input needed for a desired output)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConversionInputEstimate {
+    pub required_input: f64,   // Input amount (in `currency`) yielding at least the desired output
+    pub estimated_output: f64, // estimateconversion output for required_input
+    pub slippage_pct: f64,     // Price impact vs. the marginal rate of a tiny conversion
 }
 
+// Single entry of listtransactions
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WalletTransaction {
+    pub txid: String,
+    pub category: String, // "send" | "receive" | "generate" | "immature" | ...
+    pub amount: f64,      // Negative for sends
+    #[serde(default)]
+    pub confirmations: i64,
+    #[serde(default)]
+    pub time: u64,
+    #[serde(default)]
+    pub address: Option<String>, // Omitted by the daemon for shielded activity
+    #[serde(default)]
+    pub fee: Option<f64>, // Only present on sends (negative)
+}
+
+// Reverse-estimate search parameters
+const REVERSE_ESTIMATE_PROBE: f64 = 0.001; // Small input used to sample the marginal rate
+const REVERSE_ESTIMATE_MAX_STEPS: u32 = 20; // Upper bound on estimateconversion calls per phase
+const REVERSE_ESTIMATE_TOLERANCE: f64 = 0.000_000_01; // Stop once the bracket is within 1 satoshi
+
 // Wallet info structure for payment details step
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WalletInfo {
@@ -51,6 +150,90 @@ pub struct WalletInfo {
     pub unconfirmed_balance: f64,
     pub reserve_balance: std::collections::HashMap<String, f64>,
     pub paytxfee: f64,
+    pub immature_balance: Option<f64>,
+    pub txcount: Option<u64>,
+    pub keypoololdest: Option<u64>,  // Unix time of the oldest pre-generated key
+    pub unlocked_until: Option<u64>, // Only for encrypted wallets: 0 = locked, else unlock expiry (Unix time)
+}
+
+// --- Address helpers ---
+
+// Address type as told by its prefix
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressKind {
+    Transparent, // R-address
+    Sapling,     // zs-address
+    Sprout,      // legacy zc-address
+    Identity,    // i-address or name@
+    Unknown,
+}
+
+impl AddressKind {
+    pub fn of(address: &str) -> Self {
+        if address.starts_with("zs") {
+            AddressKind::Sapling
+        } else if address.starts_with('z') {
+            AddressKind::Sprout
+        } else if address.starts_with('R') {
+            AddressKind::Transparent
+        } else if address.starts_with('i') || address.ends_with('@') {
+            AddressKind::Identity
+        } else {
+            AddressKind::Unknown
+        }
+    }
+
+    pub fn is_shielded(self) -> bool {
+        matches!(self, AddressKind::Sapling | AddressKind::Sprout)
+    }
+}
+
+// Fields shared by validateaddress and z_validateaddress
+#[derive(Deserialize, Debug)]
+struct RawAddressValidation {
+    isvalid: bool,
+    #[serde(default)]
+    ismine: bool,
+}
+
+// Result of validating an address against the daemon
+#[derive(Debug, Serialize, Clone)]
+pub struct AddressValidation {
+    pub is_valid: bool,
+    pub is_mine: bool, // The wallet holds the key for it
+    pub kind: AddressKind,
+}
+
+// Validate an address, dispatching to z_validateaddress for shielded ones and validateaddress otherwise
+pub async fn fetch_address_validation(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    address: &str,
+) -> Result<AddressValidation, VerusRpcError> {
+    let kind = AddressKind::of(address);
+    let method = if kind.is_shielded() { "z_validateaddress" } else { "validateaddress" };
+    let raw: RawAddressValidation = make_rpc_call(rpc_user, rpc_pass, rpc_host, rpc_port, method, vec![json!(address)]).await?;
+    log::debug!("{}({}): valid={}, mine={}", method, address, raw.isvalid, raw.ismine);
+    Ok(AddressValidation { is_valid: raw.isvalid, is_mine: raw.ismine, kind })
+}
+
+// Guard for commands taking an address: InvalidFormat instead of a confusing error deep in the RPC call
+pub async fn ensure_valid_address(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    address: &str,
+) -> Result<AddressValidation, VerusRpcError> {
+    let validation = fetch_address_validation(rpc_user, rpc_pass, rpc_host, rpc_port, address).await?;
+    if !validation.is_valid {
+        log::warn!("Rejecting invalid address: {}", address);
+        return Err(VerusRpcError::InvalidFormat);
+    }
+    Ok(validation)
 }
 
 // Function to connect and get block height
@@ -58,61 +241,282 @@ pub struct WalletInfo {
 pub async fn connect_and_get_block_height(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
 ) -> Result<u64, VerusRpcError> {
     log::info!("Attempting to connect to Verus daemon...");
-    make_rpc_call(&rpc_user, &rpc_pass, rpc_port, "getblockcount", vec![]).await
+    make_rpc_call(&rpc_user, &rpc_pass, &rpc_host, rpc_port, "getblockcount", vec![]).await
+}
+
+// Verification progress above which the daemon is considered caught up with the chain tip
+const SYNCED_PROGRESS_THRESHOLD: f64 = 0.9999;
+
+// Raw getblockchaininfo fields used for sync detection
+#[derive(Deserialize, Debug)]
+struct BlockchainInfo {
+    blocks: u64,
+    headers: u64,
+    verificationprogress: f64,
+}
+
+// Daemon sync state (balances shown before is_synced may be stale)
+#[derive(Debug, Serialize, Clone)]
+pub struct SyncStatus {
+    pub blocks: u64,
+    pub headers: u64,
+    pub verification_progress: f64,
+    pub is_synced: bool,
+}
+
+// Fetch the daemon's sync state via getblockchaininfo
+pub async fn fetch_sync_status(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    timeout: Duration,
+) -> Result<SyncStatus, VerusRpcError> {
+    let info: BlockchainInfo = make_rpc_call_with_timeout(rpc_user, rpc_pass, rpc_host, rpc_port, "getblockchaininfo", vec![], timeout).await?;
+    Ok(SyncStatus {
+        blocks: info.blocks,
+        headers: info.headers,
+        verification_progress: info.verificationprogress,
+        is_synced: info.verificationprogress > SYNCED_PROGRESS_THRESHOLD,
+    })
+}
+
+// Peer connectivity and sync state, for diagnosing "messages not arriving"
+#[derive(Debug, Serialize, Clone)]
+pub struct NetworkHealth {
+    pub connections: u64,
+    pub no_peers: bool, // Zero connections: the daemon can't see new blocks or relay sends
+    pub synced: bool,
+    pub blocks: u64,
+    pub headers: u64,
+    pub headers_behind: u64, // headers - blocks
+}
+
+// getconnectioncount + getblockchaininfo in one batch
+pub async fn fetch_network_health(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+) -> Result<NetworkHealth, VerusRpcError> {
+    let calls = ["getconnectioncount", "getblockchaininfo"]
+        .iter()
+        .map(|method| (method.to_string(), vec![]))
+        .collect();
+    let mut results = make_rpc_batch(rpc_user, rpc_pass, rpc_host, rpc_port, calls).await.into_iter();
+    let mut next = || results.next().unwrap_or(Err(VerusRpcError::Format));
+    let (connections, blockchain) = (next()?, next()?);
+
+    let connections = connections
+        .as_u64()
+        .ok_or_else(|| VerusRpcError::ParseError("getconnectioncount did not return a number".to_string()))?;
+    let info: BlockchainInfo = serde_json::from_value(blockchain).map_err(|e| VerusRpcError::ParseError(e.to_string()))?;
+
+    let health = NetworkHealth {
+        connections,
+        no_peers: connections == 0,
+        synced: info.verificationprogress > SYNCED_PROGRESS_THRESHOLD,
+        blocks: info.blocks,
+        headers: info.headers,
+        headers_behind: info.headers.saturating_sub(info.blocks),
+    };
+    if health.no_peers {
+        log::warn!("Daemon has no peer connections (height {})", health.blocks);
+    }
+    Ok(health)
+}
+
+// Chain metadata: which network the daemon is on and what it calls its native currency
+#[derive(Debug, Serialize, Clone)]
+pub struct ChainInfo {
+    pub chain: String,              // getblockchaininfo "chain": "main" or "test"
+    pub blocks: u64,
+    pub protocol_version: u64,
+    pub subversion: Option<String>, // getnetworkinfo user agent, e.g. "/MagicBean:1.2.x/"
+    pub name: String,               // Native currency, e.g. "VRSC" or "VRSCTEST"
+}
+
+impl ChainInfo {
+    pub fn is_testnet(&self) -> bool {
+        self.chain == "test"
+    }
+}
+
+// getinfo, getblockchaininfo and getnetworkinfo in a single batch (getnetworkinfo is optional)
+pub async fn fetch_chain_info(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+) -> Result<ChainInfo, VerusRpcError> {
+    let calls = ["getinfo", "getblockchaininfo", "getnetworkinfo"]
+        .iter()
+        .map(|method| (method.to_string(), vec![]))
+        .collect();
+    let mut results = make_rpc_batch(rpc_user, rpc_pass, rpc_host, rpc_port, calls).await.into_iter();
+    let mut next = || results.next().unwrap_or(Err(VerusRpcError::Format));
+    let (info, blockchain, network) = (next()?, next()?, next().ok());
+
+    let chain = blockchain["chain"]
+        .as_str()
+        .ok_or_else(|| VerusRpcError::ParseError("getblockchaininfo has no chain".to_string()))?
+        .to_string();
+    // getinfo "name" is the native currency; fall back on the network if an old daemon omits it
+    let name = match info["name"].as_str().filter(|s| !s.is_empty()) {
+        Some(name) => name.to_string(),
+        None if chain == "test" => "VRSCTEST".to_string(),
+        None => "VRSC".to_string(),
+    };
+
+    Ok(ChainInfo {
+        blocks: blockchain["blocks"].as_u64().or_else(|| info["blocks"].as_u64()).unwrap_or(0),
+        protocol_version: info["protocolversion"].as_u64().unwrap_or(0),
+        subversion: network.and_then(|n| n["subversion"].as_str().map(str::to_string)),
+        chain,
+        name,
+    })
+}
+
+// Native currency name per endpoint (host:port); a running daemon never changes it
+static NATIVE_CURRENCY_CACHE: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+// ChainInfo.name, fetched once per endpoint and then served from NATIVE_CURRENCY_CACHE
+pub async fn fetch_native_currency_name(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+) -> Result<String, VerusRpcError> {
+    let endpoint = format!("{}:{}", rpc_host, rpc_port);
+    let cache = NATIVE_CURRENCY_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(name) = cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(&endpoint) {
+        return Ok(name.clone());
+    }
+
+    let name = fetch_chain_info(rpc_user, rpc_pass, rpc_host, rpc_port).await?.name;
+    log::debug!("Daemon native currency: {}", name);
+    cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(endpoint, name.clone());
+    Ok(name)
 }
 
-// Function to get balance for a z-address
-pub async fn get_private_balance(rpc_user: String, rpc_pass: String, rpc_port: u16, address: String) -> Result<f64, VerusRpcError> {
-    log::info!("Fetching private balance for address: {}", address);
-    make_rpc_call(&rpc_user, &rpc_pass, rpc_port, "z_getbalance", vec![json!(address)]).await
+// Confirmations a z-address balance needs by default to count as spendable
+pub const DEFAULT_MIN_CONF: u32 = 1;
+
+// Function to get balance for a z-address, counting notes with at least min_conf confirmations (default 1)
+pub async fn get_private_balance(rpc_user: String, rpc_pass: String, rpc_host: String, rpc_port: u16, address: String, min_conf: Option<u32>) -> Result<f64, VerusRpcError> {
+    let min_conf = min_conf.unwrap_or(DEFAULT_MIN_CONF);
+    log::info!("Fetching private balance for address: {} (minconf {})", address, min_conf);
+    make_rpc_call_retry(&rpc_user, &rpc_pass, &rpc_host, rpc_port, "z_getbalance", vec![json!(address), json!(min_conf)], DEFAULT_RETRY_POLICY).await
 }
 
 // Function to get pending balance for a z-address (0 confirmations)
-pub async fn get_pending_balance(rpc_user: String, rpc_pass: String, rpc_port: u16, address: String) -> Result<f64, VerusRpcError> {
+pub async fn get_pending_balance(rpc_user: String, rpc_pass: String, rpc_host: String, rpc_port: u16, address: String) -> Result<f64, VerusRpcError> {
     log::info!("Fetching pending balance for address: {}", address);
-    make_rpc_call(&rpc_user, &rpc_pass, rpc_port, "z_getbalance", vec![json!(address), json!(0)]).await
+    make_rpc_call_retry(&rpc_user, &rpc_pass, &rpc_host, rpc_port, "z_getbalance", vec![json!(address), json!(0)], DEFAULT_RETRY_POLICY).await
 }
 
-// NEW function to get UTXO information for Fast Messages
-pub async fn get_utxo_info(
+// Balance of a transparent address (e.g. an identity's R-address control address); 0 when it has no UTXOs.
+// getaddressbalance needs -addressindex and only counts confirmed outputs, so it's used for minconf 1;
+// otherwise (or without the index) the wallet's listunspent entries for the address are summed.
+pub async fn get_transparent_balance(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
     address: String,
-) -> Result<UtxoInfo, VerusRpcError> {
-    log::info!("Fetching UTXO info for address: {}", address);
-    
-    // Call z_listunspent with specific parameters:
-    // minconf=1: Only confirmed UTXOs
-    // maxconf=9999999: All confirmed UTXOs  
-    // watchonly=false: Only spendable UTXOs
-    // addresses=[address]: Only for this specific address
-    let utxo_list: Value = make_rpc_call(
+    minconf: u32,
+) -> Result<f64, VerusRpcError> {
+    log::info!("Fetching transparent balance for address: {} (minconf {})", address, minconf);
+    if minconf == 1 {
+        let indexed: Result<Value, VerusRpcError> = make_rpc_call(
+            &rpc_user,
+            &rpc_pass,
+            &rpc_host,
+            rpc_port,
+            "getaddressbalance",
+            vec![json!({ "addresses": [address] })],
+        ).await;
+        match indexed {
+            Ok(result) => {
+                let sats = result["balance"].as_i64().unwrap_or(0);
+                return Ok(sats as f64 / 100_000_000.0);
+            }
+            Err(VerusRpcError::Rpc { code, message }) => {
+                log::debug!("getaddressbalance unavailable ({}: {}), falling back to listunspent", code, message);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    let utxos: Vec<Value> = make_rpc_call(
         &rpc_user,
         &rpc_pass,
+        &rpc_host,
         rpc_port,
-        "z_listunspent",
-        vec![json!(1), json!(9999999), json!(false), json!([address])],
+        "listunspent",
+        vec![json!(minconf), json!(9999999), json!([address])],
     ).await?;
+    let balance: f64 = utxos.iter().filter_map(|u| u["amount"].as_f64()).sum();
+    Ok((balance * 100_000_000.0).round() / 100_000_000.0)
+}
 
-    log::debug!("Raw UTXO response: {:?}", utxo_list);
+// One balance change of a transparent address from getaddressdeltas (positive = received)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressDelta {
+    pub address: String,
+    pub satoshis: i64,
+    pub txid: String,
+    pub height: u64,
+}
 
-    // Process the UTXO list
-    let utxos = utxo_list.as_array().ok_or(VerusRpcError::ParseError(
-        "Expected array of UTXOs".to_string(),
-    ))?;
+// getaddressdeltas for the given addresses from start_height up to the current tip, in chain order.
+// A watcher can store the highest height it has processed and pass height + 1 next time.
+pub async fn fetch_address_deltas(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    addresses: &[String],
+    start_height: u64,
+) -> Result<Vec<AddressDelta>, VerusRpcError> {
+    if addresses.is_empty() {
+        return Ok(Vec::new());
+    }
+    // start and end must be given together
+    let tip: u64 = make_rpc_call(rpc_user, rpc_pass, rpc_host, rpc_port, "getblockcount", vec![]).await?;
+    if start_height > tip {
+        return Ok(Vec::new());
+    }
+    log::info!("Fetching address deltas for {} address(es), heights {}..={}", addresses.len(), start_height, tip);
+
+    let params = vec![json!({ "addresses": addresses, "start": start_height, "end": tip })];
+    let deltas: Vec<AddressDelta> = match make_rpc_call(rpc_user, rpc_pass, rpc_host, rpc_port, "getaddressdeltas", params).await {
+        Ok(deltas) => deltas,
+        Err(VerusRpcError::Rpc { code, message })
+            if code == -32602 || message.to_lowercase().contains("address index") || message.to_lowercase().contains("addressindex") =>
+        {
+            log::warn!("getaddressdeltas unavailable ({}: {}) - address index disabled", code, message);
+            return Err(VerusRpcError::AddressIndexDisabled);
+        }
+        Err(e) => return Err(e),
+    };
+    log::debug!("getaddressdeltas returned {} delta(s)", deltas.len());
+    Ok(deltas)
+}
 
+// Classify a set of UTXO amounts into usable vs dust (shared by single-address and wallet-wide lookups)
+fn classify_utxos(amounts: impl IntoIterator<Item = f64>, min_usable: f64) -> UtxoInfo {
     let mut total_utxos = 0u32;
     let mut usable_utxos = 0u32;
     let mut total_spendable_value = 0.0f64;
     let mut largest_utxo = 0.0f64;
     let mut smallest_utxo = f64::MAX;
 
-    for utxo in utxos {
-        let amount = utxo["amount"].as_f64().unwrap_or(0.0);
+    for amount in amounts {
         total_utxos += 1;
 
         // Track largest UTXO regardless of usability
@@ -120,8 +524,8 @@ pub async fn get_utxo_info(
             largest_utxo = amount;
         }
 
-        // Filter for usable UTXOs (amount >= 0.0001)
-        if amount >= 0.0001 {
+        // Filter for usable UTXOs (amount >= min_usable)
+        if amount >= min_usable {
             usable_utxos += 1;
             total_spendable_value += amount;
             
@@ -137,18 +541,67 @@ pub async fn get_utxo_info(
         smallest_utxo = 0.0;
     }
 
-    let utxo_info = UtxoInfo {
+    UtxoInfo {
         total_utxos,
         usable_utxos,
+        dust_utxos: total_utxos - usable_utxos,
         total_spendable_value,
         largest_utxo,
         smallest_utxo,
-    };
+        min_usable,
+    }
+}
+
+// Call z_listunspent for the given addresses and return the raw entries
+async fn list_unspent_notes(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    addresses: &[String],
+) -> Result<Vec<Value>, VerusRpcError> {
+    // Call z_listunspent with specific parameters:
+    // minconf=1: Only confirmed UTXOs
+    // maxconf=9999999: All confirmed UTXOs  
+    // watchonly=false: Only spendable UTXOs
+    // addresses=[...]: Only for these specific addresses
+    let utxo_list: Value = make_rpc_call(
+        rpc_user,
+        rpc_pass,
+        rpc_host,
+        rpc_port,
+        "z_listunspent",
+        vec![json!(1), json!(9999999), json!(false), json!(addresses)],
+    ).await?;
+
+    log::debug!("Raw UTXO response: {:?}", utxo_list);
+
+    match utxo_list {
+        Value::Array(utxos) => Ok(utxos),
+        _ => Err(VerusRpcError::ParseError("Expected array of UTXOs".to_string())),
+    }
+}
+
+// NEW function to get UTXO information for Fast Messages
+pub async fn get_utxo_info(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    address: String,
+    min_usable: f64,
+) -> Result<UtxoInfo, VerusRpcError> {
+    log::info!("Fetching UTXO info for address: {} (min_usable: {})", address, min_usable);
+    ensure_valid_address(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &address).await?;
+
+    let utxos = list_unspent_notes(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &[address]).await?;
+    let utxo_info = classify_utxos(utxos.iter().map(|u| u["amount"].as_f64().unwrap_or(0.0)), min_usable);
 
     log::info!(
-        "UTXO analysis complete: {} total UTXOs, {} usable UTXOs, {:.4} total spendable, largest: {:.4}, smallest: {:.4}",
+        "UTXO analysis complete: {} total UTXOs, {} usable UTXOs, {} dust, {:.4} total spendable, largest: {:.4}, smallest: {:.4}",
         utxo_info.total_utxos,
         utxo_info.usable_utxos,
+        utxo_info.dust_utxos,
         utxo_info.total_spendable_value,
         utxo_info.largest_utxo,
         utxo_info.smallest_utxo
@@ -157,13 +610,332 @@ pub async fn get_utxo_info(
     Ok(utxo_info)
 }
 
+// UTXO picture for a "*" source: z_sendmany then spends transparent UTXOs of any non-identity
+// address in the wallet, so count those (listunspent) rather than a single address's notes
+pub async fn get_wildcard_utxo_info(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    min_usable: f64,
+) -> Result<UtxoInfo, VerusRpcError> {
+    let utxos: Vec<Value> = make_rpc_call(rpc_user, rpc_pass, rpc_host, rpc_port, "listunspent", vec![json!(1)]).await?;
+    let amounts = utxos
+        .iter()
+        .filter(|u| u["spendable"].as_bool().unwrap_or(true))
+        .filter(|u| !u["address"].as_str().unwrap_or("").starts_with('i'))
+        .map(|u| u["amount"].as_f64().unwrap_or(0.0));
+    let utxo_info = classify_utxos(amounts, min_usable);
+    log::info!(
+        "Wildcard UTXO analysis: {} usable of {} transparent UTXOs, {:.4} spendable",
+        utxo_info.usable_utxos, utxo_info.total_utxos, utxo_info.total_spendable_value
+    );
+    Ok(utxo_info)
+}
+
+// Wallet-wide UTXO picture for several identity addresses
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WalletUtxoSummary {
+    pub per_address: HashMap<String, UtxoInfo>, // Keyed by z-address
+    pub total: UtxoInfo,                        // All addresses combined
+}
+
+// Which constraint caps MessageCapacity::max_messages
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CapacityLimit {
+    Utxos,   // Not enough usable UTXOs (splitting would help)
+    Balance, // Not enough funds to pay the fee of more messages
+}
+
+// How many messages an address can send right now, and why not more
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MessageCapacity {
+    pub max_messages: u64,
+    pub utxo_limited: u64,    // Usable UTXOs: one per message until change confirms
+    pub balance_limited: u64, // Spendable balance / fee_per_message
+    pub limited_by: CapacityLimit,
+    pub fee_per_message: f64,
+    pub spendable_balance: f64,
+}
+
+// Message capacity of an address from its usable UTXOs and the wallet's paytxfee
+// (DEFAULT_TX_FEE when the wallet reports none)
+pub async fn estimate_message_capacity(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    address: String,
+) -> Result<MessageCapacity, VerusRpcError> {
+    let (utxo_info, wallet_info) = futures::future::join(
+        get_utxo_info(rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port, address.clone(), DEFAULT_MIN_USABLE_UTXO),
+        fetch_wallet_info(rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port),
+    )
+    .await;
+    let (utxo_info, wallet_info) = (utxo_info?, wallet_info?);
+
+    let fee_per_message = if wallet_info.paytxfee > 0.0 { wallet_info.paytxfee } else { DEFAULT_TX_FEE };
+    let utxo_limited = utxo_info.usable_utxos as u64;
+    // Round before flooring so e.g. 0.0003 / 0.0001 doesn't come out as 2.9999...
+    let balance_limited = ((utxo_info.total_spendable_value / fee_per_message * 1e6).round() / 1e6).floor() as u64;
+    let (max_messages, limited_by) = if utxo_limited <= balance_limited {
+        (utxo_limited, CapacityLimit::Utxos)
+    } else {
+        (balance_limited, CapacityLimit::Balance)
+    };
+
+    log::info!(
+        "Message capacity for {}: {} (utxos {}, balance {} at fee {})",
+        address, max_messages, utxo_limited, balance_limited, fee_per_message
+    );
+    Ok(MessageCapacity {
+        max_messages,
+        utxo_limited,
+        balance_limited,
+        limited_by,
+        fee_per_message,
+        spendable_balance: utxo_info.total_spendable_value,
+    })
+}
+
+// NEW function to get UTXO information for several addresses with a single z_listunspent call
+pub async fn get_wallet_utxo_summary(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    addresses: Vec<String>,
+    min_usable: f64,
+) -> Result<WalletUtxoSummary, VerusRpcError> {
+    log::info!("Fetching wallet UTXO summary for {} addresses (min_usable: {})", addresses.len(), min_usable);
+
+    let utxos = list_unspent_notes(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &addresses).await?;
+
+    // Every requested address gets an entry, even without UTXOs
+    let mut amounts_by_address: HashMap<String, Vec<f64>> =
+        addresses.iter().map(|a| (a.clone(), Vec::new())).collect();
+    let mut all_amounts = Vec::with_capacity(utxos.len());
+    for utxo in &utxos {
+        let amount = utxo["amount"].as_f64().unwrap_or(0.0);
+        all_amounts.push(amount);
+        if let Some(address) = utxo["address"].as_str() {
+            amounts_by_address.entry(address.to_string()).or_default().push(amount);
+        }
+    }
+
+    let per_address: HashMap<String, UtxoInfo> = amounts_by_address
+        .into_iter()
+        .map(|(address, amounts)| (address, classify_utxos(amounts, min_usable)))
+        .collect();
+    let total = classify_utxos(all_amounts, min_usable);
+
+    log::info!(
+        "Wallet UTXO summary complete: {} addresses, {} total UTXOs, {} usable, {} dust",
+        per_address.len(),
+        total.total_utxos,
+        total.usable_utxos,
+        total.dust_utxos
+    );
+
+    Ok(WalletUtxoSummary { per_address, total })
+}
+
+// Default fee for a z_mergetoaddress transaction
+const MERGE_FEE: f64 = 0.0001;
+
+// z_mergetoaddress returns an object (merging/remaining counts) with the opid inside
+fn merge_opid(result: &Value) -> Result<String, VerusRpcError> {
+    result["opid"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| VerusRpcError::ParseError("z_mergetoaddress result has no opid".to_string()))
+}
+
+// Merge dust notes back into the address so they form a usable (>= 0.0001) output again.
+// Returns the z_mergetoaddress opid, or None when there's nothing worth merging (usable count
+// already at or above target, fewer than two dust notes, or dust not worth a usable output after the fee).
+// z_mergetoaddress can't be told which notes to take, only how many, so the merge only runs while
+// the address holds at most one usable note: then it can't leave fewer usable notes than before.
+pub async fn consolidate_utxos(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    address: String,
+    target_count: u32,
+) -> Result<Option<String>, VerusRpcError> {
+    ensure_valid_address(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &address).await?;
+    let amounts: Vec<f64> = list_unspent_notes(&rpc_user, &rpc_pass, &rpc_host, rpc_port, std::slice::from_ref(&address))
+        .await?
+        .iter()
+        .map(|note| note["amount"].as_f64().unwrap_or(0.0))
+        .collect();
+    let utxo_info = classify_utxos(amounts.iter().copied(), DEFAULT_MIN_USABLE_UTXO);
+
+    if utxo_info.usable_utxos >= target_count {
+        log::info!(
+            "consolidate_utxos: {} already has {} usable UTXOs (target {}), skipping merge",
+            address, utxo_info.usable_utxos, target_count
+        );
+        return Ok(None);
+    }
+
+    let dust_count = utxo_info.dust_utxos;
+    if dust_count < 2 {
+        log::info!("consolidate_utxos: only {} dust UTXO(s) on {}, nothing to merge", dust_count, address);
+        return Ok(None);
+    }
+
+    let dust_total: f64 = amounts.iter().filter(|&&amount| amount < DEFAULT_MIN_USABLE_UTXO).sum();
+    if dust_total < MERGE_FEE + DEFAULT_MIN_USABLE_UTXO {
+        log::info!(
+            "consolidate_utxos: {} dust on {} wouldn't leave a usable output after the {} fee, skipping merge",
+            dust_total, address, MERGE_FEE
+        );
+        return Ok(None);
+    }
+
+    // Each usable note the daemon picks instead of dust costs one usable output
+    if utxo_info.usable_utxos > 1 {
+        log::info!(
+            "consolidate_utxos: {} has {} usable UTXOs that a merge could consume, skipping merge",
+            address, utxo_info.usable_utxos
+        );
+        return Ok(None);
+    }
+
+    // The daemon chooses which notes to merge; the limit caps how many it takes
+    let result: Value = make_rpc_call(
+        &rpc_user,
+        &rpc_pass,
+        &rpc_host,
+        rpc_port,
+        "z_mergetoaddress",
+        vec![json!([address]), json!(address), json!(MERGE_FEE), json!(0), json!(dust_count)],
+    ).await?;
+    let opid = merge_opid(&result)?;
+
+    log::info!("consolidate_utxos: merging up to {} notes on {}, opid={}", dust_count, address, opid);
+    Ok(Some(opid))
+}
+
+// Gather the balances of several addresses into one (e.g. scattered transparent UTXOs before a
+// VerusID registration). z_mergetoaddress takes an explicit source list, which sendmany can't.
+// Returns the opid: wait_for_operation -> get_operation_result gives the txid for wait_for_confirmations.
+pub async fn sweep_to_address(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    from_addresses: Vec<String>,
+    to_address: String,
+    fee: Option<f64>,
+) -> Result<String, VerusRpcError> {
+    let fee = fee.unwrap_or(MERGE_FEE);
+    log::info!("sweep_to_address: {} source address(es) -> {}, fee {}", from_addresses.len(), to_address, fee);
+
+    let balance_futures = from_addresses.iter().map(|address| {
+        get_private_balance(rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port, address.clone(), None)
+    });
+    let mut available = 0.0;
+    for (address, balance) in from_addresses.iter().zip(futures::future::join_all(balance_futures).await) {
+        let balance = balance?;
+        log::debug!("sweep_to_address: {} holds {}", address, balance);
+        available += balance;
+    }
+
+    if available - fee <= 0.0 {
+        log::warn!("sweep_to_address: available {} does not cover fee {}", available, fee);
+        return Err(VerusRpcError::SweepAmountTooLow { available, fee });
+    }
+
+    let result: Value = make_rpc_call(
+        &rpc_user,
+        &rpc_pass,
+        &rpc_host,
+        rpc_port,
+        "z_mergetoaddress",
+        vec![json!(from_addresses), json!(to_address), json!(fee)],
+    ).await?;
+
+    let opid = merge_opid(&result)?;
+    log::info!(
+        "sweep_to_address: merging {} UTXO(s) ({} remaining), opid={}",
+        result["mergingUTXOs"].as_u64().unwrap_or(0),
+        result["remainingUTXOs"].as_u64().unwrap_or(0),
+        opid
+    );
+    Ok(opid)
+}
+
+// Upper bound on outputs per split; shielded outputs make the transaction (and its proof time) grow fast
+pub const MAX_SPLIT_OUTPUTS: u32 = 50;
+
+// Split an address's balance into `count` outputs of `per_amount`, all back to the same address, so
+// that many messages can be sent before change confirms (the opposite of consolidate_utxos).
+// z_sendmany rejects a repeated recipient address, so this goes through sendcurrency, which builds
+// one output per entry. Returns the opid (see wait_for_operation / get_operation_result).
+pub async fn split_utxos(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    address: String,
+    count: u32,
+    per_amount: f64,
+) -> Result<String, VerusRpcError> {
+    log::info!("split_utxos: {} x {} on {}", count, per_amount, address);
+    if !(2..=MAX_SPLIT_OUTPUTS).contains(&count) {
+        return Err(VerusRpcError::InvalidArgument(format!("count must be between 2 and {}", MAX_SPLIT_OUTPUTS)));
+    }
+    // Smaller outputs would only be counted as dust by get_utxo_info
+    if !per_amount.is_finite() || per_amount < DEFAULT_MIN_USABLE_UTXO {
+        return Err(VerusRpcError::InvalidArgument(format!("per_amount must be at least {}", DEFAULT_MIN_USABLE_UTXO)));
+    }
+    ensure_valid_address(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &address).await?;
+
+    let per_amount = (per_amount * 100_000_000.0).round() / 100_000_000.0;
+    let available = get_private_balance(rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port, address.clone(), None).await?;
+    ensure_sufficient_funds(available, per_amount * count as f64, DEFAULT_TX_FEE, true)?;
+
+    let outputs: Vec<Value> = (0..count)
+        .map(|_| json!({ "address": address, "amount": per_amount }))
+        .collect();
+    let opid: String = make_rpc_call(
+        &rpc_user,
+        &rpc_pass,
+        &rpc_host,
+        rpc_port,
+        "sendcurrency",
+        vec![json!(address), json!(outputs)],
+    ).await?;
+
+    log::info!("split_utxos: queued {} outputs on {}, opid={}", count, address, opid);
+    Ok(opid)
+}
+
 // NEW function to estimate currency conversion
 pub async fn estimate_conversion(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
     request: EstimateConversionRequest,
 ) -> Result<f64, VerusRpcError> {
+    estimate_conversion_quote(rpc_user, rpc_pass, rpc_host, rpc_port, request)
+        .await
+        .map(|quote| quote.estimated_currency_out)
+}
+
+// Full estimateconversion quote: output plus whatever fee breakdown the daemon returned
+pub async fn estimate_conversion_quote(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    request: EstimateConversionRequest,
+) -> Result<EstimateConversionResponse, VerusRpcError> {
     log::info!(
         "Estimating conversion: {} {} to {} {}",
         request.amount,
@@ -188,6 +960,7 @@ pub async fn estimate_conversion(
     let response: Value = make_rpc_call(
         &rpc_user,
         &rpc_pass,
+        &rpc_host,
         rpc_port,
         "estimateconversion",
         vec![conversion_params],
@@ -196,27 +969,271 @@ pub async fn estimate_conversion(
     log::debug!("Raw estimateconversion response: {:?}", response);
 
     // Extract the estimated currency out value
-    let estimated_out = response["estimatedcurrencyout"]
-        .as_f64()
-        .ok_or(VerusRpcError::ParseError(
+    if response["estimatedcurrencyout"].as_f64().is_none() {
+        return Err(VerusRpcError::ParseError(
             "Missing or invalid 'estimatedcurrencyout' in response".to_string(),
-        ))?;
+        ));
+    }
+    let mut quote: EstimateConversionResponse = serde_json::from_value(response)
+        .map_err(|e| VerusRpcError::ParseError(format!("Invalid estimateconversion response: {}", e)))?;
+    if request.amount > 0.0 {
+        quote.effective_price = Some(quote.estimated_currency_out / request.amount);
+    }
 
     log::info!(
-        "Conversion estimate: {} {} = {} {}",
+        "Conversion estimate: {} {} = {} {} (input fees {:?}, conversion fees {:?})",
         request.amount,
         request.currency,
-        estimated_out,
-        request.convertto
+        quote.estimated_currency_out,
+        request.convertto,
+        quote.input_currency_fees,
+        quote.conversion_fees
     );
 
-    Ok(estimated_out)
+    Ok(quote)
+}
+
+// Round to 8 decimal places (satoshi precision), rounding up so the output target is still met
+fn ceil_to_satoshi(amount: f64) -> f64 {
+    (amount * 100_000_000.0).ceil() / 100_000_000.0
+}
+
+// NEW function to find the input amount needed for a desired output.
+// `request.amount` is the desired output (in `convertto`). Uses the marginal rate from a small
+// probe as the first guess, then brackets and binary-searches estimateconversion.
+pub async fn estimate_required_input(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    request: EstimateConversionRequest,
+) -> Result<ConversionInputEstimate, VerusRpcError> {
+    let desired_out = request.amount;
+    if !desired_out.is_finite() || desired_out <= 0.0 {
+        return Err(VerusRpcError::InvalidArgument(format!("desired output must be a positive amount, got {}", desired_out)));
+    }
+    log::info!(
+        "Estimating input needed for {} {} from {}",
+        desired_out, request.convertto, request.currency
+    );
+
+    // Estimate the output for a given input amount
+    let estimate = |amount: f64| {
+        let mut req = request.clone();
+        req.amount = amount;
+        estimate_conversion(rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port, req)
+    };
+
+    // Marginal rate (output per unit of input) for a tiny conversion
+    let probe_out = estimate(REVERSE_ESTIMATE_PROBE).await?;
+    if probe_out <= 0.0 {
+        return Err(VerusRpcError::ParseError("Conversion rate is zero".to_string()));
+    }
+    let marginal_rate = probe_out / REVERSE_ESTIMATE_PROBE;
+
+    // Bracket the answer: grow the linear guess until it yields enough output
+    let mut low = 0.0;
+    let mut high = ceil_to_satoshi(desired_out / marginal_rate);
+    let mut high_out = estimate(high).await?;
+    let mut steps = 0;
+    while high_out < desired_out {
+        steps += 1;
+        if steps > REVERSE_ESTIMATE_MAX_STEPS {
+            return Err(VerusRpcError::ParseError(format!(
+                "Could not find an input amount yielding {} {}",
+                desired_out, request.convertto
+            )));
+        }
+        low = high;
+        high = ceil_to_satoshi(high * 1.25);
+        high_out = estimate(high).await?;
+    }
+
+    // Binary search inside [low, high] for the smallest sufficient input
+    for _ in 0..REVERSE_ESTIMATE_MAX_STEPS {
+        if high - low <= REVERSE_ESTIMATE_TOLERANCE {
+            break;
+        }
+        let mid = ceil_to_satoshi((low + high) / 2.0);
+        if mid >= high {
+            break;
+        }
+        let mid_out = estimate(mid).await?;
+        if mid_out >= desired_out {
+            high = mid;
+            high_out = mid_out;
+        } else {
+            low = mid;
+        }
+    }
+
+    let slippage_pct = (1.0 - high_out / (high * marginal_rate)) * 100.0;
+    log::info!(
+        "Reverse estimate: {} {} -> {} {} (slippage {:.3}%)",
+        high, request.currency, high_out, request.convertto, slippage_pct
+    );
+
+    Ok(ConversionInputEstimate {
+        required_input: high,
+        estimated_output: high_out,
+        slippage_pct,
+    })
+}
+
+// NEW function to list wallet transactions (newest last, as returned by the daemon).
+// The category filter is applied to the fetched page, so a filtered page may hold fewer than `count` entries.
+pub async fn list_wallet_transactions(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    count: u32,
+    skip: u32,
+    category: Option<String>,
+) -> Result<Vec<WalletTransaction>, VerusRpcError> {
+    log::info!("Fetching transaction history: count={}, skip={}, category={:?}", count, skip, category);
+
+    let transactions: Vec<WalletTransaction> = make_rpc_call(
+        &rpc_user,
+        &rpc_pass,
+        &rpc_host,
+        rpc_port,
+        "listtransactions",
+        vec![json!("*"), json!(count), json!(skip)],
+    ).await?;
+
+    let filtered: Vec<WalletTransaction> = match category {
+        Some(category) => transactions.into_iter().filter(|t| t.category == category).collect(),
+        None => transactions,
+    };
+
+    log::info!("Transaction history: {} entries", filtered.len());
+    Ok(filtered)
+}
+
+// Fee a wallet transaction paid, as a positive amount. gettransaction reports it (negative) when the
+// wallet funded the inputs; for shielded spends it's often missing, so it's derived from the raw tx:
+// fee = transparent in + valueBalance - transparent out.
+pub async fn fetch_transaction_fee(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    txid: &str,
+) -> Result<f64, VerusRpcError> {
+    let tx: Value = make_rpc_call(rpc_user, rpc_pass, rpc_host, rpc_port, "gettransaction", vec![json!(txid)]).await?;
+    if let Some(fee) = tx["fee"].as_f64() {
+        log::debug!("Fee for {} from gettransaction: {}", txid, fee);
+        return Ok(fee.abs());
+    }
+
+    let raw: Value = make_rpc_call(rpc_user, rpc_pass, rpc_host, rpc_port, "getrawtransaction", vec![json!(txid), json!(1)]).await?;
+    let mut transparent_in = 0.0;
+    for vin in raw["vin"].as_array().map(Vec::as_slice).unwrap_or(&[]) {
+        // Input values are only reported when the daemon has address/spent indexes
+        transparent_in += vin["value"].as_f64().ok_or_else(|| {
+            VerusRpcError::ParseError(format!("fee for {} unavailable: transparent input without a value", txid))
+        })?;
+    }
+    let transparent_out: f64 = raw["vout"]
+        .as_array()
+        .map(|outs| outs.iter().filter_map(|out| out["value"].as_f64()).sum())
+        .unwrap_or(0.0);
+    let value_balance = raw["valueBalance"].as_f64().unwrap_or(0.0);
+
+    let fee = ((transparent_in + value_balance - transparent_out) * 100_000_000.0).round() / 100_000_000.0;
+    if fee < 0.0 {
+        return Err(VerusRpcError::ParseError(format!("fee for {} came out negative ({})", txid, fee)));
+    }
+    log::debug!("Fee for {} from raw tx: {}", txid, fee);
+    Ok(fee)
+}
+
+// One currency moved by a reserve transfer output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReserveTransfer {
+    pub vout: u32,
+    pub destination: Option<String>, // Destination address, if the daemon decoded one
+    pub currency: String,            // Currency i-address
+    pub amount: f64,
+    pub flags: u32,
+    pub destination_currency: Option<String>, // Currency to convert into / export to
+}
+
+// Reserve transfer outputs of a transaction (getrawtransaction verbose). Outputs without a
+// scriptPubKey.reservetransfer object are skipped.
+pub async fn fetch_reserve_transfers(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    txid: &str,
+) -> Result<Vec<ReserveTransfer>, VerusRpcError> {
+    let raw: Value = make_rpc_call(rpc_user, rpc_pass, rpc_host, rpc_port, "getrawtransaction", vec![json!(txid), json!(1)]).await?;
+
+    let mut transfers = Vec::new();
+    for (index, out) in raw["vout"].as_array().map(Vec::as_slice).unwrap_or(&[]).iter().enumerate() {
+        let Some(transfer) = out["scriptPubKey"].get("reservetransfer") else {
+            continue;
+        };
+        let vout = out["n"].as_u64().unwrap_or(index as u64) as u32;
+        let destination = transfer["destination"]["address"].as_str().map(str::to_string);
+        let flags = transfer["flags"].as_u64().unwrap_or(0) as u32;
+        let destination_currency = transfer["destinationcurrencyid"].as_str().map(str::to_string);
+        let Some(values) = transfer["currencyvalues"].as_object() else {
+            log::warn!("Reserve transfer {}:{} has no currencyvalues, skipping", txid, vout);
+            continue;
+        };
+        for (currency, amount) in values {
+            transfers.push(ReserveTransfer {
+                vout,
+                destination: destination.clone(),
+                currency: currency.clone(),
+                amount: amount.as_f64().unwrap_or(0.0),
+                flags,
+                destination_currency: destination_currency.clone(),
+            });
+        }
+    }
+
+    log::debug!("{} reserve transfer entries in {}", transfers.len(), txid);
+    Ok(transfers)
+}
+
+// Format a unix timestamp as ISO-8601 UTC (e.g. 2024-05-01T12:34:56Z)
+fn format_iso8601(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Civil-from-days (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, rem / 3_600, (rem % 3_600) / 60, rem % 60
+    )
+}
+
+// Quote a CSV field when it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 // NEW function to get wallet info including balances and reserves
 pub async fn fetch_wallet_info(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
 ) -> Result<WalletInfo, VerusRpcError> {
     log::info!("Fetching wallet info including balances and reserves...");
@@ -225,6 +1242,7 @@ pub async fn fetch_wallet_info(
     let response: Value = make_rpc_call(
         &rpc_user,
         &rpc_pass,
+        &rpc_host,
         rpc_port,
         "getwalletinfo",
         vec![],
@@ -260,6 +1278,10 @@ pub async fn fetch_wallet_info(
         unconfirmed_balance,
         reserve_balance,
         paytxfee,
+        immature_balance: response["immature_balance"].as_f64(),
+        txcount: response["txcount"].as_u64(),
+        keypoololdest: response["keypoololdest"].as_u64(),
+        unlocked_until: response["unlocked_until"].as_u64(),
     };
 
     log::info!(
@@ -276,6 +1298,7 @@ pub async fn fetch_wallet_info(
 pub async fn fetch_wallet_addresses(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
 ) -> Result<Vec<String>, VerusRpcError> {
     log::info!("Fetching wallet addresses...");
@@ -284,6 +1307,7 @@ pub async fn fetch_wallet_addresses(
     let addresses: Vec<String> = make_rpc_call(
         &rpc_user,
         &rpc_pass,
+        &rpc_host,
         rpc_port,
         "getaddressesbyaccount",
         vec![json!("")],
@@ -295,61 +1319,189 @@ pub async fn fetch_wallet_addresses(
     Ok(addresses)
 }
 
+// Per-address balances from getcurrencybalance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencyBalances {
+    pub by_id: HashMap<String, f64>,   // Keyed by currency i-address, as the daemon reports it
+    pub by_name: HashMap<String, f64>, // Keyed by currency name; empty unless names were resolved
+}
+
 // NEW function to get currency balances for a specific address
 pub async fn fetch_address_currency_balances(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
     address: String,
-) -> Result<HashMap<String, f64>, VerusRpcError> {
-    log::info!("Fetching currency balances for address: {}", address);
+    minconf: Option<u32>,
+    resolve_names: bool,
+) -> Result<CurrencyBalances, VerusRpcError> {
+    log::info!("Fetching currency balances for address: {} (minconf {:?})", address, minconf);
     
-    // Call getcurrencybalance for the address
+    // getcurrencybalance "address" minconf friendlynames: ask for i-address keys and resolve names ourselves
     let balances: Value = make_rpc_call(
         &rpc_user,
         &rpc_pass,
+        &rpc_host,
         rpc_port,
         "getcurrencybalance",
-        vec![json!(address)],
+        vec![json!(address), json!(minconf.unwrap_or(1)), json!(false)],
     ).await?;
 
     log::debug!("Raw currency balance response: {:?}", balances);
 
     // Parse the response into a HashMap
-    let mut balance_map = HashMap::new();
+    let mut by_id = HashMap::new();
     if let Some(balances_obj) = balances.as_object() {
         for (currency, amount) in balances_obj {
             if let Some(amount_f64) = amount.as_f64() {
-                balance_map.insert(currency.clone(), amount_f64);
+                by_id.insert(currency.clone(), amount_f64);
             }
         }
     }
 
+    let mut by_name = HashMap::new();
+    if resolve_names && !by_id.is_empty() {
+        let ids: Vec<String> = by_id.keys().cloned().collect();
+        let names = crate::currency_names::fetch_currency_names(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &ids).await;
+        for (id, amount) in &by_id {
+            let name = names.get(id).cloned().unwrap_or_else(|| id.clone());
+            by_name.insert(name, *amount);
+        }
+    }
+
     log::info!("Found {} currencies for address {}: {:?}", 
-               balance_map.len(), address, balance_map);
+               by_id.len(), address, if resolve_names { &by_name } else { &by_id });
 
-    Ok(balance_map)
+    Ok(CurrencyBalances { by_id, by_name })
+}
+
+// Fail early with InsufficientFunds instead of letting the daemon reject the send with a cryptic error.
+// `fee` only counts towards the requirement when the caller says it is paid in the sent currency.
+pub fn ensure_sufficient_funds(available: f64, amount: f64, fee: f64, fee_in_same_currency: bool) -> Result<(), VerusRpcError> {
+    let required = amount + if fee_in_same_currency { fee } else { 0.0 };
+    let required = (required * 100_000_000.0).round() / 100_000_000.0;
+    // Compare in satoshis so float noise can't reject an exact-balance send
+    if (available * 100_000_000.0).round() < (required * 100_000_000.0).round() {
+        log::warn!("Insufficient funds: {} available, {} required", available, required);
+        return Err(VerusRpcError::InsufficientFunds { available, required });
+    }
+    Ok(())
 }
 
 // NEW function to initiate currency conversion
+#[allow(clippy::too_many_arguments)]
 pub async fn initiate_currency_conversion(
     rpc_user: String,
     rpc_pass: String,
+    rpc_host: String,
     rpc_port: u16,
     from_address: String,
     to_address: String,
     from_currency: String,
     to_currency: String,
     amount: f64,
+    min_output: Option<f64>,
+    fee: Option<f64>,
 ) -> Result<String, VerusRpcError> {
     log::info!(
         "Initiating currency conversion: {} {} from {} to {} at {}",
         amount, from_currency, from_address, to_address, to_currency
     );
 
+    let params = prepare_currency_conversion(
+        &rpc_user, &rpc_pass, &rpc_host, rpc_port, &from_address, &to_address, &from_currency, &to_currency, amount, min_output, fee,
+    )
+    .await?
+    .params;
+
+    // Make the RPC call
+    let txid: String = make_rpc_call(
+        &rpc_user,
+        &rpc_pass,
+        &rpc_host,
+        rpc_port,
+        "sendcurrency",
+        params,
+    ).await?;
+
+    log::info!("Currency conversion initiated successfully, txid: {}", txid);
+
+    Ok(txid)
+}
+
+// sendcurrency params for a conversion, after the address, slippage, balance and fee checks
+#[derive(Debug, Clone)]
+struct PreparedConversion {
+    params: Vec<Value>,
+    estimated_output: Option<f64>, // Set when the slippage check ran
+}
+
+// Validate a conversion and build its sendcurrency params without sending anything
+#[allow(clippy::too_many_arguments)]
+async fn prepare_currency_conversion(
+    rpc_user: &str,
+    rpc_pass: &str,
+    rpc_host: &str,
+    rpc_port: u16,
+    from_address: &str,
+    to_address: &str,
+    from_currency: &str,
+    to_currency: &str,
+    amount: f64,
+    min_output: Option<f64>,
+    fee: Option<f64>,
+) -> Result<PreparedConversion, VerusRpcError> {
+    let (rpc_user, rpc_pass, rpc_host) = (rpc_user.to_string(), rpc_pass.to_string(), rpc_host.to_string());
+    let (from_address, to_address) = (from_address.to_string(), to_address.to_string());
+    let (from_currency, to_currency) = (from_currency.to_string(), to_currency.to_string());
+
+    ensure_valid_address(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &from_address).await?;
+    ensure_valid_address(&rpc_user, &rpc_pass, &rpc_host, rpc_port, &to_address).await?;
+
     // Round amount to 8 decimal places to avoid RPC errors with high precision floats
     let rounded_amount = (amount * 100_000_000.0).round() / 100_000_000.0;
 
+    // Slippage guard: re-estimate right before sending and abort if the price moved too far
+    let mut estimated_output = None;
+    if let Some(min_output) = min_output {
+        let request = EstimateConversionRequest {
+            currency: from_currency.clone(),
+            convertto: to_currency.clone(),
+            via: None,
+            amount: rounded_amount,
+        };
+        let estimated = estimate_conversion(rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port, request).await?;
+        log::info!(
+            "Slippage check: estimated {} {} vs minimum {} {}",
+            estimated, to_currency, min_output, to_currency
+        );
+        if estimated < min_output {
+            log::warn!("Aborting conversion: estimate {} below minimum {}", estimated, min_output);
+            return Err(VerusRpcError::SlippageExceeded { estimated, min_output });
+        }
+        estimated_output = Some(estimated);
+    }
+
+    // Pre-flight balance check (skipped for wildcard sources, which the daemon funds from many addresses)
+    if !from_address.contains('*') {
+        let balances = fetch_address_currency_balances(
+            rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port, from_address.clone(), None, true,
+        ).await?;
+        // from_currency may be a name or an i-address
+        let available = balances
+            .by_name
+            .iter()
+            .chain(balances.by_id.iter())
+            .find(|(currency, _)| currency.eq_ignore_ascii_case(&from_currency))
+            .map(|(_, amount)| *amount)
+            .unwrap_or(0.0);
+        // Fees are always paid in the chain's native currency
+        let native_currency = fetch_native_currency_name(&rpc_user, &rpc_pass, &rpc_host, rpc_port).await?;
+        let fee_in_same_currency = native_currency.eq_ignore_ascii_case(&from_currency);
+        ensure_sufficient_funds(available, rounded_amount, fee.unwrap_or(DEFAULT_TX_FEE), fee_in_same_currency)?;
+    }
+
     // Build the sendcurrency parameters as a direct JSON object.
     let amounts_param = json!([{
         "address": to_address,
@@ -358,25 +1510,45 @@ pub async fn initiate_currency_conversion(
         "convertto": to_currency
     }]);
 
-    let params = vec![
+    let mut params = vec![
         json!(from_address), // Can be "*" for wildcard
         amounts_param,       // Pass the JSON array directly
     ];
 
+    // Optional fee override: sendcurrency "from" [outputs] minconf feeamount
+    if let Some(fee) = fee {
+        let wallet_info = fetch_wallet_info(rpc_user.clone(), rpc_pass.clone(), rpc_host.clone(), rpc_port).await?;
+        // A wallet with no paytxfee set still pays the daemon default, so that is the floor
+        let minimum = wallet_info.paytxfee.max(DEFAULT_TX_FEE);
+        if fee < minimum {
+            log::warn!("Rejecting fee {} (minimum is {}, wallet paytxfee {})", fee, minimum, wallet_info.paytxfee);
+            return Err(VerusRpcError::FeeTooLow { fee, minimum });
+        }
+        let rounded_fee = (fee * 100_000_000.0).round() / 100_000_000.0;
+        log::info!("Using custom fee {} for sendcurrency", rounded_fee);
+        params.push(json!(1)); // minconf (daemon default)
+        params.push(json!(rounded_fee));
+    }
+
     log::debug!("sendcurrency params: {:?}", params);
 
-    // Make the RPC call
-    let txid: String = make_rpc_call(
-        &rpc_user,
-        &rpc_pass,
-        rpc_port,
-        "sendcurrency",
-        params,
-    ).await?;
+    Ok(PreparedConversion { params, estimated_output })
+}
 
-    log::info!("Currency conversion initiated successfully, txid: {}", txid);
+// What a dry-run send_currency_conversion would have submitted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversionDryRun {
+    pub method: String,        // Always "sendcurrency"
+    pub params: Vec<Value>,
+    pub estimated_output: f64, // Fresh estimateconversion for the same amount
+}
 
-    Ok(txid)
+// send_currency_conversion result: the txid as a bare string, or the dry-run payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConversionOutcome {
+    Sent(String),
+    DryRun(ConversionDryRun),
 }
 
 // Tauri command wrapper for estimate_conversion
@@ -387,10 +1559,9 @@ pub async fn estimate_currency_conversion(
     convert_to: String,
     via: Option<String>,
     amount: f64,
-) -> Result<f64, String> {
+) -> Result<f64, CommandError> {
     // Load credentials
-    let creds = crate::credentials::load_credentials(app).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+    let creds = crate::credentials::load_credentials(app).await?;
 
     let request = EstimateConversionRequest {
         currency,
@@ -399,36 +1570,81 @@ pub async fn estimate_currency_conversion(
         amount,
     };
 
-    estimate_conversion(creds.rpc_user, creds.rpc_pass, creds.rpc_port, request)
+    estimate_conversion(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, request)
         .await
-        .map_err(|e| format!("Conversion estimate failed: {}", e))
+        .map_err(CommandError::from)
 } 
 
+// Tauri command wrapper for estimate_conversion_quote (full quote for the conversion UI)
+#[tauri::command]
+pub async fn estimate_currency_conversion_quote(
+    app: tauri::AppHandle,
+    currency: String,
+    convert_to: String,
+    via: Option<String>,
+    amount: f64,
+) -> Result<EstimateConversionResponse, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    let request = EstimateConversionRequest {
+        currency,
+        convertto: convert_to,
+        via,
+        amount,
+    };
+
+    estimate_conversion_quote(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, request)
+        .await
+        .map_err(CommandError::from)
+}
+
+// Tauri command wrapper for estimate_required_input
+#[tauri::command]
+pub async fn estimate_conversion_input(
+    app: tauri::AppHandle,
+    currency: String,
+    convert_to: String,
+    desired_out: f64,
+    via: Option<String>,
+) -> Result<ConversionInputEstimate, CommandError> {
+    // Load credentials
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    let request = EstimateConversionRequest {
+        currency,
+        convertto: convert_to,
+        via,
+        amount: desired_out,
+    };
+
+    estimate_required_input(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, request)
+        .await
+        .map_err(CommandError::from)
+}
+
 // Tauri command wrapper for get_wallet_info
 #[tauri::command]
 pub async fn get_wallet_info(
     app: tauri::AppHandle,
-) -> Result<WalletInfo, String> {
+) -> Result<WalletInfo, CommandError> {
     // Load credentials
-    let creds = crate::credentials::load_credentials(app).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+    let creds = crate::credentials::load_credentials(app).await?;
 
-    fetch_wallet_info(creds.rpc_user, creds.rpc_pass, creds.rpc_port)
+    fetch_wallet_info(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port)
         .await
-        .map_err(|e| format!("Failed to get wallet info: {}", e))
+        .map_err(CommandError::from)
 } 
 
 // NEW Tauri command to get wallet addresses
 #[tauri::command]
 pub async fn get_wallet_addresses(
     app: tauri::AppHandle,
-) -> Result<Vec<String>, String> {
-    let creds = crate::credentials::load_credentials(app).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+) -> Result<Vec<String>, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
 
-    fetch_wallet_addresses(creds.rpc_user, creds.rpc_pass, creds.rpc_port)
+    fetch_wallet_addresses(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port)
         .await
-        .map_err(|e| format!("Failed to get wallet addresses: {}", e))
+        .map_err(CommandError::from)
 }
 
 // NEW Tauri command to get address currency balances
@@ -436,13 +1652,25 @@ pub async fn get_wallet_addresses(
 pub async fn get_address_currency_balances(
     app: tauri::AppHandle,
     address: String,
-) -> Result<HashMap<String, f64>, String> {
-    let creds = crate::credentials::load_credentials(app).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+    minconf: Option<u32>,
+) -> Result<HashMap<String, f64>, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    // Keyed by currency name, as the conversion UI expects
+    fetch_address_currency_balances(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, address, minconf, true)
+        .await
+        .map(|balances| balances.by_name)
+        .map_err(CommandError::from)
+}
+
+/// Check an address (R, z, i or name@) against the daemon
+#[tauri::command]
+pub async fn validate_address(app: tauri::AppHandle, address: String) -> Result<AddressValidation, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
 
-    fetch_address_currency_balances(creds.rpc_user, creds.rpc_pass, creds.rpc_port, address)
+    fetch_address_validation(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &address)
         .await
-        .map_err(|e| format!("Failed to get address currency balances: {}", e))
+        .map_err(CommandError::from)
 }
 
 // NEW Tauri command to get balance of a specific currency for a specific address
@@ -451,18 +1679,20 @@ pub async fn get_address_currency_balance(
     app: tauri::AppHandle,
     address: String,
     currency: String,
-) -> Result<f64, String> {
-    let creds = crate::credentials::load_credentials(app).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+    minconf: Option<u32>,
+) -> Result<f64, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
 
-    let balances = fetch_address_currency_balances(creds.rpc_user, creds.rpc_pass, creds.rpc_port, address)
+    let balances = fetch_address_currency_balances(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, address, minconf, true)
         .await
-        .map_err(|e| format!("Failed to get address currency balances: {}", e))?;
+        .map_err(CommandError::from)?;
 
-    Ok(balances.get(&currency).copied().unwrap_or(0.0))
+    // Accept either the currency name or its i-address
+    Ok(balances.by_name.get(&currency).or_else(|| balances.by_id.get(&currency)).copied().unwrap_or(0.0))
 }
 
 // NEW Tauri command to send currency conversion
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn send_currency_conversion(
     app: tauri::AppHandle,
@@ -471,37 +1701,199 @@ pub async fn send_currency_conversion(
     from_currency: String,
     to_currency: String,
     amount: f64,
-) -> Result<String, String> {
-    let creds = crate::credentials::load_credentials(app).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+    expected_output: Option<f64>,
+    max_slippage_pct: Option<f64>,
+    fee: Option<f64>,
+    dry_run: Option<bool>,
+) -> Result<ConversionOutcome, CommandError> {
+    // Minimum acceptable output = the estimate the user saw, less the allowed slippage
+    let min_output = match (expected_output, max_slippage_pct) {
+        (Some(expected), Some(pct)) => {
+            if !(0.0..100.0).contains(&pct) {
+                return Err(CommandError::invalid_argument(format!("Invalid max_slippage_pct: {}", pct)));
+            }
+            Some(expected * (1.0 - pct / 100.0))
+        }
+        (None, Some(_)) => return Err(CommandError::invalid_argument("max_slippage_pct requires expected_output")),
+        _ => None,
+    };
+
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    if dry_run.unwrap_or(false) {
+        let prepared = prepare_currency_conversion(
+            &creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port,
+            &from_address, &to_address, &from_currency, &to_currency, amount, min_output, fee,
+        )
+        .await
+        .map_err(CommandError::from)?;
+        let estimated_output = match prepared.estimated_output {
+            Some(estimated) => estimated,
+            None => {
+                let request = EstimateConversionRequest {
+                    currency: from_currency.clone(),
+                    convertto: to_currency.clone(),
+                    via: None,
+                    amount: (amount * 100_000_000.0).round() / 100_000_000.0,
+                };
+                estimate_conversion(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, request)
+                    .await
+                    .map_err(CommandError::from)?
+            }
+        };
+        log::info!(
+            "Dry run: {} {} -> {} (estimated {}), no transaction was sent",
+            amount, from_currency, to_currency, estimated_output
+        );
+        return Ok(ConversionOutcome::DryRun(ConversionDryRun {
+            method: "sendcurrency".to_string(),
+            params: prepared.params,
+            estimated_output,
+        }));
+    }
 
     initiate_currency_conversion(
         creds.rpc_user,
         creds.rpc_pass,
+        creds.rpc_host,
         creds.rpc_port,
         from_address,
         to_address,
         from_currency,
         to_currency,
-        amount
+        amount,
+        min_output,
+        fee,
     )
     .await
-    .map_err(|e| format!("Failed to send currency conversion: {}", e))
+    .map(ConversionOutcome::Sent)
+    .map_err(CommandError::from)
+}
+
+
+
+// --- Wallet encryption ---
+
+// walletpassphrase error codes
+const RPC_WALLET_PASSPHRASE_INCORRECT: i32 = -14;
+const RPC_WALLET_WRONG_ENC_STATE: i32 = -15;
+
+// Give the passphrase / encryption-state codes their own variants so the UI can tell them apart
+fn wallet_passphrase_error(error: VerusRpcError) -> VerusRpcError {
+    match error {
+        VerusRpcError::Rpc { code: RPC_WALLET_PASSPHRASE_INCORRECT, .. } => VerusRpcError::WrongPassphrase,
+        VerusRpcError::Rpc { code: RPC_WALLET_WRONG_ENC_STATE, .. } => VerusRpcError::WalletNotEncrypted,
+        other => other,
+    }
+}
+
+/// Unlock an encrypted wallet for timeout_secs (call after a command fails with WalletLocked).
+/// SECURITY: the passphrase is only forwarded to the daemon and never logged.
+#[tauri::command]
+pub async fn unlock_wallet(
+    app: tauri::AppHandle,
+    passphrase: String,
+    timeout_secs: u64,
+) -> Result<(), CommandError> {
+    log::info!("Unlocking wallet for {}s", timeout_secs);
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    make_rpc_call::<()>(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "walletpassphrase", vec![json!(passphrase), json!(timeout_secs)])
+        .await
+        .map_err(|e| CommandError::rpc_failure("walletpassphrase", wallet_passphrase_error(e)))
+}
+
+/// Lock the wallet again (drops the decryption key from daemon memory)
+#[tauri::command]
+pub async fn lock_wallet(app: tauri::AppHandle) -> Result<(), CommandError> {
+    log::info!("Locking wallet");
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    make_rpc_call::<()>(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, "walletlock", vec![])
+        .await
+        .map_err(|e| CommandError::rpc_failure("walletlock", wallet_passphrase_error(e)))
 }
 
+// --- Key import ---
+
+// RPC_INVALID_ADDRESS_OR_KEY: z_importkey couldn't decode the key
+const RPC_INVALID_ADDRESS_OR_KEY: i32 = -5;
 
+// How long to wait for z_importkey before assuming it's busy rescanning
+const IMPORT_KEY_TIMEOUT: Duration = Duration::from_secs(20);
+
+// Sapling extended spending keys (mainnet / testnet) and legacy Sprout keys
+const Z_KEY_PREFIXES: &[&str] = &["secret-extended-key-main1", "secret-extended-key-test1", "SK"];
+
+// z_importkey's rescan argument, serialized as the daemon expects it
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RescanMode {
+    Yes,
+    No,
+    WhenKeyIsNew,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ZKeyImport {
+    pub address: Option<String>, // Reported by newer daemons only
+    pub rescanning: bool,        // The daemon is still rescanning - poll get_sync_status / z_getbalance
+}
+
+// Import a shielded spending key. A rescan can take hours, so the call is given IMPORT_KEY_TIMEOUT:
+// a timeout at that point means the key was accepted and the daemon is rescanning in the background.
+pub async fn import_z_key(
+    rpc_user: String,
+    rpc_pass: String,
+    rpc_host: String,
+    rpc_port: u16,
+    z_private_key: String,
+    rescan: RescanMode,
+) -> Result<ZKeyImport, VerusRpcError> {
+    let z_private_key = z_private_key.trim().to_string();
+    if !Z_KEY_PREFIXES.iter().any(|prefix| z_private_key.starts_with(prefix)) {
+        log::warn!("import_z_key: rejecting key with unrecognised format");
+        return Err(VerusRpcError::InvalidFormat);
+    }
+    crate::log_redaction::register_secret(&z_private_key);
+    log::info!("Importing z-key {} (rescan: {:?})", crate::log_redaction::key_fingerprint(&z_private_key), rescan);
+
+    let result = make_rpc_call_with_timeout::<Value>(
+        &rpc_user,
+        &rpc_pass,
+        &rpc_host,
+        rpc_port,
+        "z_importkey",
+        vec![json!(z_private_key), json!(rescan)],
+        IMPORT_KEY_TIMEOUT,
+    )
+    .await;
+
+    match result {
+        Ok(value) => {
+            let address = value.get("address").and_then(|a| a.as_str()).map(str::to_string);
+            log::info!("z_importkey completed (address: {:?})", address);
+            Ok(ZKeyImport { address, rescanning: false })
+        }
+        Err(VerusRpcError::Timeout) if rescan != RescanMode::No => {
+            log::info!("z_importkey still running after {:?}, assuming rescan in progress", IMPORT_KEY_TIMEOUT);
+            Ok(ZKeyImport { address: None, rescanning: true })
+        }
+        Err(VerusRpcError::Rpc { code: RPC_INVALID_ADDRESS_OR_KEY, .. }) => Err(VerusRpcError::InvalidFormat),
+        Err(e) => Err(e),
+    }
+}
 
 // NEW Tauri command to get current block height  
 #[tauri::command]
 pub async fn get_current_block_height(
     app: tauri::AppHandle,
-) -> Result<u64, String> {
-    let creds = crate::credentials::load_credentials(app).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+) -> Result<u64, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
 
-    connect_and_get_block_height(creds.rpc_user, creds.rpc_pass, creds.rpc_port)
+    connect_and_get_block_height(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port)
         .await
-        .map_err(|e| format!("Failed to get current block height: {}", e))
+        .map_err(CommandError::from)
 } 
 
 // NEW: Wait for block height to increase by N blocks (polling)
@@ -511,9 +1903,10 @@ pub async fn wait_for_block_increase(
     blocks: u64,
     interval_secs: u64,
     timeout_secs: u64,
-) -> Result<bool, String> {
-    let creds = crate::credentials::load_credentials(app.clone()).await
-        .map_err(|e| format!("Failed to load credentials: {}", e))?;
+    cancel_key: Option<String>,
+) -> Result<bool, CommandError> {
+    let creds = crate::credentials::load_credentials(app.clone()).await?;
+    let cancel = WaitCancel::register(cancel_key);
 
     log::info!(
         "wait_for_block_increase: blocks={}, interval={}s, timeout={}s",
@@ -523,43 +1916,178 @@ pub async fn wait_for_block_increase(
     let start_height: u64 = connect_and_get_block_height(
         creds.rpc_user.clone(),
         creds.rpc_pass.clone(),
+        creds.rpc_host.clone(),
         creds.rpc_port,
     )
     .await
-    .map_err(|e| format!("Failed to get start height: {}", e))?;
+    .map_err(CommandError::from)?;
 
     log::info!("wait_for_block_increase: start_height={}", start_height);
 
     let target_height = start_height.saturating_add(blocks);
-    let start_time = std::time::Instant::now();
+    let creds = &creds;
 
-    loop {
-        let height = connect_and_get_block_height(
-            creds.rpc_user.clone(),
-            creds.rpc_pass.clone(),
+    poll_until("wait_for_block_increase", interval_secs, timeout_secs, &cancel, |attempt_timeout| async move {
+        let height: u64 = match make_rpc_call_with_timeout(
+            &creds.rpc_user,
+            &creds.rpc_pass,
+            &creds.rpc_host,
             creds.rpc_port,
+            "getblockcount",
+            vec![],
+            attempt_timeout,
         )
         .await
-        .map_err(|e| format!("Failed to poll height: {}", e))?;
+        {
+            Ok(height) => height,
+            Err(VerusRpcError::Timeout) => {
+                // One slow poll shouldn't abort the wait - try again next interval
+                log::warn!("wait_for_block_increase: poll timed out, retrying");
+                return Ok(false);
+            }
+            Err(e) => return Err(CommandError::from(e)),
+        };
 
         log::debug!("wait_for_block_increase: current_height={}, target_height={}", height, target_height);
-
         if height >= target_height {
             log::info!("wait_for_block_increase: reached target height {}", height);
-            return Ok(true);
         }
+        Ok(height >= target_height)
+    })
+    .await
+}
+
+/// Network and native currency of the connected daemon
+#[tauri::command]
+pub async fn get_chain_info(app: tauri::AppHandle) -> Result<ChainInfo, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
 
-        if start_time.elapsed() >= Duration::from_secs(timeout_secs) {
-            log::warn!(
-                "wait_for_block_increase: timeout after {}s, current_height={}, target_height={}",
-                timeout_secs,
-                height,
-                target_height
-            );
-            return Ok(false);
+    fetch_chain_info(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port)
+        .await
+        .map_err(CommandError::from)
+}
+
+// NEW Tauri command to get the daemon sync status
+#[tauri::command]
+pub async fn get_sync_status(app: tauri::AppHandle) -> Result<SyncStatus, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    fetch_sync_status(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, DEFAULT_RPC_TIMEOUT)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Peer count and sync state for the diagnostics panel
+#[tauri::command]
+pub async fn get_network_health(app: tauri::AppHandle) -> Result<NetworkHealth, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    fetch_network_health(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port)
+        .await
+        .map_err(CommandError::from)
+}
+
+// NEW: Wait for the daemon to catch up with the chain tip (polling)
+#[tauri::command]
+pub async fn wait_for_sync(
+    app: tauri::AppHandle,
+    interval_secs: u64,
+    timeout_secs: u64,
+    cancel_key: Option<String>,
+) -> Result<bool, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+    let cancel = WaitCancel::register(cancel_key);
+
+    log::info!("wait_for_sync: interval={}s, timeout={}s", interval_secs, timeout_secs);
+    let creds = &creds;
+
+    poll_until("wait_for_sync", interval_secs, timeout_secs, &cancel, |attempt_timeout| async move {
+        match fetch_sync_status(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, attempt_timeout).await {
+            Ok(status) if status.is_synced => {
+                log::info!("wait_for_sync: synced at height {}", status.blocks);
+                Ok(true)
+            }
+            Ok(status) => {
+                log::debug!(
+                    "wait_for_sync: blocks={}, headers={}, progress={:.4}",
+                    status.blocks, status.headers, status.verification_progress
+                );
+                Ok(false)
+            }
+            Err(VerusRpcError::Timeout) => {
+                // One slow poll shouldn't abort the wait - try again next interval
+                log::warn!("wait_for_sync: poll timed out, retrying");
+                Ok(false)
+            }
+            Err(e) => Err(CommandError::from(e)),
         }
+    })
+    .await
+}
 
-        sleep(Duration::from_secs(interval_secs)).await;
-    }
+/// Fee charged by a wallet transaction (positive amount)
+#[tauri::command]
+pub async fn get_transaction_fee(app: tauri::AppHandle, txid: String) -> Result<f64, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    fetch_transaction_fee(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &txid)
+        .await
+        .map_err(|e| CommandError::with_context(format!("Failed to get fee for {}", txid), e))
 }
 
+// Tauri command: reserve transfers carried by a transaction (empty when it has none)
+#[tauri::command]
+pub async fn get_transaction_reserve_transfers(app: tauri::AppHandle, txid: String) -> Result<Vec<ReserveTransfer>, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    fetch_reserve_transfers(&creds.rpc_user, &creds.rpc_pass, &creds.rpc_host, creds.rpc_port, &txid)
+        .await
+        .map_err(|e| CommandError::with_context(format!("Failed to read reserve transfers of {}", txid), e))
+}
+
+// NEW Tauri command to get the wallet transaction history
+#[tauri::command]
+pub async fn get_transaction_history(
+    app: tauri::AppHandle,
+    count: u32,
+    skip: u32,
+    category: Option<String>,
+) -> Result<Vec<WalletTransaction>, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    list_wallet_transactions(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, count, skip, category)
+        .await
+        .map_err(CommandError::from)
+}
+
+// NEW Tauri command to export the transaction history as CSV; returns the number of rows written
+#[tauri::command]
+pub async fn export_transactions_csv(
+    app: tauri::AppHandle,
+    path: String,
+    count: u32,
+) -> Result<usize, CommandError> {
+    let creds = crate::credentials::load_credentials(app).await?;
+
+    let transactions = list_wallet_transactions(creds.rpc_user, creds.rpc_pass, creds.rpc_host, creds.rpc_port, count, 0, None)
+        .await
+        .map_err(CommandError::from)?;
+
+    let mut csv = String::from("timestamp,category,amount,fee,confirmations,txid,address\n");
+    for tx in &transactions {
+        csv.push_str(&format!(
+            "{},{},{:.8},{},{},{},{}\n",
+            format_iso8601(tx.time),
+            csv_field(&tx.category),
+            tx.amount,
+            tx.fee.map(|f| format!("{:.8}", f)).unwrap_or_default(),
+            tx.confirmations,
+            csv_field(&tx.txid),
+            csv_field(tx.address.as_deref().unwrap_or("")),
+        ));
+    }
+
+    std::fs::write(&path, csv).map_err(|e| CommandError::new("IO", format!("Failed to write CSV to {}: {}", path, e)))?;
+    log::info!("Exported {} transactions to {}", transactions.len(), path);
+    Ok(transactions.len())
+}
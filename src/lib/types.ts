@@ -9,12 +9,55 @@
 // - MAJOR: Added blockchain detection types for new automatic onboarding system
 // - Added UtxoInfo type for Fast Messages feature
 // - Added NamespaceOption type for VerusID registration namespace selection
+// - Added optional rpc_host and TLS flags to Credentials
+// - Added credentials_source / rpc_datadir for .cookie authentication
+// - Added dust_utxos and min_usable to UtxoInfo (configurable usable threshold)
+// - Added WalletUtxoSummary type (per-address UtxoInfo plus combined total)
+// - Added SyncStatus type (daemon sync progress from get_sync_status)
+// - Added TxStatus type (get_transaction_status)
+// - Added pending_balance to FormattedIdentity
+// - Added AddressKind and NewAddress (get_new_address / get_new_private_address)
+// - Added RescanMode and ZKeyImport (import_z_key)
+// - Added CommandError ({ code, message } errors from the backend)
+// - Added LoginIdentityPage (get_login_identities_page)
+// - Added ChainInfo (get_chain_info)
+// - Added NameReservation and NameCommitmentResponse (register_name_commitment)
+// - NamespaceOption.fee_currency_resolved (false = fee currency is an unverified fallback)
+// - Added MessageStoreStatus (unlock_message_store / get_message_store_status)
+// - Added NetworkHealth (get_network_health)
+// - Added RegistrationOptions (get_registration_options)
+// - Added SubIdRegistrationPlan (prepare_subid_registration)
+// - Added optional max_rpc_per_sec to Credentials
+// - Added MessageCapacity (estimate_message_capacity)
+// - Added ConversionDryRun (send_currency_conversion with dryRun: true)
+// - Added AddressDelta (get_address_deltas)
+// - Added ProfileSummary (list_profiles)
+// - Added ReserveTransfer (get_transaction_reserve_transfers)
+// - Added ConversionQuote (estimate_currency_conversion_quote)
+// - TxStatus.conflicted (negative confirmations)
 
 // Credentials for Verus RPC connection
 export interface Credentials {
     rpc_user: string;
     rpc_pass: string;
     rpc_port: number; // NEW: Port support for different blockchains
+    rpc_host?: string; // Daemon host, defaults to 127.0.0.1 on the backend
+    rpc_use_tls?: boolean; // Connect over https://
+    rpc_accept_invalid_certs?: boolean; // Accept self-signed certificates (TLS only)
+    credentials_source?: 'Manual' | 'Cookie'; // How rpc_user/rpc_pass were obtained
+    rpc_datadir?: string | null; // Daemon data directory holding .cookie (cookie auth only)
+    max_rpc_per_sec?: number | null; // Client-side RPC rate limit (unset = unlimited)
+}
+
+// Saved daemon profile as returned by list_profiles (no password); index is the id for activate/delete
+export interface ProfileSummary {
+    index: number;
+    name: string;
+    rpc_host: string;
+    rpc_port: number;
+    rpc_user: string;
+    rpc_use_tls: boolean;
+    active: boolean;
 }
 
 // Structure for Verus identity details returned from backend
@@ -23,6 +66,7 @@ export interface FormattedIdentity {
     i_address: string;
     private_address: string; // Now required (not nullable)
     balance: number | null; // Private balance (null while loading or on error)
+    pending_balance?: number | null; // 0-conf balance incl. confirmed (null while loading or on error)
 }
 
 // Generic structure for dropdown options
@@ -75,10 +119,148 @@ export type Conversation = {
 // NEW: UTXO information structure for Fast Messages feature
 export interface UtxoInfo {
     total_utxos: number;           // Total count including dust
-    usable_utxos: number;          // Count with amount >= 0.0001 (Fast Messages count)
+    usable_utxos: number;          // Count with amount >= min_usable (Fast Messages count)
+    dust_utxos: number;            // Count below min_usable (total - usable)
     total_spendable_value: number; // Sum of usable UTXOs only
     largest_utxo: number;          // Largest single UTXO amount
-    smallest_utxo: number;         // Smallest usable UTXO amount (>= 0.0001)
+    smallest_utxo: number;         // Smallest usable UTXO amount (>= min_usable)
+    min_usable: number;            // Threshold used for the counts above (default 0.0001)
+}
+
+// UTXO info across several identity addresses
+export interface WalletUtxoSummary {
+    per_address: Record<string, UtxoInfo>; // Keyed by z-address
+    total: UtxoInfo;                       // All addresses combined
+}
+
+// send_currency_conversion with dryRun: true - the sendcurrency call that would have been made
+export interface ConversionDryRun {
+    method: string; // "sendcurrency"
+    params: unknown[];
+    estimated_output: number;
+}
+
+// One currency moved by a reserve transfer output (get_transaction_reserve_transfers)
+export interface ReserveTransfer {
+    vout: number;
+    destination: string | null;
+    currency: string; // i-address
+    amount: number;
+    flags: number;
+    destination_currency: string | null;
+}
+
+// Full conversion estimate from estimate_currency_conversion_quote (fee fields null when not reported)
+export interface ConversionQuote {
+    estimatedcurrencyout: number;
+    netinputamount: number | null;
+    inputcurrencyfees: number | null;
+    conversionfees: number | null;
+    effective_price: number | null; // Output per unit of the requested input amount
+}
+
+// One transparent-address balance change from get_address_deltas (positive satoshis = received)
+export interface AddressDelta {
+    address: string;
+    satoshis: number;
+    txid: string;
+    height: number;
+}
+
+// How many messages an address can send, and which constraint binds
+export interface MessageCapacity {
+    max_messages: number;
+    utxo_limited: number;              // Usable UTXOs (one per message)
+    balance_limited: number;           // Spendable balance / fee_per_message
+    limited_by: 'utxos' | 'balance';
+    fee_per_message: number;
+    spendable_balance: number;
+}
+
+// Daemon sync state returned by get_sync_status
+export interface SyncStatus {
+    blocks: number;
+    headers: number;
+    verification_progress: number; // 0..1
+    is_synced: boolean;            // progress > 0.9999
+}
+
+// Transaction status returned by get_transaction_status
+export interface TxStatus {
+    confirmations: number;
+    in_mempool: boolean;  // Known but unconfirmed (false with 0 confirmations and not conflicted = unknown txid)
+    conflicted: boolean;  // Negative confirmations: a conflicting tx was mined, this one never will be
+    height: number | null;
+    time: number | null;  // Unix seconds
+}
+
+export type AddressKind = 'transparent' | 'sapling' | 'sprout' | 'identity' | 'unknown';
+
+// Freshly generated wallet address
+export interface NewAddress {
+    address: string;
+    kind: AddressKind;
+    in_wallet: boolean; // false = the daemon didn't keep the key; don't fund it
+}
+
+// z_importkey rescan option
+export type RescanMode = 'yes' | 'no' | 'whenkeyisnew';
+
+export interface ZKeyImport {
+    address: string | null;
+    rescanning: boolean; // Rescan still running; poll get_sync_status
+}
+
+// Network of the connected daemon
+export interface ChainInfo {
+    chain: string;             // "main" | "test"
+    blocks: number;
+    protocol_version: number;
+    subversion: string | null;
+    name: string;              // Native currency, e.g. "VRSC" / "VRSCTEST"
+}
+
+// namereservation from registernamecommitment; pass back unchanged in the registeridentity bundle
+export interface NameReservation {
+    name: string;
+    salt: string;
+    referral: string; // "" when no referral
+    parent?: string;
+    nameid: string;
+    [extra: string]: unknown; // Other daemon fields (e.g. version) are preserved
+}
+
+export interface NameCommitmentResponse {
+    txid: string;
+    namereservation: NameReservation;
+}
+
+// Local message store encryption state
+export interface MessageStoreStatus {
+    encrypted: boolean; // false = stored as plaintext (no passphrase set)
+    unlocked: boolean;  // Key is held in memory for this session
+}
+
+// Daemon connectivity for diagnostics
+export interface NetworkHealth {
+    connections: number;
+    no_peers: boolean;      // true = "no peers - check your connection"
+    synced: boolean;
+    blocks: number;
+    headers: number;
+    headers_behind: number;
+}
+
+// One page of login identities
+export interface LoginIdentityPage {
+    items: FormattedIdentity[];
+    total: number; // All qualifying identities
+}
+
+// Structured backend error; code is stable (NOT_FOUND, UNAUTHORIZED, TIMEOUT, WALLET_LOCKED, ...)
+export interface CommandError {
+    code: string;
+    message: string;
 }
 
 // NEW: Blockchain detection types
@@ -100,6 +282,24 @@ export interface ParallelDetectionResult {
     detection_duration_ms: number;
 }
 
+// Root currency + namespaces for the registration screen; each half may fail on its own
+export interface RegistrationOptions {
+    root: NamespaceOption | null;
+    root_error: string | null;
+    namespaces: NamespaceOption[];
+    namespaces_error: string | null;
+}
+
+// Validated sub-ID registration (name.parent@) with its fee in the parent's fee currency
+export interface SubIdRegistrationPlan {
+    identity_name: string;
+    parent: NamespaceOption;
+    fee_currency: string;
+    base_fee: number;
+    referral_discount: number;
+    net_fee: number;
+}
+
 // NEW: VerusID registration types
 export interface NamespaceOption {
     name: string;
@@ -107,6 +307,7 @@ export interface NamespaceOption {
     registration_fee: number;
     fully_qualified_name: string;
     fee_currency_name: string; // NEW: The actual currency name for the registration fee (e.g. "VRSCTEST", "SECOND")
+    fee_currency_resolved: boolean; // false when getcurrency failed and fee_currency_name is the namespace's own currency
     options: number; // NEW: Currency options (33 or 41) - needed for referral system
     id_referral_levels: number; // NEW: Referral levels (0-5) - determines discount amount
 } 
\ No newline at end of file